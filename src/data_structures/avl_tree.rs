@@ -1,16 +1,49 @@
 use std::{
-    cmp::{max, Ordering},
+    cmp::{max, Ordering, Reverse},
+    collections::BinaryHeap,
+    fmt,
+    hash::{Hash, Hasher},
     iter::FromIterator,
     mem,
-    ops::Not,
+    ops::{Add, AddAssign, Bound, Not, RangeBounds, Sub},
 };
 
 /// An internal node of an `AVLTree`.
-struct AVLNode<T: Ord> {
+#[derive(Clone)]
+struct AVLNode<T, A = ()> {
     value: T,
     height: usize,
-    left: Option<Box<AVLNode<T>>>,
-    right: Option<Box<AVLNode<T>>>,
+    /// Number of nodes in the subtree rooted at this node.
+    size: usize,
+    /// Summary of the values in the subtree rooted at this node, such as their sum.
+    aggregate: A,
+    left: Option<Box<AVLNode<T, A>>>,
+    right: Option<Box<AVLNode<T, A>>>,
+}
+
+/// A summary of the values in a subtree, stored in every `AVLNode`.
+///
+/// `AVLNode::update` recomputes it from the summaries of the children whenever it recomputes the
+/// height and size, so it stays correct through insertions, removals and rotations.
+trait Aggregate<T> {
+    /// Summarizes a subtree from the summaries of its children and the value of its root.
+    fn aggregate(left: Option<&Self>, value: &T, right: Option<&Self>) -> Self;
+}
+
+/// Plain trees store no summary.
+impl<T> Aggregate<T> for () {
+    fn aggregate(_: Option<&Self>, _: &T, _: Option<&Self>) {}
+}
+
+/// The sum of the values in a subtree, as stored by `AVLSumTree`.
+#[derive(Clone, Copy)]
+struct SubtreeSum<T>(T);
+
+impl<T: Add<Output = T> + Copy + Default> Aggregate<T> for SubtreeSum<T> {
+    fn aggregate(left: Option<&Self>, value: &T, right: Option<&Self>) -> Self {
+        let sum = |child: Option<&Self>| child.map_or_else(T::default, |s| s.0);
+        SubtreeSum(sum(left) + *value + sum(right))
+    }
 }
 
 /// An AVL tree.
@@ -32,11 +65,37 @@ struct AVLNode<T: Ord> {
 /// assert!(tree.contains(&2));
 /// assert!(!tree.contains(&3));
 /// ```
+///
+/// Cloning a tree copies every node along with its height and size, so the clone has the same
+/// shape as the original and needs no rebalancing.
+#[derive(Clone)]
 pub struct AVLTree<T: Ord> {
     root: Option<Box<AVLNode<T>>>,
     length: usize,
 }
 
+/// Shape statistics of an `AVLTree`, as returned by `AVLTree::balance_stats`.
+///
+/// Depths are measured in edges from the root, so the root has depth 0. The minimum and maximum
+/// depths only consider leaves.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BalanceStats {
+    /// The height of the tree (0 for an empty tree).
+    pub height: usize,
+    /// The mean depth over all nodes (0.0 for an empty tree).
+    pub average_depth: f64,
+    /// The depth of the shallowest leaf.
+    pub min_depth: usize,
+    /// The depth of the deepest leaf.
+    pub max_depth: usize,
+    /// The number of nodes whose left subtree is taller (balance factor -1).
+    pub left_heavy: usize,
+    /// The number of nodes whose subtrees have equal height (balance factor 0).
+    pub balanced: usize,
+    /// The number of nodes whose right subtree is taller (balance factor +1).
+    pub right_heavy: usize,
+}
+
 /// Refers to the left or right subtree of an `AVLNode`.
 #[derive(Clone, Copy)]
 enum Side {
@@ -63,6 +122,85 @@ impl<T: Ord> AVLTree<T> {
         }
     }
 
+    /// Builds a tree from values in any order, dropping duplicates.
+    ///
+    /// The vector is sorted and deduplicated in place, then the tree is built bottom-up in O(n)
+    /// without any rotations, so the whole construction takes O(n log n) for the sort. Input that
+    /// is already strictly ascending skips the sort and is built in O(n).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rust_algorithms::data_structures::AVLTree;
+    ///
+    /// let tree = AVLTree::from_unsorted(vec![5, 1, 4, 1, 5, 9, 2, 6]);
+    ///
+    /// assert_eq!(tree.len(), 6);
+    /// assert!(tree.iter().eq([1, 2, 4, 5, 6, 9].iter()));
+    /// ```
+    pub fn from_unsorted(mut values: Vec<T>) -> AVLTree<T> {
+        if !values.windows(2).all(|pair| pair[0] < pair[1]) {
+            values.sort_unstable();
+            values.dedup();
+        }
+        Self::from_sorted_unchecked(values)
+    }
+
+    /// Builds a tree from values in ascending order in O(n), dropping duplicates.
+    ///
+    /// The middle value becomes the root and each half is built the same way below it, bottom-up
+    /// without any comparisons or rotations, so the tree is as balanced as possible. Equal values
+    /// must be next to each other in sorted input, so only the first of them is kept, as when
+    /// inserting them one by one.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the values are not sorted in ascending order.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rust_algorithms::data_structures::AVLTree;
+    ///
+    /// let tree = AVLTree::from_sorted(vec![1, 2, 2, 3, 5, 8]);
+    ///
+    /// assert_eq!(tree.len(), 5);
+    /// assert!(tree.iter().eq([1, 2, 3, 5, 8].iter()));
+    /// ```
+    pub fn from_sorted(mut values: Vec<T>) -> AVLTree<T> {
+        assert!(
+            values.windows(2).all(|pair| pair[0] <= pair[1]),
+            "values must be sorted in ascending order"
+        );
+        values.dedup();
+        Self::from_sorted_unchecked(values)
+    }
+
+    /// Builds a tree from strictly ascending values in O(n), without checking their order.
+    ///
+    /// This skips the pass over the values that `from_sorted` makes to check and deduplicate
+    /// them. If they are not strictly ascending after all, the tree is still balanced and holds
+    /// all of them, but its values are out of order, so searches may miss values that are
+    /// present.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rust_algorithms::data_structures::AVLTree;
+    ///
+    /// let tree = AVLTree::from_sorted_unchecked((0..1000).collect());
+    ///
+    /// assert_eq!(tree.len(), 1000);
+    /// assert!(tree.contains(&999));
+    /// ```
+    pub fn from_sorted_unchecked(values: Vec<T>) -> AVLTree<T> {
+        let length = values.len();
+        AVLTree {
+            root: build_balanced(length, &mut values.into_iter()),
+            length,
+        }
+    }
+
     /// Checks if the tree contains a value.
     ///
     /// # Arguments
@@ -87,15 +225,38 @@ impl<T: Ord> AVLTree<T> {
     /// assert!(!tree.contains(&2));
     /// ```
     pub fn contains(&self, value: &T) -> bool {
+        self.get(value).is_some()
+    }
+
+    /// Gets the value stored in the tree that is equal to `value`.
+    ///
+    /// This is useful when values are ordered by a key alone, since it returns the stored value
+    /// with all of its other fields instead of just reporting whether the key is present.
+    ///
+    /// # Returns
+    ///
+    /// A reference to the stored value, or `None` if the tree does not contain it.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rust_algorithms::data_structures::AVLTree;
+    ///
+    /// let tree: AVLTree<_> = vec![3, 1, 2].into_iter().collect();
+    ///
+    /// assert_eq!(tree.get(&2), Some(&2));
+    /// assert_eq!(tree.get(&4), None);
+    /// ```
+    pub fn get(&self, value: &T) -> Option<&T> {
         let mut current = &self.root;
         while let Some(node) = current {
             current = match value.cmp(&node.value) {
-                Ordering::Equal => return true,
+                Ordering::Equal => return Some(&node.value),
                 Ordering::Less => &node.left,
                 Ordering::Greater => &node.right,
             }
         }
-        false
+        None
     }
 
     /// Adds a value to the tree.
@@ -117,11 +278,70 @@ impl<T: Ord> AVLTree<T> {
     /// assert!(!tree.insert(1));
     /// ```
     pub fn insert(&mut self, value: T) -> bool {
-        let inserted = insert(&mut self.root, value);
-        if inserted {
+        self.insert_with_rank(value).is_some()
+    }
+
+    /// Adds a value to the tree, replacing an equal value if there is one.
+    ///
+    /// Unlike `insert`, which keeps the stored value and drops the new one, this swaps in the new
+    /// value, which matters when values are ordered by a key alone. Replacing a value leaves the
+    /// length and the shape of the tree unchanged.
+    ///
+    /// # Returns
+    ///
+    /// The replaced value, or `None` if the tree contained no equal value.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rust_algorithms::data_structures::AVLTree;
+    ///
+    /// let mut tree = AVLTree::new();
+    ///
+    /// assert_eq!(tree.replace(5), None);
+    /// assert_eq!(tree.replace(5), Some(5));
+    /// assert_eq!(tree.len(), 1);
+    /// ```
+    pub fn replace(&mut self, value: T) -> Option<T> {
+        let mut current = &mut self.root;
+        while let Some(node) = current {
+            current = match value.cmp(&node.value) {
+                Ordering::Equal => return Some(mem::replace(&mut node.value, value)),
+                Ordering::Less => &mut node.left,
+                Ordering::Greater => &mut node.right,
+            }
+        }
+        self.insert(value);
+        None
+    }
+
+    /// Adds a value to the tree and returns its position among the values.
+    ///
+    /// The rank is counted with the subtree sizes along the path the value takes down the tree,
+    /// so this takes O(log n) like `insert`.
+    ///
+    /// # Returns
+    ///
+    /// The number of smaller values in the tree, or `None` if the tree already contained the
+    /// value.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rust_algorithms::data_structures::AVLTree;
+    ///
+    /// let mut tree: AVLTree<_> = vec![0, 2, 4, 6].into_iter().collect();
+    ///
+    /// assert_eq!(tree.insert_with_rank(3), Some(2));
+    /// assert_eq!(tree.insert_with_rank(7), Some(5));
+    /// assert_eq!(tree.insert_with_rank(4), None);
+    /// ```
+    pub fn insert_with_rank(&mut self, value: T) -> Option<usize> {
+        let rank = insert(&mut self.root, value, &T::cmp);
+        if rank.is_some() {
             self.length += 1;
         }
-        inserted
+        rank
     }
 
     /// Removes a value from the tree.
@@ -144,13 +364,59 @@ impl<T: Ord> AVLTree<T> {
     /// assert!(!tree.remove(&1));
     /// ```
     pub fn remove(&mut self, value: &T) -> bool {
-        let removed = remove(&mut self.root, value);
+        let removed = remove(&mut self.root, &|x| value.cmp(x)).is_some();
         if removed {
             self.length -= 1;
         }
         removed
     }
 
+    /// Removes the smallest value from the tree.
+    ///
+    /// # Returns
+    ///
+    /// The smallest value, or `None` if the tree is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rust_algorithms::data_structures::AVLTree;
+    ///
+    /// let mut tree: AVLTree<_> = vec![2, 3, 1].into_iter().collect();
+    ///
+    /// assert_eq!(tree.pop_min(), Some(1));
+    /// assert_eq!(tree.pop_min(), Some(2));
+    /// assert_eq!(tree.len(), 1);
+    /// ```
+    pub fn pop_min(&mut self) -> Option<T> {
+        let node = take_min(&mut self.root)?;
+        self.length -= 1;
+        Some(node.value)
+    }
+
+    /// Removes the largest value from the tree.
+    ///
+    /// # Returns
+    ///
+    /// The largest value, or `None` if the tree is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rust_algorithms::data_structures::AVLTree;
+    ///
+    /// let mut tree: AVLTree<_> = vec![2, 3, 1].into_iter().collect();
+    ///
+    /// assert_eq!(tree.pop_max(), Some(3));
+    /// assert_eq!(tree.pop_max(), Some(2));
+    /// assert_eq!(tree.len(), 1);
+    /// ```
+    pub fn pop_max(&mut self) -> Option<T> {
+        let node = take_max(&mut self.root)?;
+        self.length -= 1;
+        Some(node.value)
+    }
+
     /// Returns the number of values in the tree.
     ///
     /// # Returns
@@ -189,319 +455,4204 @@ impl<T: Ord> AVLTree<T> {
         self.length == 0
     }
 
-    /// Returns an iterator that visits the nodes in the tree in order.
-    fn node_iter(&self) -> NodeIter<T> {
-        let cap = self.root.as_ref().map_or(0, |n| n.height);
-        let mut node_iter = NodeIter {
-            stack: Vec::with_capacity(cap),
-        };
-        // Initialize stack with path to leftmost child
-        let mut child = &self.root;
-        while let Some(node) = child {
-            node_iter.stack.push(node.as_ref());
-            child = &node.left;
+    /// Counts the values in the tree by visiting every node.
+    ///
+    /// Unlike `len`, this ignores the cached length and the subtree sizes stored in the nodes, so
+    /// comparing the two detects a length that has gone out of sync with the nodes. Takes O(n).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rust_algorithms::data_structures::AVLTree;
+    ///
+    /// let mut tree: AVLTree<_> = (0..10).collect();
+    /// tree.remove(&3);
+    ///
+    /// assert_eq!(tree.recomputed_len(), 9);
+    /// assert_eq!(tree.len(), tree.recomputed_len());
+    /// ```
+    pub fn recomputed_len(&self) -> usize {
+        let mut count = 0;
+        let mut stack: Vec<&AVLNode<T>> = self.root.iter().map(|n| n.as_ref()).collect();
+        while let Some(node) = stack.pop() {
+            count += 1;
+            stack.extend(
+                node.left
+                    .iter()
+                    .chain(node.right.iter())
+                    .map(|n| n.as_ref()),
+            );
         }
-        node_iter
+        count
     }
 
-    /// Gets an iterator that visits the values in the tree in ascending order.
+    /// Returns the smallest value in the tree.
+    ///
+    /// Follows the left links down from the root, taking O(log n) time without allocating.
     ///
     /// # Returns
     ///
-    /// An iterator that visits the values in the tree in ascending order.
+    /// The smallest value, or `None` if the tree is empty.
     ///
     /// # Examples
     ///
     /// ```rust
     /// use rust_algorithms::data_structures::AVLTree;
     ///
-    /// let tree: AVLTree<_> = (1..4).collect();
+    /// let tree: AVLTree<_> = vec![5, 2, 8].into_iter().collect();
     ///
-    /// assert_eq!(tree.iter().collect::<Vec<_>>(), vec![&1, &2, &3]);
+    /// assert_eq!(tree.min(), Some(&2));
+    /// assert_eq!(AVLTree::<i32>::new().min(), None);
     /// ```
-    pub fn iter(&self) -> Iter<T> {
-        Iter {
-            node_iter: self.node_iter(),
+    pub fn min(&self) -> Option<&T> {
+        let mut node = self.root.as_ref()?;
+        while let Some(left) = &node.left {
+            node = left;
         }
+        Some(&node.value)
     }
-}
 
-/// Recursive helper function for `AVLTree` insertion.
-fn insert<T: Ord>(tree: &mut Option<Box<AVLNode<T>>>, value: T) -> bool {
-    if let Some(node) = tree {
-        let inserted = match value.cmp(&node.value) {
-            Ordering::Equal => false,
-            Ordering::Less => insert(&mut node.left, value),
-            Ordering::Greater => insert(&mut node.right, value),
-        };
-        if inserted {
-            node.rebalance();
+    /// Returns the largest value in the tree.
+    ///
+    /// Follows the right links down from the root, taking O(log n) time without allocating.
+    ///
+    /// # Returns
+    ///
+    /// The largest value, or `None` if the tree is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rust_algorithms::data_structures::AVLTree;
+    ///
+    /// let tree: AVLTree<_> = vec![5, 2, 8].into_iter().collect();
+    ///
+    /// assert_eq!(tree.max(), Some(&8));
+    /// assert_eq!(AVLTree::<i32>::new().max(), None);
+    /// ```
+    pub fn max(&self) -> Option<&T> {
+        let mut node = self.root.as_ref()?;
+        while let Some(right) = &node.right {
+            node = right;
         }
-        inserted
-    } else {
-        *tree = Some(Box::new(AVLNode {
-            value,
-            height: 1,
-            left: None,
-            right: None,
-        }));
-        true
+        Some(&node.value)
     }
-}
 
-/// Recursive helper function for `AVLTree` deletion.
-fn remove<T: Ord>(tree: &mut Option<Box<AVLNode<T>>>, value: &T) -> bool {
-    if let Some(node) = tree {
-        let removed = match value.cmp(&node.value) {
-            Ordering::Less => remove(&mut node.left, value),
-            Ordering::Greater => remove(&mut node.right, value),
-            Ordering::Equal => {
-                *tree = match (node.left.take(), node.right.take()) {
-                    (None, None) => None,
-                    (Some(b), None) | (None, Some(b)) => Some(b),
-                    (Some(left), Some(right)) => Some(merge(left, right)),
-                };
-                return true;
+    /// Finds the largest value in the tree that is less than or equal to `value`.
+    ///
+    /// Descends from the root, remembering the last node at which the search went right, so this
+    /// takes O(log n) time.
+    ///
+    /// # Returns
+    ///
+    /// The value equal to `value` if the tree contains it, otherwise the closest smaller value, or
+    /// `None` if every value is greater.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rust_algorithms::data_structures::AVLTree;
+    ///
+    /// let tree: AVLTree<_> = vec![10, 20, 30].into_iter().collect();
+    ///
+    /// assert_eq!(tree.floor(&25), Some(&20));
+    /// assert_eq!(tree.floor(&20), Some(&20));
+    /// assert_eq!(tree.floor(&5), None);
+    /// ```
+    pub fn floor(&self, value: &T) -> Option<&T> {
+        let mut best = None;
+        let mut current = &self.root;
+        while let Some(node) = current {
+            current = match value.cmp(&node.value) {
+                Ordering::Equal => return Some(&node.value),
+                Ordering::Less => &node.left,
+                Ordering::Greater => {
+                    best = Some(&node.value);
+                    &node.right
+                }
             }
-        };
-        if removed {
-            node.rebalance();
         }
-        removed
-    } else {
-        false
+        best
     }
-}
 
-/// Merges two trees and returns the root of the merged tree.
-fn merge<T: Ord>(left: Box<AVLNode<T>>, right: Box<AVLNode<T>>) -> Box<AVLNode<T>> {
-    let mut op_right = Some(right);
-    // Guaranteed not to panic since right has at least one node
-    let mut root = take_min(&mut op_right).unwrap();
-    root.left = Some(left);
-    root.right = op_right;
-    root.rebalance();
-    root
-}
-
-/// Removes the smallest node from the tree, if one exists.
-fn take_min<T: Ord>(tree: &mut Option<Box<AVLNode<T>>>) -> Option<Box<AVLNode<T>>> {
-    if let Some(mut node) = tree.take() {
-        // Recurse along the left side
-        if let Some(small) = take_min(&mut node.left) {
-            // Took the smallest from below; update this node and put it back in the tree
-            node.rebalance();
-            *tree = Some(node);
-            Some(small)
-        } else {
-            // Take this node and replace it with its right child
-            *tree = node.right.take();
-            Some(node)
+    /// Finds the smallest value in the tree that is greater than or equal to `value`.
+    ///
+    /// Descends from the root, remembering the last node at which the search went left, so this
+    /// takes O(log n) time.
+    ///
+    /// # Returns
+    ///
+    /// The value equal to `value` if the tree contains it, otherwise the closest greater value, or
+    /// `None` if every value is smaller.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rust_algorithms::data_structures::AVLTree;
+    ///
+    /// let tree: AVLTree<_> = vec![10, 20, 30].into_iter().collect();
+    ///
+    /// assert_eq!(tree.ceil(&25), Some(&30));
+    /// assert_eq!(tree.ceil(&20), Some(&20));
+    /// assert_eq!(tree.ceil(&35), None);
+    /// ```
+    pub fn ceil(&self, value: &T) -> Option<&T> {
+        let mut best = None;
+        let mut current = &self.root;
+        while let Some(node) = current {
+            current = match value.cmp(&node.value) {
+                Ordering::Equal => return Some(&node.value),
+                Ordering::Less => {
+                    best = Some(&node.value);
+                    &node.left
+                }
+                Ordering::Greater => &node.right,
+            }
         }
-    } else {
-        None
+        best
     }
-}
 
-impl<T: Ord> AVLNode<T> {
-    /// Returns a reference to the left or right child.
-    fn child(&self, side: Side) -> &Option<Box<AVLNode<T>>> {
-        match side {
-            Side::Left => &self.left,
-            Side::Right => &self.right,
-        }
-    }
-
-    /// Returns a mutable reference to the left or right child.
-    fn child_mut(&mut self, side: Side) -> &mut Option<Box<AVLNode<T>>> {
-        match side {
-            Side::Left => &mut self.left,
-            Side::Right => &mut self.right,
+    /// Finds the largest value in the tree that is strictly less than `value`.
+    ///
+    /// Unlike `floor`, a value equal to `value` is skipped, so this steps to the neighbour of a
+    /// value stored in the tree. `value` itself does not need to be in the tree. Takes O(log n)
+    /// time.
+    ///
+    /// # Returns
+    ///
+    /// The closest smaller value, or `None` if no value in the tree is smaller.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rust_algorithms::data_structures::AVLTree;
+    ///
+    /// let tree: AVLTree<_> = vec![10, 20, 30].into_iter().collect();
+    ///
+    /// assert_eq!(tree.predecessor(&20), Some(&10));
+    /// assert_eq!(tree.predecessor(&25), Some(&20));
+    /// assert_eq!(tree.predecessor(&10), None);
+    /// ```
+    pub fn predecessor(&self, value: &T) -> Option<&T> {
+        let mut best = None;
+        let mut current = &self.root;
+        while let Some(node) = current {
+            current = if &node.value < value {
+                best = Some(&node.value);
+                &node.right
+            } else {
+                &node.left
+            };
         }
+        best
     }
 
-    /// Returns the height of the left or right subtree.
-    fn height(&self, side: Side) -> usize {
-        self.child(side).as_ref().map_or(0, |n| n.height)
-    }
-
-    /// Returns the height difference between the left and right subtrees.
-    fn balance_factor(&self) -> i8 {
-        let (left, right) = (self.height(Side::Left), self.height(Side::Right));
-        if left < right {
-            (right - left) as i8
-        } else {
-            -((left - right) as i8)
+    /// Finds the smallest value in the tree that is strictly greater than `value`.
+    ///
+    /// Unlike `ceil`, a value equal to `value` is skipped, so this steps to the neighbour of a
+    /// value stored in the tree. `value` itself does not need to be in the tree. Takes O(log n)
+    /// time.
+    ///
+    /// # Returns
+    ///
+    /// The closest greater value, or `None` if no value in the tree is greater.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rust_algorithms::data_structures::AVLTree;
+    ///
+    /// let tree: AVLTree<_> = vec![10, 20, 30].into_iter().collect();
+    ///
+    /// assert_eq!(tree.successor(&20), Some(&30));
+    /// assert_eq!(tree.successor(&15), Some(&20));
+    /// assert_eq!(tree.successor(&30), None);
+    /// ```
+    pub fn successor(&self, value: &T) -> Option<&T> {
+        let mut best = None;
+        let mut current = &self.root;
+        while let Some(node) = current {
+            current = if &node.value > value {
+                best = Some(&node.value);
+                &node.left
+            } else {
+                &node.right
+            };
         }
+        best
     }
 
-    /// Recomputes the `height` field.
-    fn update_height(&mut self) {
-        self.height = 1 + max(self.height(Side::Left), self.height(Side::Right));
+    /// Counts the values in the tree that are less than `value`.
+    ///
+    /// Every node stores the size of its subtree, so this follows a single path down the tree and
+    /// takes O(log n).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rust_algorithms::data_structures::AVLTree;
+    ///
+    /// let tree: AVLTree<_> = (0..10).collect();
+    ///
+    /// assert_eq!(tree.count_less(&3), 3);
+    /// assert_eq!(tree.count_less(&20), 10);
+    /// ```
+    pub fn count_less(&self, value: &T) -> usize {
+        let mut count = 0;
+        let mut current = &self.root;
+        while let Some(node) = current {
+            if *value <= node.value {
+                current = &node.left;
+            } else {
+                count += node.size(Side::Left) + 1;
+                current = &node.right;
+            }
+        }
+        count
     }
 
-    /// Performs a left or right rotation.
-    fn rotate(&mut self, side: Side) {
-        let mut subtree = self.child_mut(!side).take().unwrap();
-        *self.child_mut(!side) = subtree.child_mut(side).take();
-        self.update_height();
-        // Swap root and child nodes in memory
-        mem::swap(self, subtree.as_mut());
-        // Set old root (subtree) as child of new root (self)
-        *self.child_mut(side) = Some(subtree);
-        self.update_height();
+    /// Counts the values in the tree that are greater than `value`.
+    ///
+    /// Together with `count_less`, `count_less(v) + count_greater(v) + contains(v) as usize` always
+    /// equals `len()`. Takes O(log n).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rust_algorithms::data_structures::AVLTree;
+    ///
+    /// let tree: AVLTree<_> = (0..10).collect();
+    ///
+    /// assert_eq!(tree.count_greater(&3), 6);
+    /// assert_eq!(tree.count_greater(&-1), 10);
+    /// ```
+    pub fn count_greater(&self, value: &T) -> usize {
+        let mut count = 0;
+        let mut current = &self.root;
+        while let Some(node) = current {
+            if *value >= node.value {
+                current = &node.right;
+            } else {
+                count += node.size(Side::Right) + 1;
+                current = &node.left;
+            }
+        }
+        count
     }
 
-    /// Performs left or right tree rotations to balance this node.
-    fn rebalance(&mut self) {
-        self.update_height();
-        let side = match self.balance_factor() {
-            -2 => Side::Left,
-            2 => Side::Right,
-            _ => return,
-        };
-        let subtree = self.child_mut(side).as_mut().unwrap();
-        // Left-Right and Right-Left require rotation of heavy subtree
-        if let (Side::Left, 1) | (Side::Right, -1) = (side, subtree.balance_factor()) {
-            subtree.rotate(side);
+    /// Returns the `k`-th smallest value in the tree, counting from 0.
+    ///
+    /// Every node stores the size of its subtree, so the search can tell from the size of the left
+    /// subtree whether to descend left, right, or stop. Takes O(log n).
+    ///
+    /// # Returns
+    ///
+    /// The value with exactly `k` smaller values in the tree, or `None` if `k >= len()`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rust_algorithms::data_structures::AVLTree;
+    ///
+    /// let tree: AVLTree<_> = vec![50, 10, 40, 20, 30].into_iter().collect();
+    ///
+    /// assert_eq!(tree.select(0), Some(&10));
+    /// assert_eq!(tree.select(3), Some(&40));
+    /// assert_eq!(tree.select(5), None);
+    /// ```
+    pub fn select(&self, mut k: usize) -> Option<&T> {
+        let mut current = &self.root;
+        while let Some(node) = current {
+            let left = node.size(Side::Left);
+            current = match k.cmp(&left) {
+                Ordering::Less => &node.left,
+                Ordering::Equal => return Some(&node.value),
+                Ordering::Greater => {
+                    k -= left + 1;
+                    &node.right
+                }
+            }
         }
-        // Rotate in opposite direction of heavy side
-        self.rotate(!side);
+        None
     }
-}
 
-/// Default implementation for `AVLTree`.
-///
-/// Creates an empty `AVLTree`.
-impl<T: Ord> Default for AVLTree<T> {
-    /// Creates an empty `AVLTree`.
+    /// Returns the value at position `index` in ascending order, counting from 0.
+    ///
+    /// This is `select` under the name of `Iterator::nth`, and equals `iter().nth(index)`, but
+    /// takes O(log n) instead of walking past the first `index` values.
+    ///
+    /// # Returns
+    ///
+    /// The value at `index`, or `None` if `index >= len()`.
     ///
     /// # Examples
     ///
     /// ```rust
     /// use rust_algorithms::data_structures::AVLTree;
     ///
-    /// let tree: AVLTree<i32> = Default::default();
+    /// let tree: AVLTree<_> = vec![50, 10, 40, 20, 30].into_iter().collect();
     ///
-    /// assert!(tree.is_empty());
+    /// assert_eq!(tree.nth(1), Some(&20));
+    /// assert_eq!(tree.nth(5), None);
     /// ```
-    fn default() -> Self {
-        Self::new()
+    pub fn nth(&self, index: usize) -> Option<&T> {
+        self.select(index)
     }
-}
-
-impl Not for Side {
-    type Output = Side;
 
-    fn not(self) -> Self::Output {
-        match self {
-            Side::Left => Side::Right,
-            Side::Right => Side::Left,
-        }
+    /// Returns the number of values in the tree that are less than `value`.
+    ///
+    /// This is the position `value` has or would have in the sorted order, so `select` and `rank`
+    /// are inverse to each other for values in the tree. Takes O(log n), like `count_less`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rust_algorithms::data_structures::AVLTree;
+    ///
+    /// let tree: AVLTree<_> = vec![50, 10, 40, 20, 30].into_iter().collect();
+    ///
+    /// assert_eq!(tree.rank(&40), 3);
+    /// assert_eq!(tree.rank(&35), 3);
+    /// assert_eq!(tree.select(tree.rank(&20)), Some(&20));
+    /// ```
+    pub fn rank(&self, value: &T) -> usize {
+        self.count_less(value)
     }
-}
 
-impl<T: Ord> FromIterator<T> for AVLTree<T> {
-    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
-        let mut tree = AVLTree::new();
-        for value in iter {
-            tree.insert(value);
-        }
-        tree
+    /// Counts the distinct values in the tree that lie within `range`.
+    ///
+    /// The tree holds every value once, however often it was inserted, so this counts values
+    /// without multiplicity. The count is derived from `count_less` and `count_greater` at both
+    /// bounds, which takes O(log n) regardless of how many values lie in the range.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rust_algorithms::data_structures::AVLTree;
+    ///
+    /// let tree: AVLTree<_> = vec![1, 3, 3, 3, 5, 7, 7, 9].into_iter().collect();
+    ///
+    /// assert_eq!(tree.distinct_count_range(3..=7), 3);
+    /// assert_eq!(tree.distinct_count_range(..4), 2);
+    /// assert_eq!(tree.distinct_count_range(10..), 0);
+    /// ```
+    pub fn distinct_count_range<R: RangeBounds<T>>(&self, range: R) -> usize {
+        let before = match range.start_bound() {
+            Bound::Included(start) => self.count_less(start),
+            Bound::Excluded(start) => self.length - self.count_greater(start),
+            Bound::Unbounded => 0,
+        };
+        let after = match range.end_bound() {
+            Bound::Included(end) => self.count_greater(end),
+            Bound::Excluded(end) => self.length - self.count_less(end),
+            Bound::Unbounded => 0,
+        };
+        // The two counts overlap for empty ranges
+        self.length.saturating_sub(before + after)
     }
-}
 
-/// An iterator over the nodes of an `AVLTree`.
-///
-/// This struct is created by the `node_iter` method of `AVLTree`.
-struct NodeIter<'a, T: Ord> {
-    stack: Vec<&'a AVLNode<T>>,
-}
+    /// Counts the values in the tree that lie within `range`, without visiting them.
+    ///
+    /// This is the rank of the end bound minus the rank of the start bound, adjusted for whether
+    /// each bound is included, and equals `range(range).count()`. Since the tree holds every value
+    /// once, it is the same as `distinct_count_range`. Takes O(log n).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rust_algorithms::data_structures::AVLTree;
+    ///
+    /// let tree: AVLTree<_> = (0..100).step_by(10).collect();
+    ///
+    /// assert_eq!(tree.count_range(25..=60), 4);
+    /// assert_eq!(tree.count_range(20..60), 4);
+    /// assert_eq!(tree.count_range(91..), 0);
+    /// ```
+    pub fn count_range<R: RangeBounds<T>>(&self, range: R) -> usize {
+        self.distinct_count_range(range)
+    }
 
-/// An iterator over the nodes of an `AVLTree`.
-///
-/// This struct is created by the `node_iter` method of `AVLTree`.
-impl<'a, T: Ord> Iterator for NodeIter<'a, T> {
-    type Item = &'a AVLNode<T>;
+    /// Returns the first and last values for which `f` returns `Ordering::Equal`.
+    ///
+    /// `f` must be consistent with the order of the tree, returning `Less` for all values before
+    /// the range and `Greater` for all values after it, for example by comparing only a prefix of
+    /// each value. Takes O(log n).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rust_algorithms::data_structures::AVLTree;
+    ///
+    /// let tree: AVLTree<_> = vec![(1, 'a'), (2, 'a'), (2, 'c'), (2, 'd'), (3, 'b')]
+    ///     .into_iter()
+    ///     .collect();
+    ///
+    /// assert_eq!(
+    ///     tree.equal_range_by(|x| x.0.cmp(&2)),
+    ///     (Some(&(2, 'a')), Some(&(2, 'd')))
+    /// );
+    /// assert_eq!(tree.equal_range_by(|x| x.0.cmp(&4)), (None, None));
+    /// ```
+    pub fn equal_range_by<F: Fn(&T) -> Ordering>(&self, f: F) -> (Option<&T>, Option<&T>) {
+        equal_range_by(&self.root, f)
+    }
 
-    fn next(&mut self) -> Option<Self::Item> {
-        if let Some(node) = self.stack.pop() {
-            // Push left path of right subtree to stack
-            let mut child = &node.right;
-            while let Some(subtree) = child {
-                self.stack.push(subtree.as_ref());
-                child = &subtree.left;
-            }
-            Some(node)
-        } else {
-            None
-        }
+    /// Returns the first and last values equal to `value`.
+    ///
+    /// Since the tree holds no two equal values, both are the same value if it is present, and
+    /// `None` otherwise. See `equal_range_by` for probing with a coarser ordering.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rust_algorithms::data_structures::AVLTree;
+    ///
+    /// let tree: AVLTree<_> = vec![1, 3, 5].into_iter().collect();
+    ///
+    /// assert_eq!(tree.equal_range(&3), (Some(&3), Some(&3)));
+    /// assert_eq!(tree.equal_range(&4), (None, None));
+    /// ```
+    pub fn equal_range(&self, value: &T) -> (Option<&T>, Option<&T>) {
+        self.equal_range_by(|x| x.cmp(value))
     }
-}
 
-/// An iterator over the items of an `AVLTree`.
-///
-/// This struct is created by the `iter` method of `AVLTree`.
-pub struct Iter<'a, T: Ord> {
-    node_iter: NodeIter<'a, T>,
-}
+    /// Keeps only the values for which `f` returns `true`, visiting them in ascending order.
+    ///
+    /// Like `retain_returning_removed`, the remaining values are rebuilt into a perfectly balanced
+    /// tree, which takes O(n) regardless of how many values are removed. Removing only a few
+    /// values is cheaper with `remove`, at O(log n) each.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rust_algorithms::data_structures::AVLTree;
+    ///
+    /// let mut tree: AVLTree<_> = (0..10).collect();
+    /// tree.retain(|&x| x % 3 == 0);
+    ///
+    /// assert!(tree.iter().eq([0, 3, 6, 9].iter()));
+    /// ```
+    pub fn retain<F: FnMut(&T) -> bool>(&mut self, f: F) {
+        self.retain_returning_removed(f);
+    }
 
-/// An iterator over the items of an `AVLTree`.
-///
-/// This struct is created by the `iter` method of `AVLTree`.
-impl<'a, T: Ord> Iterator for Iter<'a, T> {
-    type Item = &'a T;
+    /// Keeps only the values for which `f` returns `true` and rebuilds the tree.
+    ///
+    /// Every value is visited once in ascending order, and the remaining values are rebuilt into a
+    /// perfectly balanced tree, which takes O(n) overall.
+    ///
+    /// # Returns
+    ///
+    /// The number of values that were removed.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rust_algorithms::data_structures::AVLTree;
+    ///
+    /// let mut tree: AVLTree<_> = (0..10).collect();
+    ///
+    /// assert_eq!(tree.retain_returning_removed(|&x| x >= 7), 7);
+    /// assert_eq!(tree.len(), 3);
+    /// ```
+    pub fn retain_returning_removed<F: FnMut(&T) -> bool>(&mut self, f: F) -> usize {
+        let mut values = Vec::with_capacity(self.length);
+        into_values(self.root.take(), &mut values);
+        // The tree stays empty if `f` panics, so its length must match
+        self.length = 0;
+        let before = values.len();
+        values.retain(f);
+        let removed = before - values.len();
+        self.length = values.len();
+        self.root = build_balanced(self.length, &mut values.into_iter());
+        removed
+    }
 
-    /// Returns the next value in the tree.
+    /// Moves every value greater than or equal to `key` into a new tree, keeping the smaller
+    /// values in `self`.
+    ///
+    /// The tree is split along the search path of `key`, and the subtrees hanging off the path are
+    /// joined back into two AVL trees, each join rebalancing only along the spine of the taller
+    /// tree. This takes O(log n) and keeps both trees balanced.
     ///
     /// # Returns
     ///
-    /// The next value in the tree, or `None` if there are no more values.
+    /// A tree of the values that are at least `key`.
     ///
     /// # Examples
     ///
     /// ```rust
     /// use rust_algorithms::data_structures::AVLTree;
     ///
-    /// let tree: AVLTree<_> = (1..4).collect();
-    /// let mut iter = tree.iter();
+    /// let mut tree: AVLTree<_> = (0..10).collect();
+    /// let high = tree.split_off(&4);
     ///
-    /// assert_eq!(iter.next(), Some(&1));
-    /// assert_eq!(iter.next(), Some(&2));
-    /// assert_eq!(iter.next(), Some(&3));
-    /// assert_eq!(iter.next(), None);
+    /// assert!(tree.iter().eq([0, 1, 2, 3].iter()));
+    /// assert!(high.iter().eq([4, 5, 6, 7, 8, 9].iter()));
     /// ```
-    fn next(&mut self) -> Option<&'a T> {
-        match self.node_iter.next() {
-            Some(node) => Some(&node.value),
-            None => None,
-        }
+    pub fn split_off(&mut self, key: &T) -> AVLTree<T> {
+        let (low, high) = split(self.root.take(), key);
+        self.root = low;
+        let length = self.root.as_ref().map_or(0, |n| n.size);
+        let other = AVLTree {
+            root: high,
+            length: self.length - length,
+        };
+        self.length = length;
+        other
     }
-}
 
-#[cfg(test)]
+    /// Collects shape statistics of the tree in a single traversal.
+    ///
+    /// # Returns
+    ///
+    /// The height, depth distribution and balance factor counts of the tree.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rust_algorithms::data_structures::AVLTree;
+    ///
+    /// let tree: AVLTree<_> = (1..8).collect();
+    /// let stats = tree.balance_stats();
+    ///
+    /// assert_eq!(stats.height, 3);
+    /// assert_eq!(stats.balanced, 7);
+    /// ```
+    pub fn balance_stats(&self) -> BalanceStats {
+        let mut stats = BalanceStats {
+            height: self.root.as_ref().map_or(0, |n| n.height),
+            average_depth: 0.0,
+            min_depth: 0,
+            max_depth: 0,
+            left_heavy: 0,
+            balanced: 0,
+            right_heavy: 0,
+        };
+        let mut total_depth = 0;
+        let mut min_depth = usize::MAX;
+        let mut stack: Vec<(&AVLNode<T>, usize)> =
+            self.root.iter().map(|n| (n.as_ref(), 0)).collect();
+        while let Some((node, depth)) = stack.pop() {
+            total_depth += depth;
+            match node.balance_factor() {
+                -1 => stats.left_heavy += 1,
+                0 => stats.balanced += 1,
+                1 => stats.right_heavy += 1,
+                _ => {}
+            }
+            if node.left.is_none() && node.right.is_none() {
+                min_depth = min_depth.min(depth);
+                stats.max_depth = stats.max_depth.max(depth);
+            }
+            for child in node.left.iter().chain(node.right.iter()) {
+                stack.push((child.as_ref(), depth + 1));
+            }
+        }
+        if !self.is_empty() {
+            stats.average_depth = total_depth as f64 / self.length as f64;
+            stats.min_depth = min_depth;
+        }
+        stats
+    }
+
+    /// Returns the height of the tree, the number of nodes on its longest path from the root.
+    ///
+    /// # Returns
+    ///
+    /// The height, or 0 for an empty tree.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rust_algorithms::data_structures::AVLTree;
+    ///
+    /// let mut tree = AVLTree::new();
+    /// assert_eq!(tree.height(), 0);
+    ///
+    /// for value in 1..=7 {
+    ///     tree.insert(value);
+    /// }
+    /// assert_eq!(tree.height(), 3);
+    /// ```
+    pub fn height(&self) -> usize {
+        self.root.as_ref().map_or(0, |n| n.height)
+    }
+
+    /// Checks that the subtrees of every node differ in height by at most 1.
+    ///
+    /// The heights are recomputed from the leaves up rather than read from the nodes, so this also
+    /// catches nodes whose stored height is stale. Takes O(n).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rust_algorithms::data_structures::AVLTree;
+    ///
+    /// let tree: AVLTree<_> = (0..100).collect();
+    ///
+    /// assert!(tree.is_balanced());
+    /// ```
+    pub fn is_balanced(&self) -> bool {
+        balanced_height(&self.root).is_some()
+    }
+
+    /// Checks if two trees have the same shape with equal values at every position.
+    ///
+    /// This is stricter than `==`, which only compares the values in order: the same values
+    /// inserted in a different order can end up arranged differently by the rotations.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the trees have the same structure and values, `false` otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rust_algorithms::data_structures::AVLTree;
+    ///
+    /// let a: AVLTree<_> = vec![2, 1, 3, 4].into_iter().collect();
+    /// let b: AVLTree<_> = vec![3, 2, 4, 1].into_iter().collect();
+    ///
+    /// assert!(a == b);
+    /// assert!(!a.structural_eq(&b));
+    /// ```
+    pub fn structural_eq(&self, other: &AVLTree<T>) -> bool {
+        self.length == other.length && structurally_equal(&self.root, &other.root)
+    }
+
+    /// Returns an iterator that visits the nodes in the tree in order.
+    fn node_iter(&self) -> NodeIter<'_, T> {
+        NodeIter::new(&self.root)
+    }
+
+    /// Gets an iterator that visits the values in the tree in ascending order.
+    ///
+    /// # Returns
+    ///
+    /// An iterator that visits the values in the tree in ascending order.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rust_algorithms::data_structures::AVLTree;
+    ///
+    /// let tree: AVLTree<_> = (1..4).collect();
+    ///
+    /// assert_eq!(tree.iter().collect::<Vec<_>>(), vec![&1, &2, &3]);
+    /// ```
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            node_iter: self.node_iter(),
+        }
+    }
+
+    /// Gets an iterator over the values within `range` in ascending order.
+    ///
+    /// The iterator starts from the path to the first value in the range, skipping every subtree
+    /// that lies entirely below the start bound, and stops right after the last value in the
+    /// range, which is counted up front from the subtree sizes. Visiting `k` values therefore
+    /// takes O(log n + k) rather than a walk over the whole tree. The iterator is double-ended,
+    /// starting from the path to the last value when iterating from the back.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rust_algorithms::data_structures::AVLTree;
+    ///
+    /// let tree: AVLTree<_> = (0..100).step_by(10).collect();
+    ///
+    /// assert_eq!(tree.range(25..=60).collect::<Vec<_>>(), vec![&30, &40, &50, &60]);
+    /// assert_eq!(tree.range(..20).collect::<Vec<_>>(), vec![&0, &10]);
+    /// assert_eq!(tree.range(91..).next(), None);
+    /// ```
+    pub fn range<R: RangeBounds<T>>(&self, range: R) -> Range<'_, T> {
+        let below_start = |value: &T| match range.start_bound() {
+            Bound::Included(start) => value < start,
+            Bound::Excluded(start) => value <= start,
+            Bound::Unbounded => false,
+        };
+        let above_end = |value: &T| match range.end_bound() {
+            Bound::Included(end) => value > end,
+            Bound::Excluded(end) => value >= end,
+            Bound::Unbounded => false,
+        };
+
+        // Only the nodes at which the search turns left lie within or after the range, and only
+        // those at which it turns right lie within or before it
+        let cap = self.root.as_ref().map_or(0, |n| n.height);
+        let (mut front, mut back) = (Vec::with_capacity(cap), Vec::with_capacity(cap));
+        let mut current = &self.root;
+        while let Some(node) = current {
+            if below_start(&node.value) {
+                current = &node.right;
+            } else {
+                front.push(node.as_ref());
+                current = &node.left;
+            }
+        }
+        let mut current = &self.root;
+        while let Some(node) = current {
+            if above_end(&node.value) {
+                current = &node.left;
+            } else {
+                back.push(node.as_ref());
+                current = &node.right;
+            }
+        }
+        // The range is empty if its last value comes before its first one
+        let remaining = match (front.last(), back.last()) {
+            (Some(first), Some(last)) if first.value <= last.value => {
+                self.count_less(&last.value) + 1 - self.count_less(&first.value)
+            }
+            _ => 0,
+        };
+        Range {
+            node_iter: NodeIter {
+                front,
+                back,
+                remaining,
+            },
+        }
+    }
+
+    /// Gets an iterator over the values greater than or equal to `value` in ascending order.
+    ///
+    /// Like `std::lower_bound` in C++, the iterator starts at the first value not less than
+    /// `value`, which is `ceil(value)`. It is the same as `range(value..)` and takes O(log n) to
+    /// create.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rust_algorithms::data_structures::AVLTree;
+    ///
+    /// let tree: AVLTree<_> = (0..10).step_by(2).collect();
+    ///
+    /// assert_eq!(tree.lower_bound(&4).collect::<Vec<_>>(), vec![&4, &6, &8]);
+    /// assert_eq!(tree.lower_bound(&5).next(), Some(&6));
+    /// assert_eq!(tree.lower_bound(&9).next(), None);
+    /// ```
+    pub fn lower_bound(&self, value: &T) -> Range<'_, T> {
+        self.range((Bound::Included(value), Bound::Unbounded))
+    }
+
+    /// Gets an iterator over the values greater than `value` in ascending order.
+    ///
+    /// Like `std::upper_bound` in C++, the iterator starts at the first value greater than
+    /// `value`, which is `successor(value)`. So `value` itself, if present, is the only value
+    /// yielded by `lower_bound` but not here. Takes O(log n) to create.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rust_algorithms::data_structures::AVLTree;
+    ///
+    /// let tree: AVLTree<_> = (0..10).step_by(2).collect();
+    ///
+    /// assert_eq!(tree.upper_bound(&4).collect::<Vec<_>>(), vec![&6, &8]);
+    /// assert_eq!(tree.upper_bound(&5).next(), Some(&6));
+    /// assert_eq!(tree.upper_bound(&8).next(), None);
+    /// ```
+    pub fn upper_bound(&self, value: &T) -> Range<'_, T> {
+        self.range((Bound::Excluded(value), Bound::Unbounded))
+    }
+
+    /// Gets an iterator over successive groups of `n` values in ascending order.
+    ///
+    /// Every group holds exactly `n` values, except possibly the last one, which holds the rest.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is 0.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rust_algorithms::data_structures::AVLTree;
+    ///
+    /// let tree: AVLTree<_> = (1..6).collect();
+    /// let mut chunks = tree.chunks(2);
+    ///
+    /// assert_eq!(chunks.next(), Some(vec![&1, &2]));
+    /// assert_eq!(chunks.next(), Some(vec![&3, &4]));
+    /// assert_eq!(chunks.next(), Some(vec![&5]));
+    /// assert_eq!(chunks.next(), None);
+    /// ```
+    pub fn chunks(&self, n: usize) -> impl Iterator<Item = Vec<&T>> + '_ {
+        assert!(n > 0, "chunk size must be positive");
+        let mut values = self.iter();
+        std::iter::from_fn(move || {
+            let chunk: Vec<&T> = values.by_ref().take(n).collect();
+            if chunk.is_empty() {
+                None
+            } else {
+                Some(chunk)
+            }
+        })
+    }
+
+    /// Consumes the tree, yielding its values in descending order.
+    ///
+    /// This mirrors `into_iter`, moving the boxed nodes down the right spine onto a stack instead
+    /// of the left one, so the values are moved out without cloning and without collecting them
+    /// into a `Vec` first. The stack never holds more than the height of the tree.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rust_algorithms::data_structures::AVLTree;
+    ///
+    /// let tree: AVLTree<_> = vec![3, 1, 2].into_iter().collect();
+    ///
+    /// assert_eq!(tree.into_iter_rev().collect::<Vec<_>>(), vec![3, 2, 1]);
+    /// ```
+    pub fn into_iter_rev(self) -> IntoIterRev<T> {
+        let mut into_iter = IntoIterRev {
+            stack: Vec::with_capacity(self.root.as_ref().map_or(0, |n| n.height)),
+        };
+        into_iter.push_right_spine(self.root);
+        into_iter
+    }
+
+    /// Consumes the tree, moving its values into a `Vec` in ascending order.
+    ///
+    /// The values are moved out by `into_iter` without cloning, into a `Vec` allocated with the
+    /// length of the tree up front. Takes O(n).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rust_algorithms::data_structures::AVLTree;
+    ///
+    /// let tree: AVLTree<_> = vec![3, 1, 2].into_iter().collect();
+    ///
+    /// assert_eq!(tree.into_sorted_vec(), vec![1, 2, 3]);
+    /// ```
+    pub fn into_sorted_vec(self) -> Vec<T> {
+        let mut values = Vec::with_capacity(self.length);
+        values.extend(self);
+        values
+    }
+
+    /// Merges many trees into one tree holding every distinct value.
+    ///
+    /// The trees are consumed by their ascending `into_iter`s, which are merged through a min-heap
+    /// holding the next value of each tree, so no value is cloned. Duplicates are dropped as they
+    /// come out of the heap adjacent to each other. The merged values are then built into a
+    /// perfectly balanced tree. For N values in k trees this takes O(N log k).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rust_algorithms::data_structures::AVLTree;
+    ///
+    /// let shards: Vec<AVLTree<_>> = vec![(0..5).collect(), (3..8).collect(), (10..12).collect()];
+    /// let tree = AVLTree::union_all(shards);
+    ///
+    /// assert_eq!(tree.len(), 10);
+    /// assert!(tree.contains(&7) && !tree.contains(&8));
+    /// ```
+    pub fn union_all(trees: Vec<AVLTree<T>>) -> AVLTree<T> {
+        let mut iters: Vec<_> = trees.into_iter().map(AVLTree::into_iter).collect();
+        let mut heap = BinaryHeap::with_capacity(iters.len());
+        for (i, iter) in iters.iter_mut().enumerate() {
+            if let Some(value) = iter.next() {
+                heap.push(Reverse((value, i)));
+            }
+        }
+        let mut values: Vec<T> = Vec::new();
+        while let Some(Reverse((value, i))) = heap.pop() {
+            if values.last() != Some(&value) {
+                values.push(value);
+            }
+            if let Some(next) = iters[i].next() {
+                heap.push(Reverse((next, i)));
+            }
+        }
+        AVLTree {
+            length: values.len(),
+            root: build_balanced(values.len(), &mut values.into_iter()),
+        }
+    }
+}
+
+impl<T: Ord + Clone> AVLTree<T> {
+    /// Copies the values of the tree into a sorted `Vec`.
+    ///
+    /// The snapshot owns its values, so it stays unchanged when the tree is mutated later on. This
+    /// clones every value and allocates once, costing O(n) time and memory.
+    ///
+    /// # Returns
+    ///
+    /// A vector of the values in the tree in ascending order.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rust_algorithms::data_structures::AVLTree;
+    ///
+    /// let mut tree: AVLTree<_> = (1..4).collect();
+    /// let snapshot = tree.snapshot();
+    /// tree.insert(4);
+    ///
+    /// assert_eq!(snapshot, vec![1, 2, 3]);
+    /// ```
+    pub fn snapshot(&self) -> Vec<T> {
+        let mut values = Vec::with_capacity(self.length);
+        values.extend(self.iter().cloned());
+        values
+    }
+
+    /// Clones the values of the tree into a `Vec` in ascending order.
+    ///
+    /// This is `snapshot` under the name used by slices, allocating the `Vec` once with the length
+    /// of the tree. Use `into_sorted_vec` to move the values out instead.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rust_algorithms::data_structures::AVLTree;
+    ///
+    /// let tree: AVLTree<_> = vec![3, 1, 2].into_iter().collect();
+    ///
+    /// assert_eq!(tree.to_vec(), vec![1, 2, 3]);
+    /// assert_eq!(tree.len(), 3);
+    /// ```
+    pub fn to_vec(&self) -> Vec<T> {
+        self.snapshot()
+    }
+
+    /// Splits copies of the values into two new balanced trees, leaving `self` unchanged.
+    ///
+    /// # Arguments
+    ///
+    /// * `k`: The number of smallest values to put into the first tree. If `k` exceeds the length
+    ///   of the tree, the first tree receives every value.
+    ///
+    /// # Returns
+    ///
+    /// A tree of the `k` smallest values and a tree of the remaining values.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rust_algorithms::data_structures::AVLTree;
+    ///
+    /// let tree: AVLTree<_> = (0..10).collect();
+    /// let (low, high) = tree.cloned_split_at_rank(3);
+    ///
+    /// assert_eq!(low.iter().collect::<Vec<_>>(), vec![&0, &1, &2]);
+    /// assert_eq!(high.len(), 7);
+    /// assert_eq!(tree.len(), 10);
+    /// ```
+    pub fn cloned_split_at_rank(&self, k: usize) -> (AVLTree<T>, AVLTree<T>) {
+        let k = k.min(self.length);
+        let mut values = self.iter().cloned();
+        let low = AVLTree {
+            root: build_balanced(k, &mut values),
+            length: k,
+        };
+        let high = AVLTree {
+            root: build_balanced(self.length - k, &mut values),
+            length: self.length - k,
+        };
+        (low, high)
+    }
+
+    /// Copies the values of the tree into the Eytzinger layout of a complete binary search tree.
+    ///
+    /// The children of the value at index `i` are stored at indices `2i + 1` and `2i + 2`, which
+    /// turns the array into an implicit search tree. A binary search over it walks the array front
+    /// to back and touches few cache lines, making the layout a good fit for static lookup tables.
+    /// The layout is filled by an in-order walk over the implicit tree in O(n).
+    ///
+    /// # Returns
+    ///
+    /// A vector of the values in the tree in Eytzinger order.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rust_algorithms::data_structures::AVLTree;
+    ///
+    /// let tree: AVLTree<_> = (1..=7).collect();
+    ///
+    /// assert_eq!(tree.to_eytzinger(), vec![4, 2, 6, 1, 3, 5, 7]);
+    /// ```
+    pub fn to_eytzinger(&self) -> Vec<T> {
+        let n = self.length;
+        let mut layout = vec![None; n];
+        let mut values = self.iter();
+        let mut stack = Vec::new();
+        let mut i = 0;
+        loop {
+            while i < n {
+                stack.push(i);
+                i = 2 * i + 1;
+            }
+            match stack.pop() {
+                Some(j) => {
+                    layout[j] = values.next().cloned();
+                    i = 2 * j + 2;
+                }
+                None => break,
+            }
+        }
+        layout.into_iter().flatten().collect()
+    }
+
+    /// Builds a tree holding every value covered by any of the ranges.
+    ///
+    /// The ranges are sorted by their start and overlapping or adjacent ones are merged, so that
+    /// every value is produced once and in ascending order, and the tree is then built from them
+    /// in O(n) like `from_sorted_unchecked`. Every value of the union is materialized as its own
+    /// node, so this takes time and memory proportional to the total length of the merged
+    /// ranges, not to the number of ranges. Empty ranges are ignored.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rust_algorithms::data_structures::AVLTree;
+    ///
+    /// let tree = AVLTree::from_ranges(&[10..12, 0..3, 2..4]);
+    ///
+    /// assert!(tree.iter().eq([0, 1, 2, 3, 10, 11].iter()));
+    /// ```
+    pub fn from_ranges(ranges: &[std::ops::Range<T>]) -> AVLTree<T>
+    where
+        std::ops::Range<T>: Iterator<Item = T>,
+    {
+        let mut ranges: Vec<std::ops::Range<T>> =
+            ranges.iter().filter(|r| r.start < r.end).cloned().collect();
+        ranges.sort_unstable_by(|a, b| a.start.cmp(&b.start));
+        let mut merged: Vec<std::ops::Range<T>> = Vec::with_capacity(ranges.len());
+        for range in ranges {
+            match merged.last_mut() {
+                Some(last) if range.start <= last.end => {
+                    if range.end > last.end {
+                        last.end = range.end;
+                    }
+                }
+                _ => merged.push(range),
+            }
+        }
+        Self::from_sorted_unchecked(merged.into_iter().flatten().collect())
+    }
+
+    /// Builds the union of two trees, holding the values that are in either of them.
+    ///
+    /// Both trees are walked in ascending order side by side, which takes O(n + m) comparisons,
+    /// and the result is built from the merged values in O(n + m) with `from_sorted_unchecked`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rust_algorithms::data_structures::AVLTree;
+    ///
+    /// let a: AVLTree<_> = vec![1, 2, 3].into_iter().collect();
+    /// let b: AVLTree<_> = vec![2, 3, 4].into_iter().collect();
+    ///
+    /// assert!(a.union(&b).iter().eq([1, 2, 3, 4].iter()));
+    /// ```
+    pub fn union(&self, other: &AVLTree<T>) -> AVLTree<T> {
+        self.merge_walk(other, true, true, true)
+    }
+
+    /// Builds the intersection of two trees, holding the values that are in both of them.
+    ///
+    /// This walks both trees side by side like `union`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rust_algorithms::data_structures::AVLTree;
+    ///
+    /// let a: AVLTree<_> = vec![1, 2, 3].into_iter().collect();
+    /// let b: AVLTree<_> = vec![2, 3, 4].into_iter().collect();
+    ///
+    /// assert!(a.intersection(&b).iter().eq([2, 3].iter()));
+    /// ```
+    pub fn intersection(&self, other: &AVLTree<T>) -> AVLTree<T> {
+        self.merge_walk(other, false, true, false)
+    }
+
+    /// Builds the difference of two trees, holding the values that are in this tree but not in
+    /// `other`.
+    ///
+    /// This walks both trees side by side like `union`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rust_algorithms::data_structures::AVLTree;
+    ///
+    /// let a: AVLTree<_> = vec![1, 2, 3].into_iter().collect();
+    /// let b: AVLTree<_> = vec![2, 3, 4].into_iter().collect();
+    ///
+    /// assert!(a.difference(&b).iter().eq([1].iter()));
+    /// ```
+    pub fn difference(&self, other: &AVLTree<T>) -> AVLTree<T> {
+        self.merge_walk(other, true, false, false)
+    }
+
+    /// Walks both trees in ascending order side by side and builds a tree of the values that
+    /// are only in this tree, in both trees, or only in `other`, as selected by the flags.
+    fn merge_walk(
+        &self,
+        other: &AVLTree<T>,
+        only_self: bool,
+        both: bool,
+        only_other: bool,
+    ) -> AVLTree<T> {
+        let mut values = Vec::new();
+        let mut a = self.iter().peekable();
+        let mut b = other.iter().peekable();
+        loop {
+            let (value, keep) = match (a.peek(), b.peek()) {
+                (Some(x), Some(y)) => match x.cmp(y) {
+                    Ordering::Less => (a.next(), only_self),
+                    Ordering::Greater => (b.next(), only_other),
+                    Ordering::Equal => {
+                        b.next();
+                        (a.next(), both)
+                    }
+                },
+                (Some(_), None) => (a.next(), only_self),
+                (None, Some(_)) => (b.next(), only_other),
+                (None, None) => break,
+            };
+            if keep {
+                values.extend(value.cloned());
+            }
+        }
+        Self::from_sorted_unchecked(values)
+    }
+}
+
+impl<T: Ord + Copy + Sub<Output = T>> AVLTree<T> {
+    /// Gets the `k` values closest to `target`, ordered by increasing distance.
+    ///
+    /// The distance between two values is the larger minus the smaller one. Starting from the
+    /// position of `target`, the search expands outward one value at a time, taking whichever
+    /// neighbour is closer and preferring the smaller value on ties. Every step looks up a value
+    /// by rank, so this takes O(k log n).
+    ///
+    /// # Returns
+    ///
+    /// The `k` nearest values, or all values if the tree holds fewer than `k`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rust_algorithms::data_structures::AVLTree;
+    ///
+    /// let tree: AVLTree<_> = vec![1, 4, 6, 7, 12].into_iter().collect();
+    ///
+    /// assert_eq!(tree.nearest_k(&5, 3), vec![&4, &6, &7]);
+    /// assert_eq!(tree.nearest_k(&12, 2), vec![&12, &7]);
+    /// ```
+    pub fn nearest_k(&self, target: &T, k: usize) -> Vec<&T> {
+        let distance = |value: &T| {
+            if value > target {
+                *value - *target
+            } else {
+                *target - *value
+            }
+        };
+        let mut nearest = Vec::with_capacity(k.min(self.length));
+        // Values below `above` and at or after it are taken by increasing distance
+        let mut above = self.rank(target);
+        let mut below = above;
+        while nearest.len() < k {
+            let smaller = below.checked_sub(1).and_then(|i| self.select(i));
+            let larger = self.select(above);
+            match (smaller, larger) {
+                (Some(s), Some(l)) if distance(s) <= distance(l) => {
+                    nearest.push(s);
+                    below -= 1;
+                }
+                (_, Some(l)) => {
+                    nearest.push(l);
+                    above += 1;
+                }
+                (Some(s), None) => {
+                    nearest.push(s);
+                    below -= 1;
+                }
+                (None, None) => break,
+            }
+        }
+        nearest
+    }
+}
+
+#[cfg(test)]
+impl<T: Ord> AVLTree<T> {
+    /// Returns `true` if the tree holds exactly the values of `reference`.
+    ///
+    /// Compares the lengths and the in-order values, which serves as an oracle check in
+    /// differential tests against the standard library.
+    pub fn matches_btreeset(&self, reference: &std::collections::BTreeSet<T>) -> bool {
+        self.len() == reference.len() && self.iter().eq(reference.iter())
+    }
+
+    /// Overwrites the cached length without touching the nodes, to corrupt the tree on purpose.
+    pub fn corrupt_length(&mut self, length: usize) {
+        self.length = length;
+    }
+}
+
+#[cfg(any(test, feature = "avl_visualization"))]
+impl<T: Ord> AVLTree<T> {
+    /// Performs a single rotation at the root, without any of the rebalancing logic.
+    ///
+    /// A left rotation lifts the right child of the root into its place and makes the old root
+    /// its left child, while a right rotation does the opposite. This lets a visualization step
+    /// through rotations one at a time. The order of the values is preserved, but the tree may no
+    /// longer be balanced afterwards, and stays that way until rotated back, since later
+    /// insertions and removals only rebalance the nodes on their own paths.
+    ///
+    /// This is only available with the `avl_visualization` feature.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the rotation was performed, or `false` if the root lacks the child that would
+    /// take its place.
+    pub fn rotate_root(&mut self, left: bool) -> bool {
+        let side = if left { Side::Left } else { Side::Right };
+        match &mut self.root {
+            Some(root) if root.child(!side).is_some() => {
+                root.rotate(side);
+                true
+            }
+            _ => false,
+        }
+    }
+}
+
+/// Builds a perfectly balanced tree from the next `len` values of an ascending iterator.
+///
+/// Both subtrees of every node receive half of the values, so their heights differ by at most one
+/// and no rotations are needed. This runs in O(len).
+fn build_balanced<T, I: Iterator<Item = T>>(len: usize, values: &mut I) -> Option<Box<AVLNode<T>>> {
+    if len == 0 {
+        return None;
+    }
+    let left = build_balanced(len / 2, values);
+    let value = values
+        .next()
+        .expect("iterator yielded fewer values than requested");
+    let right = build_balanced(len - len / 2 - 1, values);
+    let mut node = AVLNode::leaf(value);
+    node.left = left;
+    node.right = right;
+    node.update();
+    Some(node)
+}
+
+/// Finds the first and last values in a tree for which `f` returns `Ordering::Equal`.
+fn equal_range_by<T, F: Fn(&T) -> Ordering>(
+    tree: &Option<Box<AVLNode<T>>>,
+    f: F,
+) -> (Option<&T>, Option<&T>) {
+    let (mut first, mut last) = (None, None);
+    let mut current = tree;
+    while let Some(node) = current {
+        current = match f(&node.value) {
+            Ordering::Less => &node.right,
+            Ordering::Equal => {
+                first = Some(&node.value);
+                &node.left
+            }
+            Ordering::Greater => &node.left,
+        }
+    }
+    let mut current = tree;
+    while let Some(node) = current {
+        current = match f(&node.value) {
+            Ordering::Less => &node.right,
+            Ordering::Equal => {
+                last = Some(&node.value);
+                &node.right
+            }
+            Ordering::Greater => &node.left,
+        }
+    }
+    (first, last)
+}
+
+/// Recursive helper function for `AVLTree::structural_eq`.
+fn structurally_equal<T: PartialEq>(
+    a: &Option<Box<AVLNode<T>>>,
+    b: &Option<Box<AVLNode<T>>>,
+) -> bool {
+    match (a, b) {
+        (None, None) => true,
+        (Some(a), Some(b)) => {
+            a.value == b.value
+                && structurally_equal(&a.left, &b.left)
+                && structurally_equal(&a.right, &b.right)
+        }
+        _ => false,
+    }
+}
+
+/// Moves the values of a tree into `values` in ascending order.
+fn into_values<T>(tree: Option<Box<AVLNode<T>>>, values: &mut Vec<T>) {
+    if let Some(node) = tree {
+        let AVLNode {
+            value, left, right, ..
+        } = *node;
+        into_values(left, values);
+        values.push(value);
+        into_values(right, values);
+    }
+}
+
+/// Recursive helper function for `AVLTree` insertion, ordering values by `cmp`.
+///
+/// Returns the rank of the new value within the subtree, or `None` if it was already present.
+fn insert<T, A: Aggregate<T>, C: Fn(&T, &T) -> Ordering>(
+    tree: &mut Option<Box<AVLNode<T, A>>>,
+    value: T,
+    cmp: &C,
+) -> Option<usize> {
+    if let Some(node) = tree {
+        let rank = match cmp(&value, &node.value) {
+            Ordering::Equal => None,
+            Ordering::Less => insert(&mut node.left, value, cmp),
+            Ordering::Greater => {
+                let smaller = node.size(Side::Left) + 1;
+                insert(&mut node.right, value, cmp).map(|rank| rank + smaller)
+            }
+        };
+        if rank.is_some() {
+            node.rebalance();
+        }
+        rank
+    } else {
+        *tree = Some(AVLNode::leaf(value));
+        Some(0)
+    }
+}
+
+/// Recomputes the height of a tree, or returns `None` if some node in it is unbalanced.
+fn balanced_height<T>(tree: &Option<Box<AVLNode<T>>>) -> Option<usize> {
+    match tree {
+        None => Some(0),
+        Some(node) => {
+            let left = balanced_height(&node.left)?;
+            let right = balanced_height(&node.right)?;
+            if left.max(right) - left.min(right) > 1 {
+                return None;
+            }
+            Some(1 + left.max(right))
+        }
+    }
+}
+
+/// Recursive helper function for `AVLTree` deletion.
+///
+/// `probe` compares the value to remove with the value of a node, which lets `AVLMap` search by
+/// key alone. Returns the removed value, or `None` if no value compared equal.
+fn remove<T, A: Aggregate<T>, F: Fn(&T) -> Ordering>(
+    tree: &mut Option<Box<AVLNode<T, A>>>,
+    probe: &F,
+) -> Option<T> {
+    if let Some(node) = tree {
+        let removed = match probe(&node.value) {
+            Ordering::Less => remove(&mut node.left, probe),
+            Ordering::Greater => remove(&mut node.right, probe),
+            Ordering::Equal => {
+                let mut node = tree.take().unwrap();
+                *tree = match (node.left.take(), node.right.take()) {
+                    (None, None) => None,
+                    (Some(b), None) | (None, Some(b)) => Some(b),
+                    (Some(left), Some(right)) => Some(merge(left, right)),
+                };
+                return Some(node.value);
+            }
+        };
+        if removed.is_some() {
+            node.rebalance();
+        }
+        removed
+    } else {
+        None
+    }
+}
+
+/// Merges two trees and returns the root of the merged tree.
+fn merge<T, A: Aggregate<T>>(
+    left: Box<AVLNode<T, A>>,
+    right: Box<AVLNode<T, A>>,
+) -> Box<AVLNode<T, A>> {
+    let mut op_right = Some(right);
+    // Guaranteed not to panic since right has at least one node
+    let mut root = take_min(&mut op_right).unwrap();
+    root.left = Some(left);
+    root.right = op_right;
+    root.rebalance();
+    root
+}
+
+/// A possibly empty subtree, as split and joined by `split` and `join`.
+type Subtree<T> = Option<Box<AVLNode<T>>>;
+
+/// Splits a tree into the values less than `key` and the values greater than or equal to it.
+fn split<T: Ord>(tree: Subtree<T>, key: &T) -> (Subtree<T>, Subtree<T>) {
+    match tree {
+        None => (None, None),
+        Some(mut node) => {
+            let (left, right) = (node.left.take(), node.right.take());
+            if node.value < *key {
+                let (low, high) = split(right, key);
+                (Some(join(left, node, low)), high)
+            } else {
+                let (low, high) = split(left, key);
+                (low, Some(join(high, node, right)))
+            }
+        }
+    }
+}
+
+/// Joins two trees with a node whose value lies between them, and returns the root of the joined
+/// tree.
+///
+/// The node is attached on the spine of the taller tree where the heights match, and the spine is
+/// rebalanced on the way back up, which takes O(1 + height difference).
+fn join<T>(left: Subtree<T>, mut mid: Box<AVLNode<T>>, right: Subtree<T>) -> Box<AVLNode<T>> {
+    let height = |tree: &Subtree<T>| tree.as_ref().map_or(0, |n| n.height);
+    let (left_height, right_height) = (height(&left), height(&right));
+    if left_height > right_height + 1 {
+        let mut root = left.unwrap();
+        root.right = Some(join(root.right.take(), mid, right));
+        root.rebalance();
+        root
+    } else if right_height > left_height + 1 {
+        let mut root = right.unwrap();
+        root.left = Some(join(left, mid, root.left.take()));
+        root.rebalance();
+        root
+    } else {
+        mid.left = left;
+        mid.right = right;
+        mid.update();
+        mid
+    }
+}
+
+/// Removes the smallest node from the tree, if one exists.
+fn take_min<T, A: Aggregate<T>>(
+    tree: &mut Option<Box<AVLNode<T, A>>>,
+) -> Option<Box<AVLNode<T, A>>> {
+    if let Some(mut node) = tree.take() {
+        // Recurse along the left side
+        if let Some(small) = take_min(&mut node.left) {
+            // Took the smallest from below; update this node and put it back in the tree
+            node.rebalance();
+            *tree = Some(node);
+            Some(small)
+        } else {
+            // Take this node and replace it with its right child
+            *tree = node.right.take();
+            Some(node)
+        }
+    } else {
+        None
+    }
+}
+
+/// Removes the largest node from the tree, if one exists.
+fn take_max<T>(tree: &mut Option<Box<AVLNode<T>>>) -> Option<Box<AVLNode<T>>> {
+    if let Some(mut node) = tree.take() {
+        // Recurse along the right side
+        if let Some(large) = take_max(&mut node.right) {
+            // Took the largest from below; update this node and put it back in the tree
+            node.rebalance();
+            *tree = Some(node);
+            Some(large)
+        } else {
+            // Take this node and replace it with its left child
+            *tree = node.left.take();
+            Some(node)
+        }
+    } else {
+        None
+    }
+}
+
+impl<T, A: Aggregate<T>> AVLNode<T, A> {
+    /// Creates a node without children.
+    fn leaf(value: T) -> Box<Self> {
+        Box::new(AVLNode {
+            aggregate: A::aggregate(None, &value, None),
+            value,
+            height: 1,
+            size: 1,
+            left: None,
+            right: None,
+        })
+    }
+
+    /// Returns a reference to the left or right child.
+    fn child(&self, side: Side) -> &Option<Box<AVLNode<T, A>>> {
+        match side {
+            Side::Left => &self.left,
+            Side::Right => &self.right,
+        }
+    }
+
+    /// Returns a mutable reference to the left or right child.
+    fn child_mut(&mut self, side: Side) -> &mut Option<Box<AVLNode<T, A>>> {
+        match side {
+            Side::Left => &mut self.left,
+            Side::Right => &mut self.right,
+        }
+    }
+
+    /// Returns the height of the left or right subtree.
+    fn height(&self, side: Side) -> usize {
+        self.child(side).as_ref().map_or(0, |n| n.height)
+    }
+
+    /// Returns the height difference between the left and right subtrees.
+    fn balance_factor(&self) -> i8 {
+        let (left, right) = (self.height(Side::Left), self.height(Side::Right));
+        if left < right {
+            (right - left) as i8
+        } else {
+            -((left - right) as i8)
+        }
+    }
+
+    /// Returns the number of nodes in the left or right subtree.
+    fn size(&self, side: Side) -> usize {
+        self.child(side).as_ref().map_or(0, |n| n.size)
+    }
+
+    /// Recomputes the `height`, `size` and `aggregate` fields.
+    fn update(&mut self) {
+        self.height = 1 + max(self.height(Side::Left), self.height(Side::Right));
+        self.size = 1 + self.size(Side::Left) + self.size(Side::Right);
+        let left = self.left.as_ref().map(|n| &n.aggregate);
+        let right = self.right.as_ref().map(|n| &n.aggregate);
+        self.aggregate = A::aggregate(left, &self.value, right);
+    }
+
+    /// Performs a left or right rotation.
+    fn rotate(&mut self, side: Side) {
+        let mut subtree = self.child_mut(!side).take().unwrap();
+        *self.child_mut(!side) = subtree.child_mut(side).take();
+        self.update();
+        // Swap root and child nodes in memory
+        mem::swap(self, subtree.as_mut());
+        // Set old root (subtree) as child of new root (self)
+        *self.child_mut(side) = Some(subtree);
+        self.update();
+    }
+
+    /// Performs left or right tree rotations to balance this node.
+    fn rebalance(&mut self) {
+        self.update();
+        let side = match self.balance_factor() {
+            -2 => Side::Left,
+            2 => Side::Right,
+            _ => return,
+        };
+        let subtree = self.child_mut(side).as_mut().unwrap();
+        // Left-Right and Right-Left require rotation of heavy subtree
+        if let (Side::Left, 1) | (Side::Right, -1) = (side, subtree.balance_factor()) {
+            subtree.rotate(side);
+        }
+        // Rotate in opposite direction of heavy side
+        self.rotate(!side);
+    }
+}
+
+/// Default implementation for `AVLTree`.
+///
+/// Creates an empty `AVLTree`.
+impl<T: Ord> Default for AVLTree<T> {
+    /// Creates an empty `AVLTree`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rust_algorithms::data_structures::AVLTree;
+    ///
+    /// let tree: AVLTree<i32> = Default::default();
+    ///
+    /// assert!(tree.is_empty());
+    /// ```
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Formats the values in ascending order like a set, such as `{1, 2, 3}`.
+///
+/// The alternate form `{:#?}` shows the shape of the tree instead, with one value per line and
+/// each child indented below its parent and labelled `L` or `R`.
+impl<T: Ord + fmt::Debug> fmt::Debug for AVLTree<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if !f.alternate() {
+            return f.debug_set().entries(self.iter()).finish();
+        }
+        match &self.root {
+            Some(root) => fmt_shape(root, 0, f),
+            None => write!(f, "{{}}"),
+        }
+    }
+}
+
+/// Writes `node` and its subtrees for the alternate `Debug` form, indented by `depth` levels.
+fn fmt_shape<T: fmt::Debug>(
+    node: &AVLNode<T>,
+    depth: usize,
+    f: &mut fmt::Formatter,
+) -> fmt::Result {
+    write!(f, "{:?}", node.value)?;
+    for (label, child) in [("L", &node.left), ("R", &node.right)].iter() {
+        if let Some(child) = child {
+            write!(f, "\n{}{}: ", "    ".repeat(depth + 1), label)?;
+            fmt_shape(child, depth + 1, f)?;
+        }
+    }
+    Ok(())
+}
+
+/// Two trees are equal if they hold the same values, regardless of their shapes.
+impl<T: Ord> PartialEq for AVLTree<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.length == other.length && self.iter().eq(other.iter())
+    }
+}
+
+impl<T: Ord> Eq for AVLTree<T> {}
+
+/// Hashes the length and then the values in ascending order, consistent with `==`, so that trees
+/// holding the same values hash alike regardless of their shapes.
+impl<T: Ord + Hash> Hash for AVLTree<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.length.hash(state);
+        for value in self.iter() {
+            value.hash(state);
+        }
+    }
+}
+
+impl Not for Side {
+    type Output = Side;
+
+    fn not(self) -> Self::Output {
+        match self {
+            Side::Left => Side::Right,
+            Side::Right => Side::Left,
+        }
+    }
+}
+
+impl<K: Ord, V> Default for AVLMap<K, V> {
+    /// Creates an empty `AVLMap`.
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: Ord + fmt::Debug, V: fmt::Debug> fmt::Debug for AVLMap<K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+/// Collects entries into a map, where later values replace earlier ones for the same key.
+impl<K: Ord, V> FromIterator<(K, V)> for AVLMap<K, V> {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut map = AVLMap::new();
+        for (key, value) in iter {
+            map.insert(key, value);
+        }
+        map
+    }
+}
+
+impl<T: Ord + Add<Output = T> + Copy + Default> Default for AVLSumTree<T> {
+    /// Creates an empty `AVLSumTree`.
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Ord + Add<Output = T> + Copy + Default> FromIterator<T> for AVLSumTree<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut tree = AVLSumTree::new();
+        for value in iter {
+            tree.insert(value);
+        }
+        tree
+    }
+}
+
+impl<T: Ord> FromIterator<T> for AVLTree<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut tree = AVLTree::new();
+        for value in iter {
+            tree.insert(value);
+        }
+        tree
+    }
+}
+
+impl<T: Ord> Extend<T> for AVLTree<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for value in iter {
+            self.insert(value);
+        }
+    }
+}
+
+impl<'a, T: 'a + Ord + Copy> Extend<&'a T> for AVLTree<T> {
+    fn extend<I: IntoIterator<Item = &'a T>>(&mut self, iter: I) {
+        self.extend(iter.into_iter().copied());
+    }
+}
+
+/// Serializes the values as a flat sequence in ascending order.
+#[cfg(feature = "serde")]
+impl<T: Ord + serde::Serialize> serde::Serialize for AVLTree<T> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeSeq;
+        let mut seq = serializer.serialize_seq(Some(self.length))?;
+        for value in self.iter() {
+            seq.serialize_element(value)?;
+        }
+        seq.end()
+    }
+}
+
+/// Deserializes a sequence of values by inserting them one by one, so that the tree is balanced
+/// whatever order the values come in. Like `collect`, this keeps the first of any equal values.
+#[cfg(feature = "serde")]
+impl<'de, T: Ord + serde::Deserialize<'de>> serde::Deserialize<'de> for AVLTree<T> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let values = Vec::<T>::deserialize(deserializer)?;
+        Ok(values.into_iter().collect())
+    }
+}
+
+impl<T: Ord> IntoIterator for AVLTree<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    /// Consumes the tree, yielding its values in ascending order.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rust_algorithms::data_structures::AVLTree;
+    ///
+    /// let tree: AVLTree<_> = vec![3, 1, 2].into_iter().collect();
+    ///
+    /// assert_eq!(tree.into_iter().collect::<Vec<_>>(), vec![1, 2, 3]);
+    /// ```
+    fn into_iter(self) -> IntoIter<T> {
+        let mut into_iter = IntoIter {
+            stack: Vec::with_capacity(self.root.as_ref().map_or(0, |n| n.height)),
+        };
+        into_iter.push_left_spine(self.root);
+        into_iter
+    }
+}
+
+/// An AVL tree ordered by a custom comparator instead of `Ord`.
+///
+/// The comparator must describe a total order and is fixed when the tree is created; there is
+/// deliberately no way to swap it on a populated tree, since the existing nodes would no longer be
+/// arranged according to the new order.
+///
+/// # Examples
+///
+/// ```rust
+/// use rust_algorithms::data_structures::AVLTreeCmp;
+///
+/// let mut tree = AVLTreeCmp::reversed();
+/// tree.insert(1);
+/// tree.insert(3);
+/// tree.insert(2);
+///
+/// assert_eq!(tree.iter().collect::<Vec<_>>(), vec![&3, &2, &1]);
+/// ```
+pub struct AVLTreeCmp<T, C: Fn(&T, &T) -> Ordering> {
+    root: Option<Box<AVLNode<T>>>,
+    length: usize,
+    cmp: C,
+}
+
+impl<T: Ord> AVLTreeCmp<T, fn(&T, &T) -> Ordering> {
+    /// Creates an empty tree ordering values from largest to smallest.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rust_algorithms::data_structures::AVLTreeCmp;
+    ///
+    /// let mut tree = AVLTreeCmp::reversed();
+    /// tree.insert(1);
+    /// tree.insert(2);
+    /// tree.insert(3);
+    ///
+    /// assert_eq!(tree.min(), Some(&3));
+    /// assert_eq!(tree.max(), Some(&1));
+    /// ```
+    pub fn reversed() -> Self {
+        AVLTreeCmp::new(|a: &T, b: &T| b.cmp(a))
+    }
+}
+
+impl<T, C: Fn(&T, &T) -> Ordering> AVLTreeCmp<T, C> {
+    /// Creates an empty tree ordering values by `cmp`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rust_algorithms::data_structures::AVLTreeCmp;
+    ///
+    /// // Order strings by length only
+    /// let mut tree = AVLTreeCmp::new(|a: &&str, b: &&str| a.len().cmp(&b.len()));
+    /// tree.insert("ccc");
+    /// tree.insert("a");
+    ///
+    /// assert!(!tree.insert("b"));
+    /// assert_eq!(tree.iter().collect::<Vec<_>>(), vec![&"a", &"ccc"]);
+    /// ```
+    pub fn new(cmp: C) -> Self {
+        AVLTreeCmp {
+            root: None,
+            length: 0,
+            cmp,
+        }
+    }
+
+    /// Checks if the tree contains a value equal to `value` under the comparator.
+    pub fn contains(&self, value: &T) -> bool {
+        let mut current = &self.root;
+        while let Some(node) = current {
+            current = match (self.cmp)(value, &node.value) {
+                Ordering::Equal => return true,
+                Ordering::Less => &node.left,
+                Ordering::Greater => &node.right,
+            }
+        }
+        false
+    }
+
+    /// Adds a value to the tree.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the tree did not yet contain an equal value, `false` otherwise.
+    pub fn insert(&mut self, value: T) -> bool {
+        let inserted = insert(&mut self.root, value, &self.cmp).is_some();
+        if inserted {
+            self.length += 1;
+        }
+        inserted
+    }
+
+    /// Removes the value equal to `value` under the comparator.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the tree contained the value, `false` otherwise.
+    pub fn remove(&mut self, value: &T) -> bool {
+        let cmp = &self.cmp;
+        let removed = remove(&mut self.root, &|x| cmp(value, x)).is_some();
+        if removed {
+            self.length -= 1;
+        }
+        removed
+    }
+
+    /// Returns the number of values in the tree.
+    pub fn len(&self) -> usize {
+        self.length
+    }
+
+    /// Detects if the tree is empty.
+    pub fn is_empty(&self) -> bool {
+        self.length == 0
+    }
+
+    /// Returns the first value in comparator order, or `None` if the tree is empty.
+    pub fn min(&self) -> Option<&T> {
+        let mut node = self.root.as_ref()?;
+        while let Some(left) = &node.left {
+            node = left;
+        }
+        Some(&node.value)
+    }
+
+    /// Returns the last value in comparator order, or `None` if the tree is empty.
+    pub fn max(&self) -> Option<&T> {
+        let mut node = self.root.as_ref()?;
+        while let Some(right) = &node.right {
+            node = right;
+        }
+        Some(&node.value)
+    }
+
+    /// Returns the first and last values that compare equal to `value` under the comparator.
+    ///
+    /// Since the tree holds no two equal values, both are the same value if it is present, and
+    /// `None` otherwise. See `equal_range_by` for probing with a coarser ordering.
+    pub fn equal_range(&self, value: &T) -> (Option<&T>, Option<&T>) {
+        equal_range_by(&self.root, |x| (self.cmp)(x, value))
+    }
+
+    /// Returns the first and last values for which `f` returns `Ordering::Equal`.
+    ///
+    /// `f` must be consistent with the order of the tree, returning `Less` for all values before
+    /// the range and `Greater` for all values after it. This allows finding all values sharing a
+    /// key when the comparator breaks ties by further fields. Takes O(log n).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rust_algorithms::data_structures::AVLTreeCmp;
+    ///
+    /// // Ordered by length first and alphabetically second
+    /// let mut tree = AVLTreeCmp::new(|a: &&str, b: &&str| (a.len(), a).cmp(&(b.len(), b)));
+    /// for word in ["fig", "pear", "kiwi", "plum", "apple"].iter() {
+    ///     tree.insert(*word);
+    /// }
+    ///
+    /// let four_letters = tree.equal_range_by(|x| x.len().cmp(&4));
+    /// assert_eq!(four_letters, (Some(&"kiwi"), Some(&"plum")));
+    /// ```
+    pub fn equal_range_by<F: Fn(&T) -> Ordering>(&self, f: F) -> (Option<&T>, Option<&T>) {
+        equal_range_by(&self.root, f)
+    }
+
+    /// Gets an iterator that visits the values in the tree in comparator order.
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            node_iter: NodeIter::new(&self.root),
+        }
+    }
+}
+
+/// An ordered map backed by an AVL tree.
+///
+/// Every node holds a key and its value and is ordered by the key alone, sharing the nodes,
+/// rotations and rebalancing of `AVLTree`, so lookups, insertions and removals take O(log n).
+///
+/// # Examples
+///
+/// ```rust
+/// use rust_algorithms::data_structures::AVLMap;
+///
+/// let mut map = AVLMap::new();
+/// map.insert(2, "two");
+/// map.insert(1, "one");
+///
+/// assert_eq!(map.get(&1), Some(&"one"));
+/// assert_eq!(map.get(&3), None);
+/// assert_eq!(map.iter().collect::<Vec<_>>(), vec![(&1, &"one"), (&2, &"two")]);
+/// ```
+#[derive(Clone)]
+pub struct AVLMap<K: Ord, V> {
+    root: Option<Box<AVLNode<(K, V)>>>,
+    length: usize,
+}
+
+impl<K: Ord, V> AVLMap<K, V> {
+    /// Creates an empty `AVLMap`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rust_algorithms::data_structures::AVLMap;
+    ///
+    /// let map: AVLMap<i32, &str> = AVLMap::new();
+    ///
+    /// assert!(map.is_empty());
+    /// ```
+    pub fn new() -> AVLMap<K, V> {
+        AVLMap {
+            root: None,
+            length: 0,
+        }
+    }
+
+    /// Returns a reference to the value for `key`.
+    ///
+    /// # Returns
+    ///
+    /// The value, or `None` if the map does not contain `key`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rust_algorithms::data_structures::AVLMap;
+    ///
+    /// let mut map = AVLMap::new();
+    /// map.insert("a", 1);
+    ///
+    /// assert_eq!(map.get(&"a"), Some(&1));
+    /// assert_eq!(map.get(&"b"), None);
+    /// ```
+    pub fn get(&self, key: &K) -> Option<&V> {
+        let mut current = &self.root;
+        while let Some(node) = current {
+            current = match key.cmp(&node.value.0) {
+                Ordering::Equal => return Some(&node.value.1),
+                Ordering::Less => &node.left,
+                Ordering::Greater => &node.right,
+            }
+        }
+        None
+    }
+
+    /// Returns a mutable reference to the value for `key`.
+    ///
+    /// # Returns
+    ///
+    /// The value, or `None` if the map does not contain `key`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rust_algorithms::data_structures::AVLMap;
+    ///
+    /// let mut map = AVLMap::new();
+    /// map.insert("a", 1);
+    /// *map.get_mut(&"a").unwrap() += 10;
+    ///
+    /// assert_eq!(map.get(&"a"), Some(&11));
+    /// ```
+    pub fn get_mut(&mut self, key: &K) -> Option<&mut V> {
+        let mut current = &mut self.root;
+        while let Some(node) = current {
+            current = match key.cmp(&node.value.0) {
+                Ordering::Equal => return Some(&mut node.value.1),
+                Ordering::Less => &mut node.left,
+                Ordering::Greater => &mut node.right,
+            }
+        }
+        None
+    }
+
+    /// Checks if the map contains `key`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rust_algorithms::data_structures::AVLMap;
+    ///
+    /// let mut map = AVLMap::new();
+    /// map.insert(1, 'a');
+    ///
+    /// assert!(map.contains_key(&1));
+    /// assert!(!map.contains_key(&2));
+    /// ```
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.get(key).is_some()
+    }
+
+    /// Maps `key` to `value`, replacing the value it was mapped to before.
+    ///
+    /// The key already in the map is kept when its value is replaced.
+    ///
+    /// # Returns
+    ///
+    /// The previous value for `key`, or `None` if the map did not contain it.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rust_algorithms::data_structures::AVLMap;
+    ///
+    /// let mut map = AVLMap::new();
+    ///
+    /// assert_eq!(map.insert(1, "a"), None);
+    /// assert_eq!(map.insert(1, "b"), Some("a"));
+    /// assert_eq!(map.get(&1), Some(&"b"));
+    /// ```
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        if let Some(old) = self.get_mut(&key) {
+            return Some(mem::replace(old, value));
+        }
+        insert(&mut self.root, (key, value), &|a: &(K, V), b: &(K, V)| {
+            a.0.cmp(&b.0)
+        });
+        self.length += 1;
+        None
+    }
+
+    /// Removes `key` from the map.
+    ///
+    /// # Returns
+    ///
+    /// The value `key` was mapped to, or `None` if the map did not contain it.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rust_algorithms::data_structures::AVLMap;
+    ///
+    /// let mut map = AVLMap::new();
+    /// map.insert(1, "a");
+    ///
+    /// assert_eq!(map.remove(&1), Some("a"));
+    /// assert_eq!(map.remove(&1), None);
+    /// ```
+    pub fn remove(&mut self, key: &K) -> Option<V> {
+        let (_, value) = remove(&mut self.root, &|(k, _): &(K, V)| key.cmp(k))?;
+        self.length -= 1;
+        Some(value)
+    }
+
+    /// Returns the number of entries in the map.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rust_algorithms::data_structures::AVLMap;
+    ///
+    /// let mut map = AVLMap::new();
+    /// map.insert(1, "a");
+    /// map.insert(1, "b");
+    ///
+    /// assert_eq!(map.len(), 1);
+    /// ```
+    pub fn len(&self) -> usize {
+        self.length
+    }
+
+    /// Detects if the map is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rust_algorithms::data_structures::AVLMap;
+    ///
+    /// let mut map = AVLMap::new();
+    /// assert!(map.is_empty());
+    ///
+    /// map.insert(1, "a");
+    /// assert!(!map.is_empty());
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.length == 0
+    }
+
+    /// Gets an iterator that visits the entries in the map in ascending order of their keys.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rust_algorithms::data_structures::AVLMap;
+    ///
+    /// let map: AVLMap<_, _> = vec![(3, 'c'), (1, 'a'), (2, 'b')].into_iter().collect();
+    /// let mut iter = map.iter();
+    ///
+    /// assert_eq!(iter.next(), Some((&1, &'a')));
+    /// assert_eq!(iter.next(), Some((&2, &'b')));
+    /// assert_eq!(iter.next(), Some((&3, &'c')));
+    /// assert_eq!(iter.next(), None);
+    /// ```
+    pub fn iter(&self) -> MapIter<'_, K, V> {
+        MapIter {
+            node_iter: NodeIter::new(&self.root),
+        }
+    }
+
+    /// Gets an iterator over the entries whose keys lie within `range`, in ascending order of
+    /// their keys, with mutable access to the values.
+    ///
+    /// Subtrees entirely below the range are skipped on the way down and the walk stops at the
+    /// first key above it, so visiting k entries takes O(log n + k).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rust_algorithms::data_structures::AVLMap;
+    ///
+    /// let mut map: AVLMap<_, _> = (1..=5).map(|k| (k, k * 10)).collect();
+    /// for (_, value) in map.range_mut(2..4) {
+    ///     *value = 0;
+    /// }
+    ///
+    /// let values: Vec<_> = map.iter().map(|(_, v)| *v).collect();
+    /// assert_eq!(values, vec![10, 0, 0, 40, 50]);
+    /// ```
+    pub fn range_mut<R: RangeBounds<K>>(&mut self, range: R) -> MapRangeMut<'_, K, V, R> {
+        let mut range_mut = MapRangeMut {
+            stack: Vec::new(),
+            range,
+        };
+        range_mut.push_left_spine(&mut self.root);
+        range_mut
+    }
+
+    /// Adds `delta` to every value whose key lies within `range`, in a single pass over those
+    /// entries.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rust_algorithms::data_structures::AVLMap;
+    ///
+    /// let mut map: AVLMap<_, _> = (1..=5).map(|k| (k, 0)).collect();
+    /// map.add_to_range(2..=3, 7);
+    ///
+    /// assert_eq!(map.get(&1), Some(&0));
+    /// assert_eq!(map.get(&2), Some(&7));
+    /// assert_eq!(map.get(&3), Some(&7));
+    /// assert_eq!(map.get(&4), Some(&0));
+    /// ```
+    pub fn add_to_range<R: RangeBounds<K>>(&mut self, range: R, delta: V)
+    where
+        V: AddAssign + Copy,
+    {
+        for (_, value) in self.range_mut(range) {
+            *value += delta;
+        }
+    }
+
+    /// Groups the entries by a derived key and folds the values of each group into an aggregate.
+    ///
+    /// Every entry is passed to `key_fn` to find its group, and its value is folded into the
+    /// aggregate of that group with `fold_fn`, starting from a clone of `init`. The entries are
+    /// visited in a single pass in key order, so each group is folded in key order as well.
+    ///
+    /// # Returns
+    ///
+    /// A map from every group key that occurs to the aggregate of its group.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rust_algorithms::data_structures::AVLMap;
+    ///
+    /// let map: AVLMap<_, _> = (1..=10).map(|k| (k, k * 10)).collect();
+    /// let sums = map.fold_by(0, |&k, _| k % 2 == 0, |sum, &v| sum + v);
+    ///
+    /// assert_eq!(sums.get(&false), Some(&250));
+    /// assert_eq!(sums.get(&true), Some(&300));
+    /// ```
+    pub fn fold_by<G, A, KF, FF>(&self, init: A, key_fn: KF, fold_fn: FF) -> AVLMap<G, A>
+    where
+        G: Ord,
+        A: Clone,
+        KF: Fn(&K, &V) -> G,
+        FF: Fn(A, &V) -> A,
+    {
+        let mut groups = AVLMap::new();
+        for (key, value) in self.iter() {
+            let group = key_fn(key, value);
+            match groups.get_mut(&group) {
+                Some(aggregate) => {
+                    // Folding consumes the aggregate, so leave `init` in its place meanwhile
+                    let folded = fold_fn(mem::replace(aggregate, init.clone()), value);
+                    *aggregate = folded;
+                }
+                None => {
+                    groups.insert(group, fold_fn(init.clone(), value));
+                }
+            }
+        }
+        groups
+    }
+}
+
+/// An AVL tree of numbers, augmented with subtree sums.
+///
+/// Every node additionally stores the sum of its subtree as its aggregate, which the nodes,
+/// rotations and rebalancing shared with `AVLTree` keep up to date through insertions and
+/// removals. This allows the sum of all values within a range to be computed in O(log n).
+///
+/// # Examples
+///
+/// ```rust
+/// use rust_algorithms::data_structures::AVLSumTree;
+///
+/// let tree: AVLSumTree<_> = (1..=10).collect();
+///
+/// assert_eq!(tree.range_sum(..), 55);
+/// assert_eq!(tree.range_sum(3..6), 12);
+/// assert_eq!(tree.range_sum(8..), 27);
+/// ```
+pub struct AVLSumTree<T> {
+    root: Option<Box<AVLNode<T, SubtreeSum<T>>>>,
+    length: usize,
+}
+
+impl<T: Ord + Add<Output = T> + Copy + Default> AVLSumTree<T> {
+    /// Creates an empty `AVLSumTree`.
+    pub fn new() -> AVLSumTree<T> {
+        AVLSumTree {
+            root: None,
+            length: 0,
+        }
+    }
+
+    /// Returns `true` if the tree contains a value.
+    pub fn contains(&self, value: &T) -> bool {
+        let mut current = &self.root;
+        while let Some(node) = current {
+            current = match value.cmp(&node.value) {
+                Ordering::Equal => return true,
+                Ordering::Less => &node.left,
+                Ordering::Greater => &node.right,
+            }
+        }
+        false
+    }
+
+    /// Adds a value to the tree.
+    ///
+    /// Returns `true` if the tree did not yet contain the value.
+    pub fn insert(&mut self, value: T) -> bool {
+        let inserted = insert(&mut self.root, value, &T::cmp).is_some();
+        if inserted {
+            self.length += 1;
+        }
+        inserted
+    }
+
+    /// Removes a value from the tree.
+    ///
+    /// Returns `true` if the tree contained the value.
+    pub fn remove(&mut self, value: &T) -> bool {
+        let removed = remove(&mut self.root, &|x| value.cmp(x)).is_some();
+        if removed {
+            self.length -= 1;
+        }
+        removed
+    }
+
+    /// Returns the number of values in the tree.
+    pub fn len(&self) -> usize {
+        self.length
+    }
+
+    /// Returns `true` if the tree contains no values.
+    pub fn is_empty(&self) -> bool {
+        self.length == 0
+    }
+
+    /// Returns the sum of all values within `range`, or `T::default()` if there are none.
+    ///
+    /// The search descends to the highest node inside the range, then follows one path down each
+    /// side of it, adding up whole subtrees that lie entirely within the range. This takes
+    /// O(log n).
+    pub fn range_sum<R: RangeBounds<T>>(&self, range: R) -> T {
+        let (start, end) = (range.start_bound(), range.end_bound());
+        // Find the topmost node inside the range; everything outside of it is out of range
+        let mut current = &self.root;
+        while let Some(node) = current {
+            if before_start(&node.value, start) {
+                current = &node.right;
+            } else if after_end(&node.value, end) {
+                current = &node.left;
+            } else {
+                break;
+            }
+        }
+        let split = match current {
+            Some(node) => node,
+            None => return T::default(),
+        };
+
+        let mut total = split.value;
+        // Left of the split, all values are below the end bound
+        let mut current = &split.left;
+        while let Some(node) = current {
+            if before_start(&node.value, start) {
+                current = &node.right;
+            } else {
+                total = total + node.value + subtree_sum(&node.right);
+                current = &node.left;
+            }
+        }
+        // Right of the split, all values are above the start bound
+        let mut current = &split.right;
+        while let Some(node) = current {
+            if after_end(&node.value, end) {
+                current = &node.left;
+            } else {
+                total = total + node.value + subtree_sum(&node.left);
+                current = &node.right;
+            }
+        }
+        total
+    }
+}
+
+/// Returns `true` if `value` lies before the `start` bound of a range.
+fn before_start<T: Ord>(value: &T, start: Bound<&T>) -> bool {
+    match start {
+        Bound::Included(s) => value < s,
+        Bound::Excluded(s) => value <= s,
+        Bound::Unbounded => false,
+    }
+}
+
+/// Returns `true` if `value` lies after the `end` bound of a range.
+fn after_end<T: Ord>(value: &T, end: Bound<&T>) -> bool {
+    match end {
+        Bound::Included(e) => value > e,
+        Bound::Excluded(e) => value >= e,
+        Bound::Unbounded => false,
+    }
+}
+
+/// Returns the sum of a possibly empty subtree.
+fn subtree_sum<T: Copy + Default>(tree: &Option<Box<AVLNode<T, SubtreeSum<T>>>>) -> T {
+    tree.as_ref().map_or_else(T::default, |n| n.aggregate.0)
+}
+
+/// An iterator over the nodes of an `AVLTree`.
+///
+/// This struct is created by the `node_iter` method of `AVLTree`.
+struct NodeIter<'a, T> {
+    /// The path to the next node from the front, whose left subtrees have been visited.
+    front: Vec<&'a AVLNode<T>>,
+    /// The path to the next node from the back, whose right subtrees have been visited.
+    back: Vec<&'a AVLNode<T>>,
+    /// The number of nodes not yet yielded from either end.
+    remaining: usize,
+}
+
+impl<'a, T> NodeIter<'a, T> {
+    /// Creates an iterator over the nodes of the tree rooted at `root`.
+    fn new(root: &'a Option<Box<AVLNode<T>>>) -> Self {
+        let cap = root.as_ref().map_or(0, |n| n.height);
+        let mut node_iter = NodeIter {
+            front: Vec::with_capacity(cap),
+            back: Vec::with_capacity(cap),
+            remaining: root.as_ref().map_or(0, |n| n.size),
+        };
+        // Initialize the stacks with the paths to the leftmost and rightmost children
+        node_iter.push_spine(root, Side::Left);
+        node_iter.push_spine(root, Side::Right);
+        node_iter
+    }
+
+    /// Pushes the path from `tree` down along `side` onto the stack for that end.
+    fn push_spine(&mut self, mut tree: &'a Option<Box<AVLNode<T>>>, side: Side) {
+        let stack = match side {
+            Side::Left => &mut self.front,
+            Side::Right => &mut self.back,
+        };
+        while let Some(node) = tree {
+            stack.push(node.as_ref());
+            tree = node.child(side);
+        }
+    }
+}
+
+/// An iterator over the nodes of an `AVLTree`.
+///
+/// This struct is created by the `node_iter` method of `AVLTree`.
+impl<'a, T> Iterator for NodeIter<'a, T> {
+    type Item = &'a AVLNode<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // Both stacks stay valid until the ends meet, which the count detects
+        if self.remaining == 0 {
+            return None;
+        }
+        let node = self.front.pop()?;
+        self.remaining -= 1;
+        // Push left path of right subtree to stack
+        self.push_spine(&node.right, Side::Left);
+        Some(node)
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for NodeIter<'a, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+        let node = self.back.pop()?;
+        self.remaining -= 1;
+        // Push right path of left subtree to stack
+        self.push_spine(&node.left, Side::Right);
+        Some(node)
+    }
+}
+
+/// An iterator over the items of an `AVLTree` within a range.
+///
+/// This struct is created by the `range` method of `AVLTree`.
+pub struct Range<'a, T> {
+    node_iter: NodeIter<'a, T>,
+}
+
+impl<'a, T> Iterator for Range<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        self.node_iter.next().map(|node| &node.value)
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for Range<'a, T> {
+    fn next_back(&mut self) -> Option<&'a T> {
+        self.node_iter.next_back().map(|node| &node.value)
+    }
+}
+
+/// An iterator over the items of an `AVLTree`.
+///
+/// This struct is created by the `iter` method of `AVLTree`.
+pub struct Iter<'a, T> {
+    node_iter: NodeIter<'a, T>,
+}
+
+/// An iterator over the items of an `AVLTree`.
+///
+/// This struct is created by the `iter` method of `AVLTree`.
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    /// Returns the next value in the tree.
+    ///
+    /// # Returns
+    ///
+    /// The next value in the tree, or `None` if there are no more values.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rust_algorithms::data_structures::AVLTree;
+    ///
+    /// let tree: AVLTree<_> = (1..4).collect();
+    /// let mut iter = tree.iter();
+    ///
+    /// assert_eq!(iter.next(), Some(&1));
+    /// assert_eq!(iter.next(), Some(&2));
+    /// assert_eq!(iter.next(), Some(&3));
+    /// assert_eq!(iter.next(), None);
+    /// ```
+    fn next(&mut self) -> Option<&'a T> {
+        match self.node_iter.next() {
+            Some(node) => Some(&node.value),
+            None => None,
+        }
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for Iter<'a, T> {
+    /// Returns the next value from the back of the tree.
+    ///
+    /// # Returns
+    ///
+    /// The largest value not yet returned from either end, or `None` if there are no more values.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rust_algorithms::data_structures::AVLTree;
+    ///
+    /// let tree: AVLTree<_> = (1..5).collect();
+    /// let mut iter = tree.iter();
+    ///
+    /// assert_eq!(iter.next_back(), Some(&4));
+    /// assert_eq!(iter.next(), Some(&1));
+    /// assert_eq!(iter.next_back(), Some(&3));
+    /// assert_eq!(iter.next_back(), Some(&2));
+    /// assert_eq!(iter.next(), None);
+    /// assert!(tree.iter().rev().eq([4, 3, 2, 1].iter()));
+    /// ```
+    fn next_back(&mut self) -> Option<&'a T> {
+        self.node_iter.next_back().map(|node| &node.value)
+    }
+}
+
+/// An owning iterator over the items of an `AVLTree`.
+///
+/// This struct is created by the `into_iter` method of `AVLTree`.
+pub struct IntoIter<T> {
+    /// The nodes whose left subtrees have been yielded, with their left links already taken.
+    stack: Vec<Box<AVLNode<T>>>,
+}
+
+impl<T> IntoIter<T> {
+    /// Moves the path from `tree` to its leftmost node onto the stack.
+    fn push_left_spine(&mut self, mut tree: Option<Box<AVLNode<T>>>) {
+        while let Some(mut node) = tree {
+            tree = node.left.take();
+            self.stack.push(node);
+        }
+    }
+}
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        let mut node = self.stack.pop()?;
+        self.push_left_spine(node.right.take());
+        // The node is freed here, with both of its links taken
+        Some(node.value)
+    }
+}
+
+/// An owning iterator over the values of an `AVLTree` in descending order.
+///
+/// This struct is created by the `into_iter_rev` method of `AVLTree`.
+pub struct IntoIterRev<T> {
+    /// The nodes whose right subtrees have been yielded, with their right links already taken.
+    stack: Vec<Box<AVLNode<T>>>,
+}
+
+impl<T> IntoIterRev<T> {
+    /// Moves the path from `tree` to its rightmost node onto the stack.
+    fn push_right_spine(&mut self, mut tree: Option<Box<AVLNode<T>>>) {
+        while let Some(mut node) = tree {
+            tree = node.right.take();
+            self.stack.push(node);
+        }
+    }
+}
+
+impl<T> Iterator for IntoIterRev<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        let mut node = self.stack.pop()?;
+        self.push_right_spine(node.left.take());
+        Some(node.value)
+    }
+}
+
+/// An iterator over the entries of an `AVLMap`.
+///
+/// This struct is created by the `iter` method of `AVLMap`.
+pub struct MapIter<'a, K, V> {
+    node_iter: NodeIter<'a, (K, V)>,
+}
+
+impl<'a, K, V> Iterator for MapIter<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<(&'a K, &'a V)> {
+        self.node_iter
+            .next()
+            .map(|node| (&node.value.0, &node.value.1))
+    }
+}
+
+impl<'a, K, V> DoubleEndedIterator for MapIter<'a, K, V> {
+    fn next_back(&mut self) -> Option<(&'a K, &'a V)> {
+        self.node_iter
+            .next_back()
+            .map(|node| (&node.value.0, &node.value.1))
+    }
+}
+
+/// An entry waiting to be visited by `MapRangeMut`, along with its right subtree.
+type PendingEntry<'a, K, V> = (&'a K, &'a mut V, &'a mut Subtree<(K, V)>);
+
+/// A mutable iterator over the entries of an `AVLMap` whose keys lie within a range.
+///
+/// This struct is created by the `range_mut` method of `AVLMap`.
+pub struct MapRangeMut<'a, K, V, R> {
+    /// The entries still to visit on the path to the next one.
+    stack: Vec<PendingEntry<'a, K, V>>,
+    range: R,
+}
+
+impl<'a, K: Ord, V, R: RangeBounds<K>> MapRangeMut<'a, K, V, R> {
+    /// Pushes the entries along the left spine of `tree` that do not lie below the range.
+    fn push_left_spine(&mut self, mut tree: &'a mut Subtree<(K, V)>) {
+        while let Some(node) = tree {
+            let AVLNode {
+                value: (key, value),
+                left,
+                right,
+                ..
+            } = &mut **node;
+            let below_start = match self.range.start_bound() {
+                Bound::Included(start) => *key < *start,
+                Bound::Excluded(start) => *key <= *start,
+                Bound::Unbounded => false,
+            };
+            if below_start {
+                tree = right;
+            } else {
+                self.stack.push((key, value, right));
+                tree = left;
+            }
+        }
+    }
+}
+
+impl<'a, K: Ord, V, R: RangeBounds<K>> Iterator for MapRangeMut<'a, K, V, R> {
+    type Item = (&'a K, &'a mut V);
+
+    fn next(&mut self) -> Option<(&'a K, &'a mut V)> {
+        let (key, value, right) = self.stack.pop()?;
+        let above_end = match self.range.end_bound() {
+            Bound::Included(end) => key > end,
+            Bound::Excluded(end) => key >= end,
+            Bound::Unbounded => false,
+        };
+        if above_end {
+            self.stack.clear();
+            return None;
+        }
+        self.push_left_spine(right);
+        Some((key, value))
+    }
+}
+
+#[cfg(test)]
 mod tests {
-    use super::AVLTree;
+    use super::{AVLMap, AVLNode, AVLSumTree, AVLTree, AVLTreeCmp, NodeIter, Side, SubtreeSum};
+    use crate::math::PCG32;
+    use std::cmp::Ordering;
+    use std::collections::BTreeSet;
+    use std::ops::Bound;
+
+    /// A record ordered by its id alone.
+    #[derive(Debug)]
+    struct Record {
+        id: u32,
+        payload: &'static str,
+    }
+
+    impl PartialEq for Record {
+        fn eq(&self, other: &Self) -> bool {
+            self.id == other.id
+        }
+    }
+
+    impl Eq for Record {}
+
+    impl PartialOrd for Record {
+        fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+            Some(self.cmp(other))
+        }
+    }
+
+    impl Ord for Record {
+        fn cmp(&self, other: &Self) -> Ordering {
+            self.id.cmp(&other.id)
+        }
+    }
+
+    #[test]
+    fn sorted() {
+        let tree: AVLTree<_> = (1..8).rev().collect();
+        assert!((1..8).eq(tree.iter().map(|&x| x)));
+    }
+
+    #[test]
+    fn balance_stats() {
+        let tree: AVLTree<_> = (0..10).collect();
+        let stats = tree.balance_stats();
+        assert_eq!(stats.height, 4);
+        assert_eq!(stats.min_depth, 2);
+        assert_eq!(stats.max_depth, 3);
+        assert_eq!(stats.average_depth, 1.9);
+        // Every node has a balance factor in -1..=1
+        assert_eq!(
+            stats.left_heavy + stats.balanced + stats.right_heavy,
+            tree.len()
+        );
+
+        let empty: AVLTree<i32> = AVLTree::new();
+        assert_eq!(empty.balance_stats().height, 0);
+        assert_eq!(empty.balance_stats().average_depth, 0.0);
+    }
+
+    #[test]
+    fn snapshot() {
+        let mut tree: AVLTree<_> = (0..10).map(|x| x.to_string()).collect();
+        let snapshot = tree.snapshot();
+        for x in 0..5 {
+            tree.remove(&x.to_string());
+        }
+        tree.insert("a".to_string());
+        let expected: Vec<_> = (0..10).map(|x| x.to_string()).collect();
+        assert_eq!(snapshot, expected);
+        assert_eq!(tree.len(), 6);
+    }
+
+    #[test]
+    fn cloned_split_at_rank() {
+        let tree: AVLTree<_> = (0..100).collect();
+        let (low, high) = tree.cloned_split_at_rank(25);
+        assert_eq!(low.len(), 25);
+        assert_eq!(high.len(), 75);
+        assert!((0..25).eq(low.iter().copied()));
+        assert!((25..100).eq(high.iter().copied()));
+        assert!(low.is_balanced());
+        assert!(high.is_balanced());
+        assert_eq!(tree.len(), 100);
+        assert!((0..100).eq(tree.iter().copied()));
+
+        let (all, none) = tree.cloned_split_at_rank(1000);
+        assert_eq!(all.len(), 100);
+        assert!(none.is_empty());
+        let (none, all) = tree.cloned_split_at_rank(0);
+        assert!(none.is_empty());
+        assert!(all.is_balanced());
+    }
+
+    #[test]
+    fn to_eytzinger() {
+        let tree: AVLTree<_> = (1..=6).collect();
+        assert_eq!(tree.to_eytzinger(), vec![4, 2, 6, 1, 3, 5]);
+        assert!(AVLTree::<i32>::new().to_eytzinger().is_empty());
+
+        let tree: AVLTree<_> = (0..100).map(|x| x * 2).collect();
+        let layout = tree.to_eytzinger();
+        assert_eq!(layout.len(), 100);
+        let search = |target: i32| {
+            let mut i = 0;
+            while i < layout.len() {
+                match target.cmp(&layout[i]) {
+                    Ordering::Equal => return true,
+                    Ordering::Less => i = 2 * i + 1,
+                    Ordering::Greater => i = 2 * i + 2,
+                }
+            }
+            false
+        };
+        assert!((0..100).all(|x| search(x * 2)));
+        assert!((0..100).all(|x| !search(x * 2 + 1)));
+    }
+
+    #[test]
+    fn count_less_and_greater() {
+        let mut tree: AVLTree<_> = (0..100).collect();
+        assert_eq!(tree.count_less(&30), 30);
+        assert_eq!(tree.count_greater(&30), 69);
+
+        for x in (0..100).filter(|x| x % 4 == 0) {
+            tree.remove(&x);
+        }
+        for v in -5..105 {
+            let less = tree.count_less(&v);
+            let greater = tree.count_greater(&v);
+            assert_eq!(less, tree.iter().filter(|&&x| x < v).count());
+            assert_eq!(less + greater + tree.contains(&v) as usize, tree.len());
+        }
+        assert!(tree
+            .node_iter()
+            .all(|n| n.size == 1 + n.size(Side::Left) + n.size(Side::Right)));
+    }
+
+    #[test]
+    fn retain() {
+        let mut tree: AVLTree<_> = (0..100).collect();
+        tree.retain(|x| x % 2 == 0);
+        assert_eq!(tree.len(), 50);
+        assert!((0..100).step_by(2).eq(tree.iter().copied()));
+        assert!(tree.is_balanced());
+        assert!(!tree.contains(&51));
+
+        tree.retain(|&x| x >= 90);
+        assert!(tree.iter().eq([90, 92, 94, 96, 98].iter()));
+        assert!(tree.is_balanced());
+        tree.retain(|_| false);
+        assert!(tree.is_empty());
+    }
+
+    #[test]
+    fn retain_returning_removed() {
+        let mut tree: AVLTree<_> = (0..20).collect();
+        assert_eq!(tree.retain_returning_removed(|x| x % 2 == 0), 10);
+        assert_eq!(tree.len(), 10);
+        assert!((0..20).step_by(2).eq(tree.iter().copied()));
+        assert!(tree.is_balanced());
+
+        assert_eq!(tree.retain_returning_removed(|_| true), 0);
+        assert_eq!(tree.retain_returning_removed(|_| false), 10);
+        assert!(tree.is_empty());
+        assert!(tree.insert(3));
+    }
+
+    #[test]
+    fn retain_with_panicking_predicate() {
+        let mut tree: AVLTree<_> = (0..20).collect();
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            tree.retain(|&x| {
+                if x == 10 {
+                    panic!("predicate failed")
+                } else {
+                    true
+                }
+            })
+        }));
+        assert!(result.is_err());
+        assert_eq!(tree.len(), tree.recomputed_len());
+        assert!(tree.insert(5));
+        assert_eq!(tree.len(), tree.recomputed_len());
+    }
+
+    #[test]
+    fn union_all() {
+        let shards: Vec<AVLTree<_>> = (0..5).map(|i| (i * 20..(i + 1) * 20).collect()).collect();
+        let tree = AVLTree::union_all(shards);
+        assert_eq!(tree.len(), 100);
+        assert!((0..100).eq(tree.iter().copied()));
+        assert!(tree.is_balanced());
+
+        // Overlapping shards and an empty one
+        let shards: Vec<AVLTree<_>> = vec![
+            (0..50).step_by(2).collect(),
+            (0..50).step_by(3).collect(),
+            AVLTree::new(),
+            (40..60).collect(),
+        ];
+        let tree = AVLTree::union_all(shards);
+        let expected: Vec<i32> = (0..60)
+            .filter(|x| x % 2 == 0 || x % 3 == 0 || *x >= 40)
+            .collect();
+        assert!(expected.iter().eq(tree.iter()));
+        assert_eq!(tree.len(), expected.len());
+        assert!(tree.is_balanced());
+        assert!(AVLTree::<i32>::union_all(Vec::new()).is_empty());
+
+        // Values are moved rather than cloned, and equal values are taken from the earliest tree
+        let shards: Vec<AVLTree<_>> = vec![
+            vec![Record {
+                id: 1,
+                payload: "a",
+            }]
+            .into_iter()
+            .collect(),
+            vec![
+                Record {
+                    id: 1,
+                    payload: "b",
+                },
+                Record {
+                    id: 2,
+                    payload: "c",
+                },
+            ]
+            .into_iter()
+            .collect(),
+        ];
+        let records: Vec<_> = AVLTree::union_all(shards).into_iter().collect();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].payload, "a");
+        assert_eq!(records[1].payload, "c");
+    }
+
+    #[test]
+    fn chunks() {
+        let tree: AVLTree<_> = (0..10).collect();
+        let chunks: Vec<Vec<i32>> = tree
+            .chunks(3)
+            .map(|chunk| chunk.into_iter().copied().collect())
+            .collect();
+        assert_eq!(
+            chunks,
+            vec![vec![0, 1, 2], vec![3, 4, 5], vec![6, 7, 8], vec![9]]
+        );
+        assert_eq!(tree.chunks(5).count(), 2);
+        assert_eq!(tree.chunks(100).next().map(|chunk| chunk.len()), Some(10));
+        assert_eq!(AVLTree::<i32>::new().chunks(3).next(), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "chunk size must be positive")]
+    fn chunks_of_zero() {
+        let _ = AVLTree::<i32>::new().chunks(0);
+    }
+
+    #[test]
+    fn equal_range() {
+        // A multiset of keys, made unique by a sequence number
+        let entries = [(5, 0), (3, 1), (5, 2), (8, 3), (5, 4), (3, 5), (1, 6)];
+        let tree: AVLTree<(i32, usize)> = entries.iter().copied().collect();
+        let by_key = |key: i32| tree.equal_range_by(move |x: &(i32, usize)| x.0.cmp(&key));
+        assert_eq!(by_key(5), (Some(&(5, 0)), Some(&(5, 4))));
+        assert_eq!(by_key(3), (Some(&(3, 1)), Some(&(3, 5))));
+        assert_eq!(by_key(8), (Some(&(8, 3)), Some(&(8, 3))));
+        assert_eq!(by_key(4), (None, None));
+        assert_eq!(by_key(9), (None, None));
+        assert_eq!(tree.equal_range(&(5, 2)), (Some(&(5, 2)), Some(&(5, 2))));
+        assert_eq!(tree.equal_range(&(5, 1)), (None, None));
 
-    /// Returns `true` if all nodes in the tree are balanced.
-    fn is_balanced<T: Ord>(tree: &AVLTree<T>) -> bool {
-        tree.node_iter()
-            .all(|n| (-1..=1).contains(&n.balance_factor()))
+        let mut tree = AVLTreeCmp::new(|a: &(i32, usize), b: &(i32, usize)| a.0.cmp(&b.0));
+        for entry in entries.iter() {
+            tree.insert(*entry);
+        }
+        // Entries with an equal key were rejected, so each key has a single element
+        assert_eq!(tree.equal_range(&(5, 99)), (Some(&(5, 0)), Some(&(5, 0))));
+        assert_eq!(tree.equal_range(&(4, 0)), (None, None));
     }
 
     #[test]
-    fn sorted() {
-        let tree: AVLTree<_> = (1..8).rev().collect();
-        assert!((1..8).eq(tree.iter().map(|&x| x)));
+    fn from_unsorted() {
+        // A fixed shuffle containing every value in 0..200 twice
+        let values: Vec<i32> = (0..400).map(|i| (i * 151) % 400 / 2).collect();
+        let tree = AVLTree::from_unsorted(values.clone());
+        let expected: AVLTree<_> = values.into_iter().collect();
+        assert_eq!(tree.len(), 200);
+        assert_eq!(tree.len(), expected.len());
+        assert!(tree.iter().eq(expected.iter()));
+        assert!(tree.is_balanced());
+
+        let sorted = AVLTree::from_unsorted((0..100).collect());
+        assert!(sorted.iter().copied().eq(0..100));
+        assert!(sorted.is_balanced());
+
+        let empty: AVLTree<i32> = AVLTree::from_unsorted(Vec::new());
+        assert!(empty.is_empty());
+    }
+
+    #[test]
+    fn matches_btreeset() {
+        let tree: AVLTree<_> = vec![3, 1, 2].into_iter().collect();
+        let mut reference: BTreeSet<_> = (1..=3).collect();
+        assert!(tree.matches_btreeset(&reference));
+        reference.insert(4);
+        assert!(!tree.matches_btreeset(&reference));
+        reference.remove(&4);
+        reference.remove(&3);
+        reference.insert(0);
+        assert!(!tree.matches_btreeset(&reference));
+    }
+
+    #[test]
+    fn fuzz_ops() {
+        // Applies the same random operations to an AVL tree and a BTreeSet, which must agree
+        let mut rng = PCG32::new_default(2024);
+        let mut tree = AVLTree::new();
+        let mut reference = BTreeSet::new();
+        for step in 0..10_000 {
+            let value = rng.get_u32() % 500;
+            match rng.get_u32() % 3 {
+                0 => assert_eq!(tree.insert(value), reference.insert(value)),
+                1 => assert_eq!(tree.remove(&value), reference.remove(&value)),
+                _ => assert_eq!(tree.contains(&value), reference.contains(&value)),
+            }
+            if step % 500 == 0 {
+                assert!(tree.matches_btreeset(&reference));
+                assert!(tree.is_balanced());
+            }
+        }
+        assert!(tree.matches_btreeset(&reference));
+        assert!(tree.is_balanced());
+    }
+
+    #[test]
+    fn insert_with_rank() {
+        let mut tree: AVLTree<_> = vec![0, 2, 4, 6].into_iter().collect();
+        assert_eq!(tree.insert_with_rank(3), Some(2));
+        assert_eq!(tree.insert_with_rank(3), None);
+        assert_eq!(tree.insert_with_rank(-1), Some(0));
+        assert_eq!(tree.insert_with_rank(10), Some(6));
+        assert_eq!(tree.len(), 7);
+
+        // Ranks stay correct while rotations rearrange the tree
+        let mut tree = AVLTree::new();
+        for value in (0..100).map(|i| (i * 37) % 100) {
+            let rank = tree.insert_with_rank(value);
+            assert_eq!(rank, Some(tree.count_less(&value)));
+        }
+        assert!(tree.is_balanced());
+    }
+
+    #[test]
+    fn distinct_count_range() {
+        // Every value in 0..30 inserted one to three times
+        let values = (0..30).flat_map(|v| vec![v; v as usize % 3 + 1]);
+        let tree: AVLTree<i32> = values.collect();
+        assert_eq!(tree.distinct_count_range(..), 30);
+        assert_eq!(tree.distinct_count_range(5..10), 5);
+        assert_eq!(tree.distinct_count_range(5..=10), 6);
+        assert_eq!(
+            tree.distinct_count_range((Bound::Excluded(5), Bound::Excluded(10))),
+            4
+        );
+        assert_eq!(tree.distinct_count_range(..-1), 0);
+        assert_eq!(tree.distinct_count_range(25..), 5);
+        assert_eq!(
+            tree.distinct_count_range((Bound::Included(20), Bound::Included(10))),
+            0
+        );
+
+        let sparse: AVLTree<i32> = (0..100).step_by(10).collect();
+        assert_eq!(sparse.distinct_count_range(15..55), 4);
+        assert_eq!(sparse.distinct_count_range(11..19), 0);
+    }
+
+    #[test]
+    fn count_range() {
+        let tree: AVLTree<i32> = (0..50).step_by(5).collect();
+        // Windows straddling keys, with every kind of bound
+        assert_eq!(tree.count_range(3..12), 2);
+        assert_eq!(tree.count_range(5..10), 1);
+        assert_eq!(tree.count_range(5..=10), 2);
+        assert_eq!(
+            tree.count_range((Bound::Excluded(5), Bound::Included(10))),
+            1
+        );
+        assert_eq!(
+            tree.count_range((Bound::Excluded(5), Bound::Excluded(10))),
+            0
+        );
+        assert_eq!(tree.count_range(..=45), 10);
+        assert_eq!(tree.count_range(46..), 0);
+        // Empty ranges
+        assert_eq!(tree.count_range(6..9), 0);
+        assert_eq!(tree.count_range(10..10), 0);
+        assert_eq!(
+            tree.count_range((Bound::Included(30), Bound::Included(10))),
+            0
+        );
+        assert_eq!(AVLTree::<i32>::new().count_range(..), 0);
+
+        let bounds: Vec<Bound<i32>> = (-2..52)
+            .flat_map(|v| vec![Bound::Included(v), Bound::Excluded(v)])
+            .chain(Some(Bound::Unbounded))
+            .collect();
+        for &start in &bounds {
+            for &end in &bounds {
+                let range = (start, end);
+                assert_eq!(tree.count_range(range), tree.range(range).count());
+            }
+        }
+    }
+
+    #[test]
+    fn nth() {
+        let mut rng = PCG32::new_default(2732);
+        let tree: AVLTree<_> = (0..300).map(|_| rng.get_u32() % 1000).collect();
+        assert!((0..tree.len())
+            .map(|i| tree.nth(i).unwrap())
+            .eq(tree.iter()));
+        assert_eq!(tree.nth(tree.len()), None);
+        assert_eq!(tree.nth(usize::MAX), None);
+        assert_eq!(AVLTree::<i32>::new().nth(0), None);
+    }
+
+    #[test]
+    fn select_and_rank() {
+        let mut rng = PCG32::new_default(99);
+        let mut tree = AVLTree::new();
+        let mut reference: Vec<u32> = Vec::new();
+        for step in 0..3_000 {
+            let value = rng.get_u32() % 300;
+            let position = reference.binary_search(&value);
+            if rng.get_u32().is_multiple_of(3) {
+                assert_eq!(tree.remove(&value), position.is_ok());
+                if let Ok(i) = position {
+                    reference.remove(i);
+                }
+            } else {
+                assert_eq!(tree.insert(value), position.is_err());
+                if let Err(i) = position {
+                    reference.insert(i, value);
+                }
+            }
+            assert_eq!(tree.root.as_ref().map_or(0, |n| n.size), tree.len());
+            if step % 100 == 0 {
+                for (k, value) in reference.iter().enumerate() {
+                    assert_eq!(tree.select(k), Some(value));
+                    assert_eq!(tree.rank(value), k);
+                }
+                assert_eq!(tree.select(reference.len()), None);
+                for value in 0..300 {
+                    let expected = reference.iter().filter(|&&v| v < value).count();
+                    assert_eq!(tree.rank(&value), expected);
+                }
+            }
+        }
+        assert_eq!(tree.len(), reference.len());
+    }
+
+    #[test]
+    fn range() {
+        let tree: AVLTree<i32> = (0..50).map(|x| x * 2).collect();
+        let collect =
+            |range: (Bound<i32>, Bound<i32>)| -> Vec<i32> { tree.range(range).copied().collect() };
+        assert!(tree.range(..).copied().eq((0..50).map(|x| x * 2)));
+        assert_eq!(tree.range(-10..=200).count(), 50);
+        // Bounds between existing values
+        assert_eq!(
+            tree.range(11..17).copied().collect::<Vec<_>>(),
+            vec![12, 14, 16]
+        );
+        assert_eq!(
+            tree.range(11..=18).copied().collect::<Vec<_>>(),
+            vec![12, 14, 16, 18]
+        );
+        assert_eq!(
+            collect((Bound::Excluded(10), Bound::Excluded(18))),
+            vec![12, 14, 16]
+        );
+        assert_eq!(tree.range(..5).copied().collect::<Vec<_>>(), vec![0, 2, 4]);
+        assert_eq!(
+            tree.range(93..).copied().collect::<Vec<_>>(),
+            vec![94, 96, 98]
+        );
+        // Empty ranges
+        assert_eq!(tree.range(13..14).next(), None);
+        assert_eq!(tree.range(100..).next(), None);
+        assert_eq!(tree.range(..0).next(), None);
+        assert!(collect((Bound::Included(40), Bound::Included(20))).is_empty());
+        assert!(collect((Bound::Excluded(40), Bound::Excluded(42))).is_empty());
+        assert_eq!(AVLTree::<i32>::new().range(..).next(), None);
+    }
+
+    #[test]
+    fn range_visits_few_nodes() {
+        let tree: AVLTree<u32> = (0..1024).collect();
+        let height = tree.root.as_ref().unwrap().height;
+        let mut range = tree.range(500..505);
+        // The initial stack holds at most one path from the root
+        assert!(range.node_iter.front.len() <= height);
+        assert!(range.node_iter.back.len() <= height);
+        assert_eq!(range.by_ref().count(), 5);
+        // Once the last value is yielded, no further nodes are visited
+        let remaining = range.node_iter.front.len();
+        assert_eq!(range.next(), None);
+        assert_eq!(range.node_iter.front.len(), remaining);
+        assert!(remaining <= height);
+    }
+
+    #[test]
+    fn nearest_k() {
+        let tree: AVLTree<i32> = (0..100).collect();
+        assert_eq!(tree.nearest_k(&50, 3), vec![&50, &49, &51]);
+        assert_eq!(tree.nearest_k(&0, 3), vec![&0, &1, &2]);
+        assert_eq!(tree.nearest_k(&-5, 2), vec![&0, &1]);
+        assert_eq!(tree.nearest_k(&150, 2), vec![&99, &98]);
+        assert_eq!(tree.nearest_k(&50, 0), Vec::<&i32>::new());
+        assert_eq!(tree.nearest_k(&30, 1000).len(), 100);
+
+        // Unsigned values, with the target missing from the tree
+        let tree: AVLTree<u32> = vec![2, 10, 11, 19, 30].into_iter().collect();
+        assert_eq!(tree.nearest_k(&15, 4), vec![&11, &19, &10, &2]);
+        assert_eq!(tree.nearest_k(&0, 5), vec![&2, &10, &11, &19, &30]);
+        assert!(AVLTree::<u32>::new().nearest_k(&3, 2).is_empty());
+    }
+
+    #[test]
+    fn double_ended_iter() {
+        let tree: AVLTree<u32> = (0..100).map(|i| (i * 37) % 100).collect();
+        assert!(tree.iter().rev().copied().eq((0..100).rev()));
+
+        // Take from either end following a fixed pattern until the ends meet
+        let mut rng = PCG32::new_default(5);
+        for _ in 0..20 {
+            let mut iter = tree.iter();
+            let (mut front, mut back) = (Vec::new(), Vec::new());
+            loop {
+                if rng.get_u32().is_multiple_of(2) {
+                    match iter.next() {
+                        Some(&v) => front.push(v),
+                        None => break,
+                    }
+                } else {
+                    match iter.next_back() {
+                        Some(&v) => back.push(v),
+                        None => break,
+                    }
+                }
+            }
+            assert_eq!(iter.next(), None);
+            assert_eq!(iter.next_back(), None);
+            back.reverse();
+            front.extend(back);
+            assert!(front.into_iter().eq(0..100));
+        }
+
+        let empty: AVLTree<u32> = AVLTree::new();
+        assert_eq!(empty.iter().next_back(), None);
+    }
+
+    #[test]
+    fn double_ended_range() {
+        let tree: AVLTree<i32> = (0..50).collect();
+        assert!(tree.range(10..20).rev().copied().eq((10..20).rev()));
+        let mut range = tree.range(10..=12);
+        assert_eq!(range.next_back(), Some(&12));
+        assert_eq!(range.next(), Some(&10));
+        assert_eq!(range.next_back(), Some(&11));
+        assert_eq!(range.next(), None);
+        assert_eq!(range.next_back(), None);
+    }
+
+    #[test]
+    fn into_iter() {
+        let values: Vec<u32> = (0..200).map(|i| (i * 71) % 200).collect();
+        let tree: AVLTree<_> = values.into_iter().collect();
+        assert!(tree.into_iter().eq(0..200));
+        assert_eq!(AVLTree::<u32>::new().into_iter().next(), None);
+
+        // Values that cannot be cloned are moved out of the tree
+        #[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+        struct Token(String);
+        let tree: AVLTree<Token> = ["pear", "apple", "fig"]
+            .iter()
+            .map(|s| Token(s.to_string()))
+            .collect();
+        let tokens: Vec<Token> = tree.into_iter().collect();
+        assert_eq!(
+            tokens,
+            vec![
+                Token("apple".to_string()),
+                Token("fig".to_string()),
+                Token("pear".to_string())
+            ]
+        );
+    }
+
+    #[test]
+    fn into_iter_rev() {
+        let mut rng = PCG32::new_default(23);
+        let tree: AVLTree<u32> = (0..300).map(|_| rng.get_u32() % 500).collect();
+        let mut ascending: Vec<u32> = tree.iter().copied().collect();
+        ascending.reverse();
+        assert_eq!(tree.into_iter_rev().collect::<Vec<_>>(), ascending);
+        assert_eq!(AVLTree::<u32>::new().into_iter_rev().next(), None);
+
+        #[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+        struct Token(String);
+        let tokens = |words: &[&str]| -> AVLTree<Token> {
+            words.iter().map(|s| Token(s.to_string())).collect()
+        };
+        let words = ["pear", "apple", "fig", "kiwi", "date"];
+        let mut descending: Vec<Token> = tokens(&words).into_iter().collect();
+        descending.reverse();
+        assert_eq!(
+            tokens(&words).into_iter_rev().collect::<Vec<_>>(),
+            descending
+        );
+    }
+
+    #[test]
+    fn min_max() {
+        let mut tree = AVLTree::new();
+        assert_eq!(tree.min(), None);
+        assert_eq!(tree.max(), None);
+
+        tree.insert(42);
+        assert_eq!(tree.min(), Some(&42));
+        assert_eq!(tree.max(), Some(&42));
+
+        let mut rng = PCG32::new_default(31);
+        for _ in 0..500 {
+            tree.insert(rng.get_u32() % 10_000);
+            assert_eq!(tree.min(), tree.iter().next());
+            assert_eq!(tree.max(), tree.iter().next_back());
+        }
+        // The extremes follow removals
+        let min = *tree.min().unwrap();
+        let max = *tree.max().unwrap();
+        tree.remove(&min);
+        tree.remove(&max);
+        assert!(tree.min().unwrap() > &min);
+        assert!(tree.max().unwrap() < &max);
+        assert_eq!(tree.min(), tree.iter().next());
+        assert_eq!(tree.max(), tree.iter().next_back());
+    }
+
+    #[test]
+    fn floor_ceil() {
+        let tree: AVLTree<i32> = (1..=50).map(|x| x * 10).collect();
+        // Below the minimum
+        assert_eq!(tree.floor(&5), None);
+        assert_eq!(tree.ceil(&5), Some(&10));
+        // Above the maximum
+        assert_eq!(tree.floor(&501), Some(&500));
+        assert_eq!(tree.ceil(&501), None);
+        // Exactly on a key, including both ends
+        for x in [10, 250, 500].iter() {
+            assert_eq!(tree.floor(x), Some(x));
+            assert_eq!(tree.ceil(x), Some(x));
+        }
+        // Strictly between two keys
+        for x in (11..500).filter(|x| x % 10 != 0) {
+            assert_eq!(tree.floor(&x), Some(&(x - x % 10)));
+            assert_eq!(tree.ceil(&x), Some(&(x - x % 10 + 10)));
+        }
+        assert_eq!(AVLTree::<i32>::new().floor(&0), None);
+        assert_eq!(AVLTree::<i32>::new().ceil(&0), None);
+    }
+
+    #[test]
+    fn predecessor_successor() {
+        let mut rng = PCG32::new_default(47);
+        let tree: AVLTree<u32> = (0..400).map(|_| rng.get_u32() % 1000).collect();
+        let sorted: Vec<u32> = tree.iter().copied().collect();
+
+        // Walking the successors from the minimum visits every value in order
+        let mut walk = Vec::new();
+        let mut current = tree.min();
+        while let Some(&value) = current {
+            walk.push(value);
+            current = tree.successor(&value);
+        }
+        assert_eq!(walk, sorted);
+
+        // And walking the predecessors from the maximum visits them in reverse
+        let mut walk = Vec::new();
+        let mut current = tree.max();
+        while let Some(&value) = current {
+            walk.push(value);
+            current = tree.predecessor(&value);
+        }
+        walk.reverse();
+        assert_eq!(walk, sorted);
+
+        // Neighbours of every value in range, whether it is in the tree or not
+        for x in 0..1001 {
+            let below = sorted.iter().rev().find(|&&v| v < x);
+            let above = sorted.iter().find(|&&v| v > x);
+            assert_eq!(tree.predecessor(&x), below);
+            assert_eq!(tree.successor(&x), above);
+        }
+        assert_eq!(AVLTree::<u32>::new().successor(&0), None);
+        assert_eq!(AVLTree::<u32>::new().predecessor(&0), None);
+    }
+
+    #[test]
+    fn structural_eq() {
+        // 1 ends up below 2 in the first tree, but 4 below 3 in the second
+        let a: AVLTree<_> = vec![2, 1, 3, 4].into_iter().collect();
+        let b: AVLTree<_> = vec![3, 2, 4, 1].into_iter().collect();
+        assert!(a == b);
+        assert!(!a.structural_eq(&b));
+        assert!(a.structural_eq(&a));
+
+        // Rotations can still lead to the same shape
+        let c: AVLTree<_> = vec![2, 3, 1, 4].into_iter().collect();
+        assert!(a.structural_eq(&c));
+
+        let d: AVLTree<_> = vec![2, 1, 3].into_iter().collect();
+        assert!(a != d);
+        assert!(!a.structural_eq(&d));
+        assert!(AVLTree::<i32>::new().structural_eq(&AVLTree::new()));
+    }
+
+    #[test]
+    fn get_returns_stored_value() {
+        let mut tree = AVLTree::new();
+        for (id, payload) in [(4, "four"), (1, "one"), (7, "seven")].iter() {
+            tree.insert(Record { id: *id, payload });
+        }
+        let query = Record {
+            id: 7,
+            payload: "query",
+        };
+        assert_eq!(tree.get(&query).map(|r| r.payload), Some("seven"));
+        assert!(tree.get(&Record { id: 5, payload: "" }).is_none());
+        // Inserting an equal record keeps the original one
+        assert!(!tree.insert(Record {
+            id: 1,
+            payload: "uno"
+        }));
+        assert_eq!(
+            tree.get(&Record { id: 1, payload: "" }).unwrap().payload,
+            "one"
+        );
+    }
+
+    #[test]
+    fn replace_swaps_payload() {
+        let mut tree = AVLTree::new();
+        for (id, payload) in [(4, "four"), (1, "one"), (7, "seven")].iter() {
+            assert!(tree.replace(Record { id: *id, payload }).is_none());
+        }
+        assert_eq!(tree.len(), 3);
+
+        let old = tree.replace(Record {
+            id: 1,
+            payload: "uno",
+        });
+        assert_eq!(old.map(|r| r.payload), Some("one"));
+        assert_eq!(tree.len(), 3);
+        assert_eq!(
+            tree.get(&Record { id: 1, payload: "" }).unwrap().payload,
+            "uno"
+        );
+        let old = tree.replace(Record {
+            id: 1,
+            payload: "eins",
+        });
+        assert_eq!(old.map(|r| r.payload), Some("uno"));
+
+        let payloads: Vec<_> = tree.iter().map(|r| r.payload).collect();
+        assert_eq!(payloads, vec!["eins", "four", "seven"]);
+        assert!(tree.is_balanced());
+    }
+
+    #[test]
+    fn pop_min_max() {
+        let mut rng = PCG32::new_default(53);
+        let mut values: Vec<u32> = (0..300).collect();
+        for i in (1..values.len()).rev() {
+            values.swap(i, rng.get_u32() as usize % (i + 1));
+        }
+        let mut tree: AVLTree<u32> = values.into_iter().collect();
+        for expected in 0..150 {
+            assert_eq!(tree.pop_min(), Some(expected));
+            assert_eq!(tree.len(), 299 - expected as usize);
+            assert!(tree.is_balanced());
+        }
+        for expected in (150..300).rev() {
+            assert_eq!(tree.pop_max(), Some(expected));
+            assert!(tree.is_balanced());
+        }
+        assert!(tree.is_empty());
+        assert_eq!(tree.pop_min(), None);
+        assert_eq!(tree.pop_max(), None);
+
+        // The sizes stay correct for rank queries afterwards
+        let mut tree: AVLTree<u32> = (0..50).collect();
+        tree.pop_min();
+        tree.pop_max();
+        assert_eq!(tree.select(0), Some(&1));
+        assert_eq!(tree.rank(&48), 47);
+    }
+
+    #[test]
+    fn rotate_root() {
+        //   2            4
+        //  / \          / \
+        // 1   4   ->   2   5
+        //    / \      / \
+        //   3   5    1   3
+        let mut tree: AVLTree<_> = vec![2, 1, 4, 3, 5].into_iter().collect();
+        assert!(tree.rotate_root(true));
+        let root = tree.root.as_ref().unwrap();
+        assert_eq!(root.value, 4);
+        let left = root.left.as_ref().unwrap();
+        assert_eq!(left.value, 2);
+        assert_eq!(left.left.as_ref().unwrap().value, 1);
+        assert_eq!(left.right.as_ref().unwrap().value, 3);
+        assert_eq!(root.right.as_ref().unwrap().value, 5);
+        assert_eq!(
+            (root.height, root.size, left.height, left.size),
+            (3, 5, 2, 3)
+        );
+        assert!(tree.iter().copied().eq(1..=5));
+        assert_eq!(tree.rank(&3), 2);
+
+        // Rotating right restores the original shape
+        assert!(tree.rotate_root(false));
+        let original: AVLTree<_> = vec![2, 1, 4, 3, 5].into_iter().collect();
+        assert!(tree.structural_eq(&original));
+
+        // A rotation needs the child that moves up
+        let mut tree: AVLTree<_> = vec![2, 1].into_iter().collect();
+        assert!(!tree.rotate_root(true));
+        assert!(tree.rotate_root(false));
+        assert_eq!(tree.root.as_ref().unwrap().value, 1);
+        assert!(!AVLTree::<i32>::new().rotate_root(true));
+    }
+
+    #[test]
+    fn clone_is_independent() {
+        let mut rng = PCG32::new_default(59);
+        let mut tree: AVLTree<u32> = (0..200).map(|_| rng.get_u32() % 1000).collect();
+        let snapshot = tree.clone();
+        assert!(snapshot.structural_eq(&tree));
+        let before: Vec<u32> = tree.iter().copied().collect();
+
+        for _ in 0..100 {
+            let value = rng.get_u32() % 1000;
+            if !tree.remove(&value) {
+                tree.insert(value);
+            }
+        }
+        tree.pop_min();
+        assert!(tree != snapshot);
+        assert!(snapshot.iter().eq(before.iter()));
+        assert_eq!(snapshot.len(), before.len());
+        assert!(snapshot.is_balanced());
+        assert_eq!(snapshot.select(10), Some(&before[10]));
+    }
+
+    #[test]
+    fn debug_format() {
+        let tree: AVLTree<_> = vec![2, 1, 4, 3, 5].into_iter().collect();
+        assert_eq!(format!("{:?}", tree), "{1, 2, 3, 4, 5}");
+        assert_eq!(
+            format!("{:#?}", tree),
+            "2\n    L: 1\n    R: 4\n        L: 3\n        R: 5"
+        );
+
+        let tree: AVLTree<_> = vec!["b", "a"].into_iter().collect();
+        assert_eq!(format!("{:?}", tree), r#"{"a", "b"}"#);
+        assert_eq!(format!("{:#?}", tree), "\"b\"\n    L: \"a\"");
+
+        assert_eq!(format!("{:?}", AVLTree::<i32>::new()), "{}");
+        assert_eq!(format!("{:#?}", AVLTree::<i32>::new()), "{}");
+    }
+
+    #[test]
+    fn equality_and_hashing() {
+        use std::collections::hash_map::DefaultHasher;
+        use std::collections::HashMap;
+        use std::hash::{Hash, Hasher};
+
+        fn hash_of(tree: &AVLTree<u32>) -> u64 {
+            let mut hasher = DefaultHasher::new();
+            tree.hash(&mut hasher);
+            hasher.finish()
+        }
+
+        let ascending: AVLTree<u32> = (0..100).collect();
+        let scrambled: AVLTree<u32> = (0..100).map(|i| (i * 37) % 100).collect();
+        assert!(!ascending.structural_eq(&scrambled));
+        assert!(ascending == scrambled);
+        assert_eq!(hash_of(&ascending), hash_of(&scrambled));
+
+        let mut fewer = scrambled.clone();
+        fewer.remove(&50);
+        assert!(fewer != ascending);
+        assert_ne!(hash_of(&fewer), hash_of(&ascending));
+
+        // Trees work as keys of a hash map
+        let mut counts = HashMap::new();
+        *counts.entry(ascending).or_insert(0) += 1;
+        *counts.entry(scrambled).or_insert(0) += 1;
+        *counts.entry(fewer).or_insert(0) += 1;
+        assert_eq!(counts.len(), 2);
+        assert_eq!(counts[&(0..100).collect::<AVLTree<u32>>()], 2);
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn serde_round_trip() {
+        use serde::de::value::{self, SeqDeserializer};
+        use serde::Deserialize;
+        use serde_test::{assert_de_tokens, assert_tokens, Token};
+
+        let tree: AVLTree<u32> = vec![3, 1, 2].into_iter().collect();
+        assert_tokens(
+            &tree,
+            &[
+                Token::Seq { len: Some(3) },
+                Token::U32(1),
+                Token::U32(2),
+                Token::U32(3),
+                Token::SeqEnd,
+            ],
+        );
+        assert_tokens(
+            &AVLTree::<u32>::new(),
+            &[Token::Seq { len: Some(0) }, Token::SeqEnd],
+        );
+
+        // Any order is accepted, including descending input that would degenerate a plain BST
+        let mut tokens = vec![Token::Seq { len: None }];
+        tokens.extend((0..100).rev().map(Token::U32));
+        tokens.push(Token::SeqEnd);
+        let expected: AVLTree<u32> = (0..100).collect();
+        assert_de_tokens(&expected, &tokens);
+        let values = SeqDeserializer::<_, value::Error>::new((0..100u32).rev());
+        let restored = AVLTree::<u32>::deserialize(values).unwrap();
+        assert!(restored.is_balanced());
+        assert_eq!(restored.len(), 100);
+    }
+
+    #[test]
+    fn from_sorted() {
+        for len in 0..200 {
+            let tree = AVLTree::from_sorted((0..len).collect());
+            let inserted: AVLTree<_> = (0..len).collect();
+            assert!(tree == inserted);
+            assert!(tree.is_balanced());
+            assert_eq!(tree.len(), len);
+            // The height is as small as possible for the number of values
+            let height = tree.root.as_ref().map_or(0, |n| n.height);
+            assert_eq!(height, (usize::BITS - len.leading_zeros()) as usize);
+            assert!(tree
+                .iter()
+                .enumerate()
+                .all(|(i, &v)| tree.select(i) == Some(&v)));
+        }
+
+        // Duplicates are dropped
+        let tree = AVLTree::from_sorted(vec![1, 1, 2, 3, 3, 3, 4]);
+        assert_eq!(tree.len(), 4);
+        assert!(tree.iter().copied().eq(1..=4));
+        assert!(tree.is_balanced());
+
+        let tree = AVLTree::from_sorted_unchecked(vec!["a", "b", "c"]);
+        assert!(tree.contains(&"a") && tree.contains(&"c"));
+    }
+
+    #[test]
+    #[should_panic(expected = "values must be sorted in ascending order")]
+    fn from_sorted_rejects_unsorted() {
+        AVLTree::from_sorted(vec![1, 3, 2]);
+    }
+
+    #[test]
+    fn set_operations() {
+        use std::collections::HashSet;
+
+        fn check(a: &[u32], b: &[u32]) {
+            let tree_a: AVLTree<u32> = a.iter().copied().collect();
+            let tree_b: AVLTree<u32> = b.iter().copied().collect();
+            let set_a: HashSet<u32> = a.iter().copied().collect();
+            let set_b: HashSet<u32> = b.iter().copied().collect();
+            let sorted = |set: HashSet<&u32>| {
+                let mut values: Vec<u32> = set.into_iter().copied().collect();
+                values.sort_unstable();
+                values
+            };
+
+            let results = [
+                (tree_a.union(&tree_b), sorted(set_a.union(&set_b).collect())),
+                (
+                    tree_a.intersection(&tree_b),
+                    sorted(set_a.intersection(&set_b).collect()),
+                ),
+                (
+                    tree_a.difference(&tree_b),
+                    sorted(set_a.difference(&set_b).collect()),
+                ),
+                (
+                    tree_b.difference(&tree_a),
+                    sorted(set_b.difference(&set_a).collect()),
+                ),
+            ];
+            for (tree, expected) in results.iter() {
+                assert!(
+                    tree.iter().eq(expected.iter()),
+                    "{:?} != {:?}",
+                    tree,
+                    expected
+                );
+                assert_eq!(tree.len(), expected.len());
+                assert!(tree.is_balanced());
+            }
+        }
+
+        let evens: Vec<u32> = (0..50).map(|i| i * 2).collect();
+        let odds: Vec<u32> = (0..50).map(|i| i * 2 + 1).collect();
+        // Disjoint, identical, partially overlapping and empty sets
+        check(&evens, &odds);
+        check(&[100, 200], &[1, 2, 3]);
+        check(&evens, &evens);
+        check(&evens, &(0..60).collect::<Vec<_>>());
+        check(&[], &odds);
+        check(&[], &[]);
+
+        let mut rng = PCG32::new_default(71);
+        for _ in 0..20 {
+            let a: Vec<u32> = (0..rng.get_u32() % 100)
+                .map(|_| rng.get_u32() % 150)
+                .collect();
+            let b: Vec<u32> = (0..rng.get_u32() % 100)
+                .map(|_| rng.get_u32() % 150)
+                .collect();
+            check(&a, &b);
+        }
+    }
+
+    #[test]
+    fn from_ranges() {
+        let tree = AVLTree::from_ranges(&[0..5, 3..8, 20..22]);
+        assert!(tree.iter().eq([0, 1, 2, 3, 4, 5, 6, 7, 20, 21].iter()));
+        assert!(tree.is_balanced());
+
+        // Nested, adjacent, unordered and empty ranges
+        let tree = AVLTree::from_ranges(&[30..40, 5..10, 10..12, 32..35, 7..7, -3..0]);
+        let expected: Vec<i64> = (-3..0).chain(5..12).chain(30..40).collect();
+        assert!(tree.iter().eq(expected.iter()));
+        assert_eq!(tree.len(), expected.len());
+        assert!(AVLTree::<u8>::from_ranges(&[]).is_empty());
+        assert_eq!(AVLTree::from_ranges(&[100u8..255, 0..100]).len(), 255);
+    }
+
+    #[test]
+    fn extend() {
+        let mut tree: AVLTree<_> = (1..5).collect();
+        tree.extend(5..9);
+        assert!((1..9).eq(tree.iter().copied()));
+        assert_eq!(tree.len(), 8);
+        assert!(tree.is_balanced());
+
+        // Duplicates are ignored, and values can be copied out of references
+        tree.extend([0, 4, 8, 12].iter());
+        assert!(tree.iter().eq([0, 1, 2, 3, 4, 5, 6, 7, 8, 12].iter()));
+        assert_eq!(tree.len(), 10);
+        assert!(tree.is_balanced());
+    }
+
+    #[test]
+    fn split_off() {
+        fn check(tree: &AVLTree<i32>, expected: &[i32]) {
+            assert!(tree.iter().eq(expected.iter()));
+            assert_eq!(tree.len(), expected.len());
+            assert!(tree.is_balanced());
+            assert!(tree.node_iter().all(|n| {
+                n.size == 1 + n.size(Side::Left) + n.size(Side::Right)
+                    && n.height == 1 + n.height(Side::Left).max(n.height(Side::Right))
+            }));
+        }
+        let evens = || (0..40).step_by(2).collect::<AVLTree<_>>();
+        let values: Vec<i32> = (0..40).step_by(2).collect();
+
+        // A present key goes to the returned tree, an absent one splits between its neighbours
+        let mut tree = evens();
+        let high = tree.split_off(&10);
+        check(&tree, &values[..5]);
+        check(&high, &values[5..]);
+        let mut tree = evens();
+        let high = tree.split_off(&23);
+        check(&tree, &values[..12]);
+        check(&high, &values[12..]);
+
+        // Before the minimum and after the maximum
+        let mut tree = evens();
+        let high = tree.split_off(&-1);
+        check(&tree, &[]);
+        check(&high, &values);
+        let mut tree = evens();
+        let high = tree.split_off(&100);
+        check(&tree, &values);
+        check(&high, &[]);
+
+        // Every split of trees of many shapes
+        let mut rng = PCG32::new_default(2692);
+        for len in 0..60 {
+            let values: Vec<i32> = (0..len).map(|_| (rng.get_u32() % 200) as i32).collect();
+            let reference: BTreeSet<i32> = values.iter().copied().collect();
+            for key in (-1..201).step_by(7) {
+                let mut tree: AVLTree<_> = values.iter().copied().collect();
+                let high = tree.split_off(&key);
+                let low: Vec<i32> = reference.range(..key).copied().collect();
+                let rest: Vec<i32> = reference.range(key..).copied().collect();
+                check(&tree, &low);
+                check(&high, &rest);
+            }
+        }
+    }
+
+    #[test]
+    fn avl_map_ordering() {
+        let mut rng = PCG32::new_default(2712);
+        let mut map = AVLMap::new();
+        let mut reference = std::collections::BTreeMap::new();
+        for _ in 0..2000 {
+            let key = rng.get_u32() % 300;
+            let value = rng.get_u32();
+            if rng.get_u32().is_multiple_of(3) {
+                assert_eq!(map.remove(&key), reference.remove(&key));
+            } else {
+                assert_eq!(map.insert(key, value), reference.insert(key, value));
+            }
+            assert_eq!(map.len(), reference.len());
+        }
+        assert!(map.iter().eq(reference.iter()));
+        assert!(map.iter().rev().eq(reference.iter().rev()));
+        assert!(NodeIter::new(&map.root).all(|n| (-1..=1).contains(&n.balance_factor())));
+        for key in 0..300 {
+            assert_eq!(map.get(&key), reference.get(&key));
+        }
+    }
+
+    #[test]
+    fn avl_map_overwrite() {
+        // Keys equal by id but with different payloads show which key is kept
+        let mut map = AVLMap::new();
+        let first = Record {
+            id: 1,
+            payload: "first",
+        };
+        assert_eq!(map.insert(first, 10), None);
+        assert_eq!(
+            map.insert(
+                Record {
+                    id: 1,
+                    payload: "second"
+                },
+                20
+            ),
+            Some(10)
+        );
+        assert_eq!(map.len(), 1);
+        let (key, value) = map.iter().next().unwrap();
+        assert_eq!((key.payload, *value), ("first", 20));
+
+        if let Some(value) = map.get_mut(&Record { id: 1, payload: "" }) {
+            *value += 1;
+        }
+        assert_eq!(map.get(&Record { id: 1, payload: "" }), Some(&21));
+        assert_eq!(map.get_mut(&Record { id: 2, payload: "" }), None);
+
+        let map: AVLMap<_, _> = vec![(2, 'a'), (1, 'b'), (2, 'c')].into_iter().collect();
+        assert_eq!(map.iter().collect::<Vec<_>>(), vec![(&1, &'b'), (&2, &'c')]);
+        assert_eq!(format!("{:?}", map), "{1: 'b', 2: 'c'}");
+        assert!(AVLMap::<i32, i32>::default().iter().next().is_none());
+    }
+
+    #[test]
+    fn avl_map_add_to_range() {
+        let mut map: AVLMap<u32, u32> = (0..100).map(|k| (k, k * 2)).collect();
+        map.add_to_range(20..30, 10);
+        for (&key, &value) in map.iter() {
+            let expected = if (20..30).contains(&key) {
+                key * 2 + 10
+            } else {
+                key * 2
+            };
+            assert_eq!(value, expected, "key {}", key);
+        }
+        assert_eq!(map.len(), 100);
+
+        // Bounds of every kind, including ranges that hold no keys
+        let keys = |map: &mut AVLMap<u32, u32>, range: (Bound<u32>, Bound<u32>)| {
+            map.range_mut(range).map(|(&k, _)| k).collect::<Vec<_>>()
+        };
+        assert_eq!(
+            keys(&mut map, (Bound::Excluded(95), Bound::Unbounded)),
+            vec![96, 97, 98, 99]
+        );
+        assert_eq!(
+            keys(&mut map, (Bound::Unbounded, Bound::Included(2))),
+            vec![0, 1, 2]
+        );
+        assert!(keys(&mut map, (Bound::Included(150), Bound::Unbounded)).is_empty());
+        assert!(keys(&mut map, (Bound::Excluded(5), Bound::Excluded(6))).is_empty());
+        assert!(AVLMap::<u32, u32>::new().range_mut(..).next().is_none());
+    }
+
+    #[test]
+    fn avl_map_fold_by() {
+        let map: AVLMap<u32, u32> = (1..=10).map(|k| (k, k * 10)).collect();
+        let sums = map.fold_by(0, |&key, _| key % 3, |sum, &value| sum + value);
+        let expected = vec![(&0, &180), (&1, &220), (&2, &150)];
+        assert_eq!(sums.iter().collect::<Vec<_>>(), expected);
+
+        // Every group is folded in key order
+        let map: AVLMap<u32, char> = vec![(5, 'e'), (3, 'c'), (8, 'h'), (1, 'a'), (4, 'd')]
+            .into_iter()
+            .collect();
+        let groups = map.fold_by(
+            String::new(),
+            |&key, _| key % 2,
+            |mut acc, &c| {
+                acc.push(c);
+                acc
+            },
+        );
+        assert_eq!(groups.get(&0).map(String::as_str), Some("dh"));
+        assert_eq!(groups.get(&1).map(String::as_str), Some("ace"));
+        assert!(AVLMap::<u32, u32>::new()
+            .fold_by(0, |&k, _| k, |a, _| a)
+            .is_empty());
+    }
+
+    #[test]
+    fn height_and_is_balanced() {
+        let mut tree: AVLTree<_> = (1..=3).collect();
+        assert_eq!(tree.height(), 2);
+        assert!(tree.is_balanced());
+
+        // Rotating the root of 1 <- 2 -> 3 leaves a path of three nodes
+        assert!(tree.rotate_root(true));
+        assert_eq!(tree.height(), 3);
+        assert!(!tree.is_balanced());
+
+        let tree: AVLTree<_> = (0..1000).collect();
+        assert!(tree.is_balanced());
+        assert!(tree.height() <= 15);
+        assert_eq!(tree.height(), tree.balance_stats().height);
+        assert!(AVLTree::<i32>::new().is_balanced());
+    }
+
+    #[test]
+    fn recomputed_len() {
+        let mut rng = PCG32::new_default(273);
+        let mut tree = AVLTree::new();
+        assert_eq!(tree.recomputed_len(), 0);
+        for _ in 0..1000 {
+            let value = rng.get_u32() % 200;
+            if rng.get_u32().is_multiple_of(3) {
+                tree.remove(&value);
+            } else {
+                tree.insert(value);
+            }
+            assert_eq!(tree.len(), tree.recomputed_len());
+        }
+
+        let len = tree.len();
+        tree.corrupt_length(len + 1);
+        assert_ne!(tree.len(), tree.recomputed_len());
+        assert_eq!(tree.recomputed_len(), len);
+    }
+
+    #[test]
+    fn lower_and_upper_bound() {
+        let tree: AVLTree<i32> = (0..100).step_by(3).collect();
+        for value in -2..102 {
+            assert_eq!(tree.lower_bound(&value).next(), tree.ceil(&value));
+            assert_eq!(tree.upper_bound(&value).next(), tree.successor(&value));
+            assert!(tree
+                .lower_bound(&value)
+                .eq(tree.iter().filter(|&&x| x >= value)));
+            assert!(tree
+                .upper_bound(&value)
+                .eq(tree.iter().filter(|&&x| x > value)));
+        }
+        // Past the maximum, and on an empty tree
+        assert_eq!(tree.lower_bound(&100).count(), 0);
+        assert_eq!(tree.upper_bound(&99).count(), 0);
+        assert_eq!(tree.lower_bound(&-5).count(), tree.len());
+        assert_eq!(AVLTree::<i32>::new().lower_bound(&0).next(), None);
+        // The iterators are double-ended like any range
+        assert_eq!(tree.lower_bound(&50).next_back(), Some(&99));
+    }
+
+    #[test]
+    fn into_sorted_vec_and_to_vec() {
+        let mut rng = PCG32::new_default(2752);
+        let tree: AVLTree<_> = (0..500).map(|_| rng.get_u32() % 1000).collect();
+        let expected: Vec<u32> = tree.iter().cloned().collect();
+        let copy = tree.to_vec();
+        assert_eq!(copy, expected);
+        assert_eq!(copy.capacity(), tree.len());
+        let values = tree.into_sorted_vec();
+        assert_eq!(values, expected);
+        assert_eq!(values.capacity(), values.len());
+
+        // Values that cannot be cloned are moved out
+        let records: AVLTree<_> = vec![3, 1, 2]
+            .into_iter()
+            .map(|id| Record { id, payload: "" })
+            .collect();
+        let ids: Vec<u32> = records.into_sorted_vec().iter().map(|r| r.id).collect();
+        assert_eq!(ids, vec![1, 2, 3]);
+        assert!(AVLTree::<i32>::new().into_sorted_vec().is_empty());
+        assert!(AVLTree::<i32>::new().to_vec().is_empty());
+    }
+
+    /// Checks heights, balance factors and sums of every node.
+    fn sums_are_consistent(
+        tree: &Option<Box<AVLNode<i64, SubtreeSum<i64>>>>,
+    ) -> Option<(usize, i64)> {
+        match tree {
+            None => Some((0, 0)),
+            Some(node) => {
+                let (left_height, left_sum) = sums_are_consistent(&node.left)?;
+                let (right_height, right_sum) = sums_are_consistent(&node.right)?;
+                let height = 1 + left_height.max(right_height);
+                let sum = left_sum + node.value + right_sum;
+                let balanced = (left_height as isize - right_height as isize).abs() <= 1;
+                if balanced && node.height == height && node.aggregate.0 == sum {
+                    Some((height, sum))
+                } else {
+                    None
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn sum_tree_range_sum() {
+        let tree: AVLSumTree<i64> = (0..100).collect();
+        assert_eq!(tree.range_sum(10..20), (10..20).sum());
+        assert_eq!(tree.range_sum(10..=20), (10..=20).sum());
+        assert_eq!(tree.range_sum(..50), (0..50).sum());
+        assert_eq!(tree.range_sum(90..), (90..100).sum());
+        assert_eq!(tree.range_sum(..), (0..100).sum());
+        assert_eq!(
+            tree.range_sum((Bound::Excluded(10), Bound::Included(20))),
+            (11..=20).sum()
+        );
+    }
+
+    #[test]
+    fn sum_tree_empty_ranges() {
+        let tree: AVLSumTree<i64> = (0..100).step_by(10).collect();
+        assert_eq!(tree.range_sum(11..20), 0);
+        assert_eq!(tree.range_sum(200..), 0);
+        assert_eq!(tree.range_sum(..0), 0);
+        assert_eq!(AVLSumTree::<i64>::new().range_sum(..), 0);
+    }
+
+    #[test]
+    fn sum_tree_sums_after_removal() {
+        let mut tree: AVLSumTree<i64> = (0..100).collect();
+        for value in (0..100).filter(|v| v % 3 == 0) {
+            assert!(tree.remove(&value));
+        }
+        assert!(!tree.remove(&0));
+        assert_eq!(tree.len(), 66);
+        assert!(sums_are_consistent(&tree.root).is_some());
+        let expected: i64 = (25..75).filter(|v| v % 3 != 0).sum();
+        assert_eq!(tree.range_sum(25..75), expected);
+    }
+
+    #[test]
+    fn sum_tree_consistent_after_inserts() {
+        let mut tree = AVLSumTree::new();
+        for value in (0..200).rev().chain(200..400).map(|v| (v * 37) % 401) {
+            tree.insert(value);
+            assert!(sums_are_consistent(&tree.root).is_some());
+        }
+        // Every residue modulo 401 except 400 * 37 % 401 = 364 was inserted
+        assert_eq!(tree.len(), 400);
+        assert!(tree.contains(&100));
+        assert!(!tree.contains(&364));
+    }
+
+    #[test]
+    fn sum_tree_negative_values() {
+        let tree: AVLSumTree<i32> = (-50..50).collect();
+        assert_eq!(tree.range_sum(-10..=10), 0);
+        assert_eq!(tree.range_sum(..0), (-50..0).sum());
+    }
+
+    #[test]
+    fn reversed_comparator() {
+        let mut tree = AVLTreeCmp::reversed();
+        for x in [5, 1, 8, 3, 9, 2].iter() {
+            assert!(tree.insert(*x));
+        }
+        assert!(!tree.insert(8));
+        assert_eq!(tree.len(), 6);
+        assert!(tree.iter().copied().eq(vec![9, 8, 5, 3, 2, 1]));
+        assert_eq!(tree.min(), Some(&9));
+        assert_eq!(tree.max(), Some(&1));
+
+        assert!(tree.remove(&9));
+        assert!(!tree.contains(&9));
+        assert_eq!(tree.min(), Some(&8));
+    }
+
+    #[test]
+    fn custom_comparator() {
+        // Order by the second field only
+        let mut tree = AVLTreeCmp::new(|a: &(char, i32), b: &(char, i32)| a.1.cmp(&b.1));
+        tree.insert(('a', 3));
+        tree.insert(('b', 1));
+        assert!(!tree.insert(('c', 3)));
+        assert!(tree.contains(&('z', 1)));
+        assert!(tree.iter().eq(vec![&('b', 1), &('a', 3)]));
     }
 
     #[test]
     fn balanced() {
         let mut tree: AVLTree<_> = (1..8).collect();
-        assert!(is_balanced(&tree));
+        assert!(tree.is_balanced());
         for x in 1..8 {
             tree.remove(&x);
-            assert!(is_balanced(&tree));
+            assert!(tree.is_balanced());
         }
     }
 }
@@ -11,6 +11,9 @@ use bitvec::prelude::*;
 use std::collections::hash_map::DefaultHasher;
 use std::hash::{Hash, Hasher};
 
+/// A hash function over the bytes of an element.
+type HashFunction = Box<dyn Fn(&[u8]) -> u64>;
+
 /// Simple Bloom Filter implementation with a given size and number of hash functions.
 /// Multiple hash functions are used to reduce the probability of false positives.
 ///
@@ -54,16 +57,114 @@ pub struct BloomFilter {
     /// Stores bits to indicate whether an element may be in the set
     bit_array: BitVec,
     /// Hash functions to use
-    hash_functions: Vec<Box<dyn Fn(&[u8]) -> u64>>,
+    hash_functions: Vec<HashFunction>,
+    /// Whether the hash functions are the double hashes of `with_rate`, which only depend on
+    /// their number, rather than arbitrary functions passed to `new`
+    double_hashing: bool,
 }
 
 impl BloomFilter {
     /// Creates a new Bloom Filter with the given size and hash functions
-    pub fn new(size: usize, hash_functions: Vec<Box<dyn Fn(&[u8]) -> u64>>) -> Self {
+    pub fn new(size: usize, hash_functions: Vec<HashFunction>) -> Self {
         BloomFilter {
             bit_array: bitvec![0; size],
             hash_functions,
+            double_hashing: false,
+        }
+    }
+
+    /// Creates a new Bloom Filter sized to hold `capacity` elements with a false positive rate of
+    /// about `fp_rate`.
+    ///
+    /// The optimal number of bits is `m = -n ln(p) / ln(2)^2` and the optimal number of hash
+    /// functions is `k = m / n * ln(2)`, for `n` elements and rate `p`. The `k` hash functions are
+    /// derived from two base hashes by double hashing, `h1 + i * h2` for `i` in `0..k`, which is as
+    /// good as `k` independent hashes for the false positive rate.
+    ///
+    /// Panics if `capacity` is zero or `fp_rate` does not lie strictly between 0 and 1.
+    pub fn with_rate(capacity: usize, fp_rate: f64) -> Self {
+        assert!(capacity > 0, "capacity must be positive");
+        assert!(
+            fp_rate > 0.0 && fp_rate < 1.0,
+            "false positive rate must lie strictly between 0 and 1"
+        );
+        let ln2 = std::f64::consts::LN_2;
+        let size = (-(capacity as f64) * fp_rate.ln() / (ln2 * ln2)).ceil() as usize;
+        let hash_count = ((size as f64 / capacity as f64 * ln2).round() as u64).max(1);
+        let hash_functions = (0..hash_count)
+            .map(|i| -> HashFunction {
+                Box::new(move |data| {
+                    let (h1, h2) = Self::base_hashes(data);
+                    h1.wrapping_add(i.wrapping_mul(h2))
+                })
+            })
+            .collect();
+        BloomFilter {
+            double_hashing: true,
+            ..Self::new(size, hash_functions)
+        }
+    }
+
+    /// Computes the two base hashes for double hashing. The second is odd, so that the derived
+    /// hashes do not repeat early when the size of the filter is a power of two.
+    fn base_hashes(data: &[u8]) -> (u64, u64) {
+        let mut hasher = DefaultHasher::new();
+        data.hash(&mut hasher);
+        let h1 = hasher.finish();
+        // Continuing from the first hash gives an unrelated second one
+        h1.hash(&mut hasher);
+        (h1, hasher.finish() | 1)
+    }
+
+    /// Estimates the current false positive rate from the fraction of bits that are set, as
+    /// `(set bits / size)^k` for `k` hash functions.
+    pub fn estimated_fp_rate(&self) -> f64 {
+        if self.bit_array.is_empty() {
+            return 1.0;
         }
+        let fill = self.bit_array.count_ones() as f64 / self.bit_array.len() as f64;
+        fill.powi(self.hash_functions.len() as i32)
+    }
+
+    /// Adds every element of `other` to this filter, so that it contains the elements of both.
+    ///
+    /// Both filters must use the same hash functions, so both must be created by `with_rate` from
+    /// the same capacity and rate. The closures passed to `new` cannot be compared, so filters
+    /// created by `new` cannot be combined.
+    ///
+    /// Panics if either filter was created by `new`, or if the filters differ in size or number of
+    /// hash functions.
+    pub fn union(&mut self, other: &BloomFilter) {
+        self.assert_same_parameters(other);
+        for index in other.bit_array.iter_ones() {
+            self.bit_array.set(index, true);
+        }
+    }
+
+    /// Keeps only the bits set in both filters.
+    ///
+    /// Every element inserted into both filters is still contained afterwards, but an element
+    /// inserted into only one of them may be reported too, more often than by a filter built from
+    /// the common elements alone. The same requirements as for `union` apply.
+    pub fn intersect(&mut self, other: &BloomFilter) {
+        self.assert_same_parameters(other);
+        for index in other.bit_array.iter_zeros() {
+            self.bit_array.set(index, false);
+        }
+    }
+
+    /// Panics unless both filters use the double hashes of `with_rate`, with the same size and
+    /// number of hash functions.
+    fn assert_same_parameters(&self, other: &BloomFilter) {
+        assert!(
+            self.double_hashing && other.double_hashing,
+            "only filters created by `with_rate` can be combined"
+        );
+        assert!(
+            self.bit_array.len() == other.bit_array.len()
+                && self.hash_functions.len() == other.hash_functions.len(),
+            "filters must have the same parameters"
+        );
     }
 
     /// Inserts an element into the Bloom Filter
@@ -101,7 +202,7 @@ impl BloomFilter {
     }
 
     /// Hashes an element using the given hash function
-    fn hash<T>(item: &T, hash_function: &Box<dyn Fn(&[u8]) -> u64>) -> u64
+    fn hash<T>(item: &T, hash_function: &HashFunction) -> u64
     where
         T: AsRef<[u8]> + Hash,
     {
@@ -331,4 +432,84 @@ mod tests {
         assert!(!bloom_filter.contains(&"grape"));
         assert!(!bloom_filter.contains(&"kiwi"));
     }
+
+    #[test]
+    fn test_with_rate_parameters() {
+        // 1000 elements at 1% need about 9586 bits and 7 hash functions
+        let bloom_filter = BloomFilter::with_rate(1000, 0.01);
+        assert_eq!(bloom_filter.bit_array.len(), 9586);
+        assert_eq!(bloom_filter.hash_functions.len(), 7);
+        assert_eq!(bloom_filter.estimated_fp_rate(), 0.0);
+    }
+
+    #[test]
+    fn test_with_rate_false_positives() {
+        let target = 0.01;
+        let mut bloom_filter = BloomFilter::with_rate(1000, target);
+        let inserted: Vec<String> = (0..1000).map(|i| format!("member-{}", i)).collect();
+        for element in &inserted {
+            bloom_filter.insert(element);
+        }
+        // No false negatives
+        assert!(inserted
+            .iter()
+            .all(|element| bloom_filter.contains(element)));
+
+        let trials = 20_000;
+        let false_positives = (0..trials)
+            .filter(|i| bloom_filter.contains(&format!("other-{}", i)))
+            .count();
+        let measured = false_positives as f64 / trials as f64;
+        assert!(
+            measured > target / 3.0 && measured < target * 2.0,
+            "measured false positive rate {} is far from {}",
+            measured,
+            target
+        );
+        let estimated = bloom_filter.estimated_fp_rate();
+        assert!(
+            estimated > target / 2.0 && estimated < target * 2.0,
+            "estimated false positive rate {} is far from {}",
+            estimated,
+            target
+        );
+    }
+
+    #[test]
+    fn test_union_and_intersect() {
+        let mut fruit = BloomFilter::with_rate(100, 0.01);
+        let mut berries = BloomFilter::with_rate(100, 0.01);
+        for element in &["apple", "banana", "cherry"] {
+            fruit.insert(element);
+        }
+        for element in &["cherry", "strawberry", "blueberry"] {
+            berries.insert(element);
+        }
+
+        let mut common = BloomFilter::with_rate(100, 0.01);
+        common.union(&fruit);
+        common.intersect(&berries);
+        assert!(common.contains(&"cherry"));
+
+        fruit.union(&berries);
+        for element in &["apple", "banana", "cherry", "strawberry", "blueberry"] {
+            assert!(fruit.contains(element), "union lost {}", element);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "filters must have the same parameters")]
+    fn test_union_different_parameters() {
+        let mut small = BloomFilter::with_rate(100, 0.01);
+        small.union(&BloomFilter::with_rate(1000, 0.01));
+    }
+
+    #[test]
+    #[should_panic(expected = "only filters created by `with_rate` can be combined")]
+    fn test_union_custom_hash_functions() {
+        // Same size and number of hash functions, but different functions
+        let constant = |c: u64| -> HashFunction { Box::new(move |_| c) };
+        let mut filter = BloomFilter::new(7, vec![constant(0), constant(1)]);
+        filter.intersect(&BloomFilter::new(7, vec![constant(2), constant(3)]));
+    }
 }
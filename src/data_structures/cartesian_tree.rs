@@ -0,0 +1,135 @@
+/// Builds the Cartesian tree of `values` and returns the parent of every index.
+///
+/// The Cartesian tree is a binary tree over the indices that is a min-heap by value, and whose
+/// in-order traversal visits the indices in their original order. Its root is the index of the
+/// minimum, with the indices to the left and right of it forming the left and right subtrees.
+/// The minimum of any range `values[i..=j]` lies at the lowest common ancestor of `i` and `j`,
+/// which reduces range minimum queries to LCA queries. Of equal values, the one further left
+/// becomes the ancestor.
+///
+/// The tree is built in O(n) with a stack holding the right spine of the tree built so far: each
+/// new value pops the larger values off the spine, adopts the last one popped as its left child
+/// and becomes the right child of the value left on top.
+///
+/// Returns `parents`, where `parents[i]` is the parent of index `i`, or `None` for the root.
+///
+/// # Examples
+///
+/// ```rust
+/// use rust_algorithms::data_structures::cartesian_tree;
+///
+/// //      1
+/// //     / \
+/// //    3   2
+/// //     \
+/// //      7
+/// let parents = cartesian_tree(&[3, 7, 1, 2]);
+///
+/// assert_eq!(parents, vec![Some(2), Some(0), None, Some(2)]);
+/// ```
+pub fn cartesian_tree(values: &[i64]) -> Vec<Option<usize>> {
+    let mut parents = vec![None; values.len()];
+    let mut spine: Vec<usize> = Vec::new();
+    for (i, &value) in values.iter().enumerate() {
+        let mut last = None;
+        while let Some(&top) = spine.last() {
+            if values[top] <= value {
+                break;
+            }
+            last = spine.pop();
+        }
+        if let Some(last) = last {
+            parents[last] = Some(i);
+        }
+        if let Some(&top) = spine.last() {
+            parents[i] = Some(top);
+        }
+        spine.push(i);
+    }
+    parents
+}
+
+#[cfg(test)]
+mod tests {
+    use super::cartesian_tree;
+    use crate::math::PCG32;
+
+    /// Returns the indices in in-order, after checking that every parent has at most one child
+    /// on each side and that the tree has a single root.
+    fn in_order(parents: &[Option<usize>]) -> Vec<usize> {
+        let n = parents.len();
+        let mut children = vec![(None, None); n];
+        let mut roots = Vec::new();
+        for (i, parent) in parents.iter().enumerate() {
+            match *parent {
+                Some(p) if i < p => {
+                    assert!(children[p].0.replace(i).is_none(), "two left children");
+                }
+                Some(p) => {
+                    assert!(children[p].1.replace(i).is_none(), "two right children");
+                }
+                None => roots.push(i),
+            }
+        }
+        let mut order = Vec::new();
+        if n == 0 {
+            return order;
+        }
+        assert_eq!(roots.len(), 1, "the tree must have one root");
+        let mut stack = Vec::new();
+        let mut current = Some(roots[0]);
+        while current.is_some() || !stack.is_empty() {
+            while let Some(node) = current {
+                stack.push(node);
+                current = children[node].0;
+            }
+            let node = stack.pop().unwrap();
+            order.push(node);
+            current = children[node].1;
+        }
+        order
+    }
+
+    #[test]
+    fn small_sequence() {
+        //        0
+        //       / \
+        //      5   2
+        //     / \   \
+        //    9   8   3
+        //             \
+        //              7
+        let values = [9, 5, 8, 0, 2, 3, 7];
+        let parents = cartesian_tree(&values);
+        assert_eq!(
+            parents,
+            vec![Some(1), Some(3), Some(1), None, Some(3), Some(4), Some(5)]
+        );
+        assert_eq!(in_order(&parents), (0..values.len()).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn heap_ordered_and_in_order() {
+        let mut rng = PCG32::new_default(67);
+        for len in 0..60 {
+            let values: Vec<i64> = (0..len).map(|_| rng.get_u32() as i64 % 20 - 10).collect();
+            let parents = cartesian_tree(&values);
+            assert_eq!(in_order(&parents), (0..len).collect::<Vec<_>>());
+            for (i, parent) in parents.iter().enumerate() {
+                if let Some(p) = *parent {
+                    assert!(values[p] <= values[i]);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn sorted_and_equal_values() {
+        // An ascending sequence is a path of right children, a descending one of left children
+        assert_eq!(cartesian_tree(&[1, 2, 3]), vec![None, Some(0), Some(1)]);
+        assert_eq!(cartesian_tree(&[3, 2, 1]), vec![Some(1), Some(2), None]);
+        // Equal values hang to the right of the leftmost one
+        assert_eq!(cartesian_tree(&[4, 4, 4]), vec![None, Some(0), Some(1)]);
+        assert!(cartesian_tree(&[]).is_empty());
+    }
+}
@@ -0,0 +1,305 @@
+/// An exact cover solver using Knuth's Algorithm X with dancing links.
+///
+/// Given a 0/1 matrix, an exact cover is a set of rows that together have exactly one 1 in every
+/// column. Constraint puzzles such as Sudoku and pentomino tilings reduce to exact cover by making
+/// every constraint a column and every possible choice a row holding a 1 in the constraints it
+/// satisfies.
+///
+/// The 1s of the matrix are kept as nodes in circular doubly linked lists, one per row and one per
+/// column, under a list of column headers. Algorithm X picks the column with the fewest 1s left,
+/// tries each row covering it, and removes every column that row covers along with all rows
+/// clashing with it. Since a removed node keeps its own links, it can be put back in O(1) when
+/// backtracking, which is the "dance" of the links. The nodes are stored in vectors and linked by
+/// index. Solving leaves the matrix as it was built, so it can be solved again.
+///
+/// # Examples
+///
+/// ```rust
+/// use rust_algorithms::data_structures::DancingLinks;
+///
+/// let mut dlx = DancingLinks::new(&[
+///     vec![true, false, true],
+///     vec![false, true, false],
+///     vec![true, true, false],
+///     vec![false, false, true],
+/// ]);
+///
+/// assert_eq!(dlx.solve(), Some(vec![0, 1]));
+/// assert_eq!(dlx.solve_all(), vec![vec![0, 1], vec![2, 3]]);
+/// ```
+pub struct DancingLinks {
+    left: Vec<usize>,
+    right: Vec<usize>,
+    up: Vec<usize>,
+    down: Vec<usize>,
+    /// The column header of every node, which is the node itself for headers.
+    column: Vec<usize>,
+    /// The matrix row of every node, unused for the root and the headers.
+    row: Vec<usize>,
+    /// The number of 1s left in every column, indexed by its header.
+    size: Vec<usize>,
+}
+
+/// The root node, linked into the circular list of the column headers.
+const ROOT: usize = 0;
+
+impl DancingLinks {
+    /// Creates a solver for the exact covers of `matrix`, where `matrix[r][c]` is `true` if row `r`
+    /// has a 1 in column `c`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the rows do not all have the same length.
+    pub fn new(matrix: &[Vec<bool>]) -> Self {
+        let columns = matrix.first().map_or(0, Vec::len);
+        // The root and the headers come first, followed by the 1s of the matrix
+        let headers = columns + 1;
+        let mut dlx = DancingLinks {
+            left: (0..headers).map(|i| (i + headers - 1) % headers).collect(),
+            right: (0..headers).map(|i| (i + 1) % headers).collect(),
+            up: (0..headers).collect(),
+            down: (0..headers).collect(),
+            column: (0..headers).collect(),
+            row: vec![usize::MAX; headers],
+            size: vec![0; headers],
+        };
+        for (r, cells) in matrix.iter().enumerate() {
+            assert_eq!(cells.len(), columns, "all rows must have the same length");
+            let first = dlx.column.len();
+            for (c, _) in cells.iter().enumerate().filter(|(_, &cell)| cell) {
+                let header = c + 1;
+                let node = dlx.column.len();
+                let last = dlx.up[header];
+                dlx.up.push(last);
+                dlx.down.push(header);
+                dlx.down[last] = node;
+                dlx.up[header] = node;
+                dlx.left.push(node - 1);
+                dlx.right.push(node + 1);
+                dlx.column.push(header);
+                dlx.row.push(r);
+                dlx.size[header] += 1;
+            }
+            let last = dlx.column.len() - 1;
+            if last >= first {
+                dlx.left[first] = last;
+                dlx.right[last] = first;
+            }
+        }
+        dlx
+    }
+
+    /// Finds an exact cover.
+    ///
+    /// Returns the indices of its rows in ascending order, or `None` if there is no exact cover.
+    /// A matrix without columns is covered by no rows.
+    pub fn solve(&mut self) -> Option<Vec<usize>> {
+        let mut solution = None;
+        self.search(&mut Vec::new(), &mut |rows| {
+            solution = Some(rows.to_vec());
+            true
+        });
+        solution.map(|mut rows| {
+            rows.sort_unstable();
+            rows
+        })
+    }
+
+    /// Finds every exact cover.
+    ///
+    /// Returns the rows of every exact cover in ascending order, with the covers sorted.
+    pub fn solve_all(&mut self) -> Vec<Vec<usize>> {
+        let mut solutions = Vec::new();
+        self.search(&mut Vec::new(), &mut |rows| {
+            let mut rows = rows.to_vec();
+            rows.sort_unstable();
+            solutions.push(rows);
+            false
+        });
+        solutions.sort_unstable();
+        solutions
+    }
+
+    /// Extends the partial cover `rows` to exact covers, passing each one to `found`.
+    ///
+    /// Returns `true` as soon as `found` does, after restoring the links.
+    fn search(&mut self, rows: &mut Vec<usize>, found: &mut dyn FnMut(&[usize]) -> bool) -> bool {
+        if self.right[ROOT] == ROOT {
+            return found(rows);
+        }
+        let mut column = self.right[ROOT];
+        let mut c = self.right[column];
+        while c != ROOT {
+            if self.size[c] < self.size[column] {
+                column = c;
+            }
+            c = self.right[c];
+        }
+        if self.size[column] == 0 {
+            return false;
+        }
+
+        self.cover(column);
+        let mut stop = false;
+        let mut r = self.down[column];
+        while r != column && !stop {
+            rows.push(self.row[r]);
+            let mut j = self.right[r];
+            while j != r {
+                self.cover(self.column[j]);
+                j = self.right[j];
+            }
+            stop = self.search(rows, found);
+            let mut j = self.left[r];
+            while j != r {
+                self.uncover(self.column[j]);
+                j = self.left[j];
+            }
+            rows.pop();
+            r = self.down[r];
+        }
+        self.uncover(column);
+        stop
+    }
+
+    /// Removes the column with header `c` and every row with a 1 in it.
+    fn cover(&mut self, c: usize) {
+        self.right[self.left[c]] = self.right[c];
+        self.left[self.right[c]] = self.left[c];
+        let mut i = self.down[c];
+        while i != c {
+            let mut j = self.right[i];
+            while j != i {
+                self.up[self.down[j]] = self.up[j];
+                self.down[self.up[j]] = self.down[j];
+                self.size[self.column[j]] -= 1;
+                j = self.right[j];
+            }
+            i = self.down[i];
+        }
+    }
+
+    /// Puts back what `cover(c)` removed, in the reverse order.
+    fn uncover(&mut self, c: usize) {
+        let mut i = self.up[c];
+        while i != c {
+            let mut j = self.left[i];
+            while j != i {
+                self.size[self.column[j]] += 1;
+                self.up[self.down[j]] = j;
+                self.down[self.up[j]] = j;
+                j = self.left[j];
+            }
+            i = self.up[i];
+        }
+        self.right[self.left[c]] = c;
+        self.left[self.right[c]] = c;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::DancingLinks;
+
+    fn matrix(rows: &[&str]) -> Vec<Vec<bool>> {
+        rows.iter()
+            .map(|row| row.chars().map(|c| c == '1').collect())
+            .collect()
+    }
+
+    #[test]
+    fn knuth_example() {
+        let mut dlx = DancingLinks::new(&matrix(&[
+            "0010110", "1001001", "0110010", "1001000", "0100001", "0001101",
+        ]));
+        assert_eq!(dlx.solve(), Some(vec![0, 3, 4]));
+        assert_eq!(dlx.solve_all(), vec![vec![0, 3, 4]]);
+        // Solving restores the links
+        assert_eq!(dlx.solve(), Some(vec![0, 3, 4]));
+    }
+
+    #[test]
+    fn several_or_no_covers() {
+        let mut dlx = DancingLinks::new(&matrix(&["11", "10", "01", "11"]));
+        assert_eq!(dlx.solve_all(), vec![vec![0], vec![1, 2], vec![3]]);
+
+        // Nothing covers the last column
+        let mut dlx = DancingLinks::new(&matrix(&["100", "010", "110"]));
+        assert_eq!(dlx.solve(), None);
+        assert!(dlx.solve_all().is_empty());
+
+        // Every cover must avoid overlaps
+        let mut dlx = DancingLinks::new(&matrix(&["110", "011"]));
+        assert_eq!(dlx.solve(), None);
+
+        assert_eq!(DancingLinks::new(&[]).solve(), Some(vec![]));
+        assert_eq!(DancingLinks::new(&matrix(&["0"])).solve(), None);
+    }
+
+    #[test]
+    fn sudoku() {
+        let puzzle = [
+            "53..7....",
+            "6..195...",
+            ".98....6.",
+            "8...6...3",
+            "4..8.3..1",
+            "7...2...6",
+            ".6....28.",
+            "...419..5",
+            "....8..79",
+        ];
+        let solution = [
+            "534678912",
+            "672195348",
+            "198342567",
+            "859761423",
+            "426853791",
+            "713924856",
+            "961537284",
+            "287419635",
+            "345286179",
+        ];
+
+        // A row for every digit that may go in every cell, covering the cell and the digit in its
+        // row, column and box
+        let mut choices = Vec::new();
+        let mut rows = Vec::new();
+        for (r, line) in puzzle.iter().enumerate() {
+            for (c, given) in line.chars().enumerate() {
+                for d in 0..9 {
+                    if given != '.' && given.to_digit(10) != Some(d as u32 + 1) {
+                        continue;
+                    }
+                    let b = r / 3 * 3 + c / 3;
+                    let mut row = vec![false; 324];
+                    row[r * 9 + c] = true;
+                    row[81 + r * 9 + d] = true;
+                    row[162 + c * 9 + d] = true;
+                    row[243 + b * 9 + d] = true;
+                    rows.push(row);
+                    choices.push((r, c, d));
+                }
+            }
+        }
+
+        let mut dlx = DancingLinks::new(&rows);
+        let mut grid = vec![vec!['.'; 9]; 9];
+        for i in dlx.solve().unwrap() {
+            let (r, c, d) = choices[i];
+            grid[r][c] = std::char::from_digit(d as u32 + 1, 10).unwrap();
+        }
+        let grid: Vec<String> = grid
+            .into_iter()
+            .map(|row| row.into_iter().collect())
+            .collect();
+        assert_eq!(grid, solution);
+        assert_eq!(dlx.solve_all().len(), 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "all rows must have the same length")]
+    fn ragged_matrix() {
+        DancingLinks::new(&matrix(&["10", "1"]));
+    }
+}
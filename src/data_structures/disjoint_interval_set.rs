@@ -0,0 +1,159 @@
+use std::collections::BTreeMap;
+
+/// A set of points stored as disjoint half-open intervals `[lo, hi)`.
+///
+/// Intervals are kept in a `BTreeMap` from start to end. Inserting an interval merges it with
+/// every interval it overlaps or touches, so `[1, 3)` and `[3, 5)` become `[1, 5)`, and removing
+/// an interval splits any stored interval that it cuts through.
+///
+/// # Examples
+///
+/// ```rust
+/// use rust_algorithms::data_structures::DisjointIntervalSet;
+///
+/// let mut set = DisjointIntervalSet::new();
+/// set.insert(1, 3);
+/// set.insert(3, 5);
+/// set.remove(2, 4);
+///
+/// assert_eq!(set.iter().collect::<Vec<_>>(), vec![(1, 2), (4, 5)]);
+/// assert!(set.contains(1));
+/// assert!(!set.contains(2));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct DisjointIntervalSet<T: Ord + Copy> {
+    intervals: BTreeMap<T, T>,
+}
+
+impl<T: Ord + Copy> DisjointIntervalSet<T> {
+    /// Creates an empty set.
+    pub fn new() -> Self {
+        DisjointIntervalSet {
+            intervals: BTreeMap::new(),
+        }
+    }
+
+    /// Adds all points of `[lo, hi)`, merging with overlapping and adjacent intervals.
+    /// Empty intervals (`lo >= hi`) are ignored.
+    pub fn insert(&mut self, lo: T, hi: T) {
+        if lo >= hi {
+            return;
+        }
+        let (mut new_lo, mut new_hi) = (lo, hi);
+        // Intervals starting at or before `hi` and ending at or after `lo` touch the new one
+        let touching: Vec<(T, T)> = self
+            .intervals
+            .range(..=hi)
+            .rev()
+            .take_while(|&(_, &end)| end >= lo)
+            .map(|(&start, &end)| (start, end))
+            .collect();
+        for (start, end) in touching {
+            self.intervals.remove(&start);
+            new_lo = new_lo.min(start);
+            new_hi = new_hi.max(end);
+        }
+        self.intervals.insert(new_lo, new_hi);
+    }
+
+    /// Removes all points of `[lo, hi)`, splitting intervals that extend past either end.
+    pub fn remove(&mut self, lo: T, hi: T) {
+        if lo >= hi {
+            return;
+        }
+        let overlapping: Vec<(T, T)> = self
+            .intervals
+            .range(..hi)
+            .rev()
+            .take_while(|&(_, &end)| end > lo)
+            .map(|(&start, &end)| (start, end))
+            .collect();
+        for (start, end) in overlapping {
+            self.intervals.remove(&start);
+            if start < lo {
+                self.intervals.insert(start, lo);
+            }
+            if hi < end {
+                self.intervals.insert(hi, end);
+            }
+        }
+    }
+
+    /// Checks if `point` lies in one of the intervals.
+    pub fn contains(&self, point: T) -> bool {
+        self.intervals
+            .range(..=point)
+            .next_back()
+            .is_some_and(|(_, &end)| point < end)
+    }
+
+    /// Returns an iterator over the disjoint intervals as `(lo, hi)` pairs in ascending order.
+    pub fn iter(&self) -> impl Iterator<Item = (T, T)> + '_ {
+        self.intervals.iter().map(|(&start, &end)| (start, end))
+    }
+
+    /// Returns the number of disjoint intervals.
+    pub fn len(&self) -> usize {
+        self.intervals.len()
+    }
+
+    /// Checks if the set contains no points.
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn intervals(set: &DisjointIntervalSet<i32>) -> Vec<(i32, i32)> {
+        set.iter().collect()
+    }
+
+    #[test]
+    fn merges_overlapping() {
+        let mut set = DisjointIntervalSet::new();
+        set.insert(10, 20);
+        set.insert(30, 40);
+        set.insert(0, 5);
+        assert_eq!(intervals(&set), vec![(0, 5), (10, 20), (30, 40)]);
+
+        set.insert(15, 35);
+        assert_eq!(intervals(&set), vec![(0, 5), (10, 40)]);
+        // Adjacent intervals merge as well
+        set.insert(5, 10);
+        assert_eq!(intervals(&set), vec![(0, 40)]);
+        // Covered intervals change nothing
+        set.insert(2, 8);
+        assert_eq!(intervals(&set), vec![(0, 40)]);
+        set.insert(3, 3);
+        assert_eq!(set.len(), 1);
+    }
+
+    #[test]
+    fn remove_splits() {
+        let mut set = DisjointIntervalSet::new();
+        set.insert(0, 100);
+        set.remove(40, 60);
+        assert_eq!(intervals(&set), vec![(0, 40), (60, 100)]);
+
+        set.remove(30, 70);
+        assert_eq!(intervals(&set), vec![(0, 30), (70, 100)]);
+        set.remove(-10, 5);
+        set.remove(95, 200);
+        assert_eq!(intervals(&set), vec![(5, 30), (70, 95)]);
+        set.remove(0, 1000);
+        assert!(set.is_empty());
+    }
+
+    #[test]
+    fn membership() {
+        let mut set = DisjointIntervalSet::new();
+        set.insert(1, 4);
+        set.insert(10, 12);
+        set.remove(2, 3);
+        let members: Vec<i32> = (0..15).filter(|&x| set.contains(x)).collect();
+        assert_eq!(members, vec![1, 3, 10, 11]);
+    }
+}
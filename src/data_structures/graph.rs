@@ -62,6 +62,38 @@ where
             e.push((edge.0, edge.2));
         });
     }
+    /// A depth-first search finds a cycle exactly when it reaches an already visited node through
+    /// an edge other than the one it arrived by. Only one edge back to the parent is skipped, so
+    /// parallel edges and self-loops count as cycles. Runs in O(V + E).
+    fn has_cycle(&self) -> bool {
+        let mut visited: HashSet<&'a T> = HashSet::new();
+        for &start in self.adjacency_table.keys() {
+            if !visited.insert(start) {
+                continue;
+            }
+            // Node, parent, index of the next neighbour, and whether the parent edge was skipped
+            let mut stack = vec![(start, None, 0, false)];
+            while let Some(&(node, parent, index, skipped)) = stack.last() {
+                let next = match self.adjacency_table[node].get(index) {
+                    Some(&(next, _)) => next,
+                    None => {
+                        stack.pop();
+                        continue;
+                    }
+                };
+                let top = stack.last_mut().unwrap();
+                top.2 += 1;
+                if !skipped && parent == Some(next) {
+                    top.3 = true;
+                } else if !visited.insert(next) {
+                    return true;
+                } else {
+                    stack.push((next, Some(node), 0, false));
+                }
+            }
+        }
+        false
+    }
 }
 
 pub trait Graph<'a, T>
@@ -71,6 +103,43 @@ where
     fn new() -> Self;
     fn adjacency_table_mutable(&mut self) -> &mut HashMap<&'a T, Vec<(&'a T, i32)>>;
     fn adjacency_table(&self) -> &HashMap<&'a T, Vec<(&'a T, i32)>>;
+    /// Returns `true` if the graph contains a cycle, following edge directions if it has any.
+    ///
+    /// By default every edge is followed in its direction only: a depth-first search finds a cycle
+    /// exactly when it reaches a node that is still on the search path, i.e. when it follows a
+    /// back edge. Graphs that store each undirected edge in both directions must override this.
+    /// Runs in O(V + E).
+    fn has_cycle(&self) -> bool {
+        // `true` while a node is on the search path, `false` once it is finished
+        let mut on_path: HashMap<&'a T, bool> = HashMap::new();
+        for &start in self.adjacency_table().keys() {
+            if on_path.contains_key(start) {
+                continue;
+            }
+            on_path.insert(start, true);
+            let mut stack = vec![(start, 0)];
+            while let Some(&(node, index)) = stack.last() {
+                match self.adjacency_table()[node].get(index) {
+                    Some(&(next, _)) => {
+                        stack.last_mut().unwrap().1 += 1;
+                        match on_path.get(next) {
+                            Some(true) => return true,
+                            Some(false) => {}
+                            None => {
+                                on_path.insert(next, true);
+                                stack.push((next, 0));
+                            }
+                        }
+                    }
+                    None => {
+                        on_path.insert(node, false);
+                        stack.pop();
+                    }
+                }
+            }
+        }
+        false
+    }
 
     fn add_node(&mut self, node: &'a T) -> bool {
         if let Vacant(entry) = self.adjacency_table_mutable().entry(node) {
@@ -163,6 +232,32 @@ mod test_undirected_graph {
 
         assert_eq!(graph.neighbours(&a).unwrap(), &vec![(&b, 5), (&c, 7)]);
     }
+
+    #[test]
+    fn test_has_cycle() {
+        let nodes: Vec<u32> = (0..6).collect();
+        let mut graph: UndirectedGraph<u32> = UndirectedGraph::new();
+        // A tree
+        for &(u, v) in [(0, 1), (0, 2), (1, 3), (1, 4), (2, 5)].iter() {
+            graph.add_edge((&nodes[u], &nodes[v], 1));
+        }
+        assert!(!graph.has_cycle());
+        graph.add_edge((&nodes[4], &nodes[5], 1));
+        assert!(graph.has_cycle());
+
+        let mut forest: UndirectedGraph<u32> = UndirectedGraph::new();
+        forest.add_edge((&nodes[0], &nodes[1], 1));
+        forest.add_edge((&nodes[2], &nodes[3], 1));
+        forest.add_node(&nodes[4]);
+        assert!(!forest.has_cycle());
+        // A second edge between the same nodes closes a cycle
+        forest.add_edge((&nodes[3], &nodes[2], 1));
+        assert!(forest.has_cycle());
+
+        let mut self_loop: UndirectedGraph<u32> = UndirectedGraph::new();
+        self_loop.add_edge((&nodes[0], &nodes[0], 1));
+        assert!(self_loop.has_cycle());
+    }
 }
 
 #[cfg(test)]
@@ -266,4 +361,23 @@ mod test_directed_graph {
         assert!(graph.contains(&c));
         assert!(!graph.contains(&d));
     }
+
+    #[test]
+    fn test_has_cycle() {
+        let nodes: Vec<u32> = (0..6).collect();
+        let mut graph: DirectedGraph<u32> = DirectedGraph::new();
+        // A DAG in which node 4 is reachable along two paths
+        for &(u, v) in [(0, 1), (0, 2), (1, 3), (2, 3), (3, 4), (1, 4), (5, 4)].iter() {
+            graph.add_edge((&nodes[u], &nodes[v], 1));
+        }
+        assert!(!graph.has_cycle());
+        // A back edge from 4 to 1
+        graph.add_edge((&nodes[4], &nodes[1], 1));
+        assert!(graph.has_cycle());
+
+        let mut self_loop: DirectedGraph<u32> = DirectedGraph::new();
+        self_loop.add_edge((&nodes[0], &nodes[0], 1));
+        assert!(self_loop.has_cycle());
+        assert!(!DirectedGraph::<u32>::new().has_cycle());
+    }
 }
@@ -0,0 +1,249 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// A min-priority queue of keys that can be looked up and reprioritized by key.
+///
+/// The entries are kept in a binary heap, alongside a map from every key to its position in the
+/// heap. The map is updated whenever entries move, which allows checking membership in O(1) and
+/// changing the priority of a key in O(log n), the decrease-key operation needed by Dijkstra's or
+/// Prim's algorithm. Each key is present at most once.
+///
+/// # Examples
+///
+/// ```rust
+/// use rust_algorithms::data_structures::IndexedPriorityQueue;
+///
+/// let mut queue = IndexedPriorityQueue::new();
+/// queue.push("b", 5);
+/// queue.push("a", 3);
+/// queue.push("c", 8);
+///
+/// queue.change_priority(&"c", 1);
+///
+/// assert_eq!(queue.pop(), Some(("c", 1)));
+/// assert_eq!(queue.pop(), Some(("a", 3)));
+/// assert!(queue.contains(&"b"));
+/// assert!(!queue.contains(&"a"));
+/// ```
+pub struct IndexedPriorityQueue<K: Hash + Eq + Clone, P: Ord> {
+    heap: Vec<(K, P)>,
+    positions: HashMap<K, usize>,
+}
+
+impl<K: Hash + Eq + Clone, P: Ord> IndexedPriorityQueue<K, P> {
+    /// Creates an empty `IndexedPriorityQueue`.
+    pub fn new() -> Self {
+        IndexedPriorityQueue {
+            heap: Vec::new(),
+            positions: HashMap::new(),
+        }
+    }
+
+    /// Returns the number of keys in the queue.
+    pub fn len(&self) -> usize {
+        self.heap.len()
+    }
+
+    /// Returns `true` if the queue contains no keys.
+    pub fn is_empty(&self) -> bool {
+        self.heap.is_empty()
+    }
+
+    /// Returns `true` if the queue contains `key`.
+    pub fn contains(&self, key: &K) -> bool {
+        self.positions.contains_key(key)
+    }
+
+    /// Returns the priority of `key`, if it is in the queue.
+    pub fn priority(&self, key: &K) -> Option<&P> {
+        self.positions.get(key).map(|&i| &self.heap[i].1)
+    }
+
+    /// Returns the key with the smallest priority, without removing it.
+    pub fn peek(&self) -> Option<(&K, &P)> {
+        self.heap.first().map(|(key, priority)| (key, priority))
+    }
+
+    /// Adds `key` with the given priority.
+    ///
+    /// If `key` is already in the queue, its priority is changed instead and the old priority is
+    /// returned.
+    pub fn push(&mut self, key: K, priority: P) -> Option<P> {
+        if self.contains(&key) {
+            return self.change_priority(&key, priority);
+        }
+        self.positions.insert(key.clone(), self.heap.len());
+        self.heap.push((key, priority));
+        self.sift_up(self.heap.len() - 1);
+        None
+    }
+
+    /// Removes and returns the key with the smallest priority.
+    pub fn pop(&mut self) -> Option<(K, P)> {
+        if self.heap.is_empty() {
+            return None;
+        }
+        let last = self.heap.len() - 1;
+        self.swap(0, last);
+        let (key, priority) = self.heap.pop()?;
+        self.positions.remove(&key);
+        self.sift_down(0);
+        Some((key, priority))
+    }
+
+    /// Changes the priority of `key`, moving it up or down the heap as needed.
+    ///
+    /// Returns the old priority, or `None` if `key` is not in the queue, in which case the queue
+    /// is left unchanged.
+    pub fn change_priority(&mut self, key: &K, priority: P) -> Option<P> {
+        let i = *self.positions.get(key)?;
+        let old = std::mem::replace(&mut self.heap[i].1, priority);
+        if self.heap[i].1 < old {
+            self.sift_up(i);
+        } else {
+            self.sift_down(i);
+        }
+        Some(old)
+    }
+
+    /// Removes `key` from the queue, returning its priority.
+    pub fn remove(&mut self, key: &K) -> Option<P> {
+        let i = self.positions.remove(key)?;
+        let last = self.heap.len() - 1;
+        self.swap(i, last);
+        let (_, priority) = self.heap.pop()?;
+        if i < self.heap.len() {
+            // The entry moved into the gap may belong on either side of it
+            self.sift_up(i);
+            self.sift_down(i);
+        }
+        Some(priority)
+    }
+
+    /// Swaps two entries of the heap and updates their positions.
+    fn swap(&mut self, i: usize, j: usize) {
+        self.heap.swap(i, j);
+        for &k in [i, j].iter() {
+            if let Some(position) = self.positions.get_mut(&self.heap[k].0) {
+                *position = k;
+            }
+        }
+    }
+
+    fn sift_up(&mut self, mut i: usize) {
+        while i > 0 {
+            let parent = (i - 1) / 2;
+            if self.heap[i].1 >= self.heap[parent].1 {
+                break;
+            }
+            self.swap(i, parent);
+            i = parent;
+        }
+    }
+
+    fn sift_down(&mut self, mut i: usize) {
+        loop {
+            let mut smallest = i;
+            for child in [2 * i + 1, 2 * i + 2].iter() {
+                if *child < self.heap.len() && self.heap[*child].1 < self.heap[smallest].1 {
+                    smallest = *child;
+                }
+            }
+            if smallest == i {
+                break;
+            }
+            self.swap(i, smallest);
+            i = smallest;
+        }
+    }
+}
+
+impl<K: Hash + Eq + Clone, P: Ord> Default for IndexedPriorityQueue<K, P> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::IndexedPriorityQueue;
+
+    fn drain<P: Ord>(queue: &mut IndexedPriorityQueue<usize, P>) -> Vec<(usize, P)> {
+        std::iter::from_fn(|| queue.pop()).collect()
+    }
+
+    #[test]
+    fn pops_by_priority() {
+        let mut queue = IndexedPriorityQueue::new();
+        for key in 0..50 {
+            queue.push(key, (key * 37) % 50);
+        }
+        assert_eq!(queue.len(), 50);
+        assert_eq!(queue.peek(), Some((&0, &0)));
+        let priorities: Vec<usize> = drain(&mut queue).into_iter().map(|(_, p)| p).collect();
+        assert_eq!(priorities, (0..50).collect::<Vec<_>>());
+        assert!(queue.is_empty());
+        assert_eq!(queue.pop(), None);
+        assert_eq!(queue.peek(), None);
+    }
+
+    #[test]
+    fn change_priority() {
+        let mut queue = IndexedPriorityQueue::new();
+        for key in 0..10 {
+            queue.push(key, key * 10);
+        }
+        assert_eq!(queue.change_priority(&9, 5), Some(90));
+        assert_eq!(queue.change_priority(&0, 55), Some(0));
+        assert_eq!(queue.change_priority(&42, 1), None);
+        assert_eq!(queue.priority(&9), Some(&5));
+        // Pushing a present key reprioritizes it
+        assert_eq!(queue.push(3, 100), Some(30));
+        assert_eq!(queue.len(), 10);
+
+        let keys: Vec<usize> = drain(&mut queue).into_iter().map(|(k, _)| k).collect();
+        assert_eq!(keys, vec![9, 1, 2, 4, 5, 0, 6, 7, 8, 3]);
+    }
+
+    #[test]
+    fn tracks_membership() {
+        let mut queue = IndexedPriorityQueue::new();
+        assert!(!queue.contains(&1));
+        queue.push(1, 'b');
+        queue.push(2, 'a');
+        assert!(queue.contains(&1) && queue.contains(&2));
+        assert_eq!(queue.pop(), Some((2, 'a')));
+        assert!(!queue.contains(&2) && queue.contains(&1));
+        queue.push(3, 'c');
+        assert_eq!(queue.remove(&1), Some('b'));
+        assert_eq!(queue.remove(&1), None);
+        assert!(!queue.contains(&1));
+        assert_eq!(queue.pop(), Some((3, 'c')));
+        assert!(!queue.contains(&3));
+    }
+
+    #[test]
+    fn dijkstra() {
+        let graph: Vec<Vec<(usize, u64)>> = vec![
+            vec![(1, 7), (2, 9), (5, 14)],
+            vec![(0, 7), (2, 10), (3, 15)],
+            vec![(0, 9), (1, 10), (3, 11), (5, 2)],
+            vec![(1, 15), (2, 11), (4, 6)],
+            vec![(3, 6), (5, 9)],
+            vec![(0, 14), (2, 2), (4, 9)],
+        ];
+        let mut dist = vec![u64::MAX; graph.len()];
+        let mut queue = IndexedPriorityQueue::new();
+        dist[0] = 0;
+        queue.push(0, 0);
+        while let Some((u, d)) = queue.pop() {
+            for &(v, weight) in &graph[u] {
+                if d + weight < dist[v] {
+                    dist[v] = d + weight;
+                    queue.push(v, dist[v]);
+                }
+            }
+        }
+        assert_eq!(dist, vec![0, 7, 9, 20, 20, 11]);
+    }
+}
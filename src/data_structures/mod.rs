@@ -3,31 +3,49 @@ mod avl_tree;
 mod b_tree;
 mod binary_search_tree;
 mod bloom_filter;
+mod cartesian_tree;
+mod dancing_links;
+mod disjoint_interval_set;
 mod fenwick_tree;
 mod graph;
 mod hashtable;
 mod heap;
+mod indexed_priority_queue;
 mod linked_list;
+mod persistent_segment_tree;
+mod persistent_union_find;
+mod quadtree;
 mod queue;
 mod rb_tree;
 mod rope;
 mod segment_tree;
 mod stack;
 mod stack_using_singly_linked_list;
+mod succinct_bitvector;
+mod treap_sequence;
 mod trie;
 mod union_find;
+mod wavelet_tree;
 
 pub use bloom_filter::BloomFilter;
+pub use cartesian_tree::cartesian_tree;
+pub use dancing_links::DancingLinks;
+pub use disjoint_interval_set::DisjointIntervalSet;
 pub use hashtable::HashTable;
 pub use heap::MaxHeap;
 pub use heap::MinHeap;
+pub use indexed_priority_queue::IndexedPriorityQueue;
 pub use linked_list::LinkedList;
+pub use persistent_segment_tree::PersistentSegmentTree;
+pub use persistent_union_find::RollbackUnionFind;
+pub use quadtree::{QuadTree, Rect};
 pub use queue::Queue;
 pub use rope::Rope;
 pub use stack::Stack;
+pub use succinct_bitvector::BitVector;
 
 // REVIEW: Some of these might actually belong in src/graph
-pub use avl_tree::AVLTree;
+pub use avl_tree::{AVLMap, AVLSumTree, AVLTree, AVLTreeCmp, BalanceStats};
 pub use b_tree::BTree;
 pub use binary_search_tree::BinarySearchTree;
 pub use fenwick_tree::FenwickTree;
@@ -35,5 +53,7 @@ pub use graph::{DirectedGraph, Graph, UndirectedGraph};
 pub use rb_tree::RBTree;
 pub use segment_tree::SegmentTree;
 pub use stack_using_singly_linked_list::Stack as SllStack;
+pub use treap_sequence::TreapSequence;
 pub use trie::Trie;
 pub use union_find::UnionFind;
+pub use wavelet_tree::WaveletTree;
@@ -0,0 +1,227 @@
+use std::ops::{Add, Range};
+use std::rc::Rc;
+
+/// The children of an inner node, covering the lower and upper half of its range.
+type Children<T> = (Rc<Node<T>>, Rc<Node<T>>);
+
+/// A node of a `PersistentSegmentTree`, covering a contiguous range of indices.
+struct Node<T> {
+    sum: T,
+    /// `None` for leaves.
+    children: Option<Children<T>>,
+}
+
+/// A persistent segment tree answering range sum queries over every version of an array.
+///
+/// An update never modifies existing nodes. Instead, it copies the O(log n) nodes on the path from
+/// the root to the updated leaf and shares all other nodes with the previous version. Every
+/// version therefore stays queryable, and each update costs only O(log n) time and memory.
+///
+/// Version 0 is the array the tree was built from, and every update creates the next version.
+///
+/// # Examples
+///
+/// ```rust
+/// use rust_algorithms::data_structures::PersistentSegmentTree;
+///
+/// let mut tree = PersistentSegmentTree::new(&[1, 2, 3, 4]);
+/// let v1 = tree.update(0, 2, 10);
+///
+/// assert_eq!(tree.query(0, 0..4), 10);
+/// assert_eq!(tree.query(v1, 0..4), 17);
+/// assert_eq!(tree.query(v1, 2..3), 10);
+/// ```
+pub struct PersistentSegmentTree<T> {
+    len: usize,
+    roots: Vec<Option<Rc<Node<T>>>>,
+}
+
+impl<T: Add<Output = T> + Copy + Default> PersistentSegmentTree<T> {
+    /// Builds version 0 of the tree from a slice in O(n).
+    pub fn new(values: &[T]) -> Self {
+        let root = if values.is_empty() {
+            None
+        } else {
+            Some(build(values))
+        };
+        PersistentSegmentTree {
+            len: values.len(),
+            roots: vec![root],
+        }
+    }
+
+    /// Returns the number of elements in each version.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if the tree has no elements.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the number of versions created so far, including version 0.
+    pub fn versions(&self) -> usize {
+        self.roots.len()
+    }
+
+    /// Creates a new version equal to `version` except that `index` holds `value`.
+    ///
+    /// Returns the number of the new version. Panics if `version` or `index` is out of bounds.
+    pub fn update(&mut self, version: usize, index: usize, value: T) -> usize {
+        assert!(
+            version < self.roots.len(),
+            "version {} does not exist",
+            version
+        );
+        assert!(index < self.len, "index {} out of bounds", index);
+        let root = self.roots[version].as_ref().unwrap();
+        let updated = update(root, 0..self.len, index, value);
+        self.roots.push(Some(updated));
+        self.roots.len() - 1
+    }
+
+    /// Returns the sum of the elements in `range` as of `version`.
+    ///
+    /// An empty range sums to `T::default()`. Panics if `version` does not exist or `range` extends
+    /// past the end of the array.
+    pub fn query(&self, version: usize, range: Range<usize>) -> T {
+        assert!(
+            version < self.roots.len(),
+            "version {} does not exist",
+            version
+        );
+        assert!(range.end <= self.len, "range {:?} out of bounds", range);
+        match &self.roots[version] {
+            Some(root) if range.start < range.end => query(root, 0..self.len, &range),
+            _ => T::default(),
+        }
+    }
+}
+
+/// Builds a tree over a non-empty slice.
+fn build<T: Add<Output = T> + Copy>(values: &[T]) -> Rc<Node<T>> {
+    if values.len() == 1 {
+        return Rc::new(Node {
+            sum: values[0],
+            children: None,
+        });
+    }
+    let mid = values.len() / 2;
+    let (left, right) = (build(&values[..mid]), build(&values[mid..]));
+    Rc::new(Node {
+        sum: left.sum + right.sum,
+        children: Some((left, right)),
+    })
+}
+
+/// Returns a copy of the path to `index` with the leaf set to `value`, sharing all other nodes.
+fn update<T: Add<Output = T> + Copy>(
+    node: &Rc<Node<T>>,
+    span: Range<usize>,
+    index: usize,
+    value: T,
+) -> Rc<Node<T>> {
+    let (left, right) = match &node.children {
+        None => {
+            return Rc::new(Node {
+                sum: value,
+                children: None,
+            })
+        }
+        Some(children) => children,
+    };
+    let mid = span.start + (span.end - span.start) / 2;
+    let (left, right) = if index < mid {
+        (
+            update(left, span.start..mid, index, value),
+            Rc::clone(right),
+        )
+    } else {
+        (Rc::clone(left), update(right, mid..span.end, index, value))
+    };
+    Rc::new(Node {
+        sum: left.sum + right.sum,
+        children: Some((left, right)),
+    })
+}
+
+/// Sums the part of `range` covered by `node`, which spans `span` and overlaps `range`.
+fn query<T: Add<Output = T> + Copy>(node: &Node<T>, span: Range<usize>, range: &Range<usize>) -> T {
+    if range.start <= span.start && span.end <= range.end {
+        return node.sum;
+    }
+    // A partially covered node always spans at least two indices and thus has children
+    let (left, right) = node.children.as_ref().unwrap();
+    let mid = span.start + (span.end - span.start) / 2;
+    if range.end <= mid {
+        query(left, span.start..mid, range)
+    } else if range.start >= mid {
+        query(right, mid..span.end, range)
+    } else {
+        query(left, span.start..mid, range) + query(right, mid..span.end, range)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn old_versions_are_preserved() {
+        let mut values = vec![5, 3, 8, 1, 9, 2, 7];
+        let mut tree = PersistentSegmentTree::new(&values);
+        let mut history = vec![values.clone()];
+        let updates = [(0, 2, 0), (1, 6, -4), (0, 0, 10), (3, 3, 3), (2, 5, 6)];
+        for &(version, index, value) in updates.iter() {
+            values = history[version].clone();
+            values[index] = value;
+            assert_eq!(tree.update(version, index, value), history.len());
+            history.push(values.clone());
+        }
+        assert_eq!(tree.versions(), 6);
+
+        for (version, values) in history.iter().enumerate() {
+            for start in 0..=values.len() {
+                for end in start..=values.len() {
+                    let expected: i32 = values[start..end].iter().sum();
+                    assert_eq!(tree.query(version, start..end), expected);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn updates_share_unchanged_nodes() {
+        let mut tree = PersistentSegmentTree::new(&[1, 2, 3, 4]);
+        let v1 = tree.update(0, 0, 5);
+        let (old, new) = match (&tree.roots[0], &tree.roots[v1]) {
+            (Some(old), Some(new)) => (old, new),
+            _ => unreachable!(),
+        };
+        let (old_left, old_right) = old.children.as_ref().unwrap();
+        let (new_left, new_right) = new.children.as_ref().unwrap();
+        assert!(Rc::ptr_eq(old_right, new_right));
+        assert!(!Rc::ptr_eq(old_left, new_left));
+    }
+
+    #[test]
+    fn single_element_and_empty() {
+        let mut tree = PersistentSegmentTree::new(&[4]);
+        let v1 = tree.update(0, 0, 9);
+        assert_eq!(tree.query(0, 0..1), 4);
+        assert_eq!(tree.query(v1, 0..1), 9);
+        assert_eq!(tree.query(v1, 1..1), 0);
+
+        let empty = PersistentSegmentTree::<i64>::new(&[]);
+        assert!(empty.is_empty());
+        assert_eq!(empty.query(0, 0..0), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "does not exist")]
+    fn unknown_version() {
+        let mut tree = PersistentSegmentTree::new(&[1, 2, 3]);
+        tree.update(1, 0, 0);
+    }
+}
@@ -0,0 +1,175 @@
+/// A union-find data structure whose unions can be undone.
+///
+/// Every successful union attaches the root of the smaller set to the root of the larger one and
+/// records that on an undo stack, so it can be reverted by detaching the root again. For this to
+/// work, `find` must never restructure the trees, so there is no path compression. Union by size
+/// alone keeps every tree at most O(log n) deep, which makes `find`, `union` and `connected`
+/// take O(log n) instead of the nearly constant amortized time of a compressed union-find, while
+/// undoing a union takes O(1). This suits backtracking algorithms and offline dynamic
+/// connectivity, which need to return to earlier states.
+///
+/// # Examples
+///
+/// ```rust
+/// use rust_algorithms::data_structures::RollbackUnionFind;
+///
+/// let mut uf = RollbackUnionFind::new(4);
+/// uf.union(0, 1);
+/// let checkpoint = uf.checkpoint();
+/// uf.union(1, 2);
+/// uf.union(2, 3);
+/// assert!(uf.connected(0, 3));
+///
+/// uf.rollback(checkpoint);
+/// assert!(uf.connected(0, 1));
+/// assert!(!uf.connected(0, 2));
+/// ```
+pub struct RollbackUnionFind {
+    parent: Vec<usize>,
+    size: Vec<usize>,
+    count: usize,
+    /// The roots attached by each successful union, most recent last.
+    history: Vec<usize>,
+}
+
+impl RollbackUnionFind {
+    /// Creates a new `RollbackUnionFind` with `n` singleton sets.
+    pub fn new(n: usize) -> Self {
+        RollbackUnionFind {
+            parent: (0..n).collect(),
+            size: vec![1; n],
+            count: n,
+            history: Vec::new(),
+        }
+    }
+
+    /// Returns the representative of the set containing `x`.
+    pub fn find(&self, mut x: usize) -> usize {
+        while x != self.parent[x] {
+            x = self.parent[x];
+        }
+        x
+    }
+
+    /// Returns `true` if `x` and `y` are in the same set.
+    pub fn connected(&self, x: usize, y: usize) -> bool {
+        self.find(x) == self.find(y)
+    }
+
+    /// Unions the sets containing `x` and `y`.
+    ///
+    /// Returns `true` if they were different sets. Only such unions are recorded for undoing.
+    pub fn union(&mut self, x: usize, y: usize) -> bool {
+        let (mut x, mut y) = (self.find(x), self.find(y));
+        if x == y {
+            return false;
+        }
+        if self.size[x] < self.size[y] {
+            std::mem::swap(&mut x, &mut y);
+        }
+        self.parent[y] = x;
+        self.size[x] += self.size[y];
+        self.count -= 1;
+        self.history.push(y);
+        true
+    }
+
+    /// Returns the number of disjoint sets.
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// Returns a checkpoint of the current state to pass to `rollback`.
+    ///
+    /// This is the number of unions performed so far that have not been undone.
+    pub fn checkpoint(&self) -> usize {
+        self.history.len()
+    }
+
+    /// Undoes the most recent union that has not been undone yet.
+    ///
+    /// Returns `false` if there was no union left to undo.
+    pub fn undo(&mut self) -> bool {
+        match self.history.pop() {
+            Some(y) => {
+                let x = self.parent[y];
+                self.parent[y] = y;
+                self.size[x] -= self.size[y];
+                self.count += 1;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Undoes unions until the state of `checkpoint` is restored.
+    ///
+    /// Does nothing if `checkpoint` lies after the current state.
+    pub fn rollback(&mut self, checkpoint: usize) {
+        while self.history.len() > checkpoint {
+            self.undo();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::RollbackUnionFind;
+
+    fn components(uf: &RollbackUnionFind) -> Vec<usize> {
+        (0..uf.parent.len()).map(|x| uf.find(x)).collect()
+    }
+
+    #[test]
+    fn union_and_connected() {
+        let mut uf = RollbackUnionFind::new(6);
+        assert!(uf.union(0, 1));
+        assert!(uf.union(2, 3));
+        assert!(uf.union(1, 3));
+        assert!(!uf.union(0, 2));
+        assert!(uf.connected(0, 3));
+        assert!(!uf.connected(0, 4));
+        assert_eq!(uf.count(), 3);
+    }
+
+    #[test]
+    fn rollback_restores_components() {
+        let mut uf = RollbackUnionFind::new(8);
+        uf.union(0, 1);
+        uf.union(2, 3);
+        let before = components(&uf);
+        let checkpoint = uf.checkpoint();
+
+        uf.union(1, 2);
+        uf.union(4, 5);
+        uf.union(5, 0);
+        // Unions within a set are not recorded, so they need no undoing
+        uf.union(3, 4);
+        assert_eq!(uf.count(), 3);
+        assert!(uf.connected(3, 4));
+
+        uf.rollback(checkpoint);
+        assert_eq!(components(&uf), before);
+        assert_eq!(uf.count(), 6);
+        assert!(uf.connected(0, 1) && uf.connected(2, 3));
+        assert!(!uf.connected(1, 2) && !uf.connected(4, 5));
+        assert_eq!(uf.size[uf.find(0)], 2);
+    }
+
+    #[test]
+    fn undo() {
+        let mut uf = RollbackUnionFind::new(3);
+        uf.union(0, 1);
+        uf.union(1, 2);
+        assert!(uf.undo());
+        assert!(uf.connected(0, 1) && !uf.connected(1, 2));
+        assert!(uf.undo());
+        assert!(!uf.connected(0, 1));
+        assert!(!uf.undo());
+        assert_eq!(uf.count(), 3);
+
+        // New unions after undoing start from the restored state
+        uf.union(2, 0);
+        assert!(uf.connected(0, 2) && !uf.connected(0, 1));
+    }
+}
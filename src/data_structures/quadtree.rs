@@ -0,0 +1,262 @@
+//! A quadtree partitions a rectangular region of the plane into four quadrants, recursively
+//! subdividing a quadrant once it holds more than a fixed number of points. This makes range
+//! queries skip whole regions that cannot contain a match.
+
+type Point = (f64, f64);
+
+/// Quadtrees stop subdividing at this depth, so that many equal points cannot cause unbounded
+/// recursion. Nodes at this depth simply hold more points than their capacity.
+const MAX_DEPTH: usize = 32;
+
+/// An axis-aligned rectangle, including its edges.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Rect {
+    pub x_min: f64,
+    pub y_min: f64,
+    pub x_max: f64,
+    pub y_max: f64,
+}
+
+impl Rect {
+    /// Creates the rectangle spanning `x_min..=x_max` and `y_min..=y_max`.
+    pub fn new(x_min: f64, y_min: f64, x_max: f64, y_max: f64) -> Self {
+        Rect {
+            x_min,
+            y_min,
+            x_max,
+            y_max,
+        }
+    }
+
+    /// Checks if the point lies inside the rectangle or on its edges.
+    pub fn contains(&self, (x, y): Point) -> bool {
+        self.x_min <= x && x <= self.x_max && self.y_min <= y && y <= self.y_max
+    }
+
+    /// Checks if the two rectangles share at least one point.
+    pub fn intersects(&self, other: &Rect) -> bool {
+        self.x_min <= other.x_max
+            && other.x_min <= self.x_max
+            && self.y_min <= other.y_max
+            && other.y_min <= self.y_max
+    }
+}
+
+struct Node {
+    boundary: Rect,
+    points: Vec<Point>,
+    children: Option<Box<[Node; 4]>>,
+    depth: usize,
+}
+
+/// A point quadtree over a fixed bounding box.
+///
+/// Points lying exactly on a subdivision line are assigned to the quadrant with the larger
+/// coordinate, i.e. a point on the vertical split goes east and a point on the horizontal split
+/// goes north. Points on the outer edges of the bounding box are accepted.
+///
+/// # Examples
+///
+/// ```rust
+/// use rust_algorithms::data_structures::{QuadTree, Rect};
+///
+/// let mut tree = QuadTree::new(Rect::new(0.0, 0.0, 10.0, 10.0), 4);
+/// tree.insert((1.0, 1.0));
+/// tree.insert((5.0, 5.0));
+/// tree.insert((9.0, 9.0));
+///
+/// assert_eq!(tree.query_range(&Rect::new(0.0, 0.0, 5.0, 5.0)).len(), 2);
+/// ```
+pub struct QuadTree {
+    root: Node,
+    capacity: usize,
+    length: usize,
+}
+
+impl QuadTree {
+    /// Creates an empty quadtree covering `boundary`, where a node is subdivided once it holds
+    /// more than `capacity` points.
+    pub fn new(boundary: Rect, capacity: usize) -> Self {
+        assert!(capacity > 0, "node capacity must be positive");
+        QuadTree {
+            root: Node::new(boundary, 0),
+            capacity,
+            length: 0,
+        }
+    }
+
+    /// Inserts a point, returning `false` if it lies outside the bounding box.
+    pub fn insert(&mut self, point: Point) -> bool {
+        if !self.root.boundary.contains(point) {
+            return false;
+        }
+        self.root.insert(point, self.capacity);
+        self.length += 1;
+        true
+    }
+
+    /// Returns all stored points lying inside `range` or on its edges.
+    pub fn query_range(&self, range: &Rect) -> Vec<Point> {
+        let mut found = Vec::new();
+        self.root.query_range(range, &mut found);
+        found
+    }
+
+    /// Returns the number of points in the quadtree.
+    pub fn len(&self) -> usize {
+        self.length
+    }
+
+    /// Checks if the quadtree holds no points.
+    pub fn is_empty(&self) -> bool {
+        self.length == 0
+    }
+}
+
+impl Node {
+    fn new(boundary: Rect, depth: usize) -> Self {
+        Node {
+            boundary,
+            points: Vec::new(),
+            children: None,
+            depth,
+        }
+    }
+
+    fn midpoint(&self) -> Point {
+        (
+            (self.boundary.x_min + self.boundary.x_max) / 2.0,
+            (self.boundary.y_min + self.boundary.y_max) / 2.0,
+        )
+    }
+
+    /// Index of the child quadrant `point` belongs to: west/east in bit 0, south/north in bit 1.
+    fn quadrant(&self, (x, y): Point) -> usize {
+        let (mid_x, mid_y) = self.midpoint();
+        (x >= mid_x) as usize + 2 * (y >= mid_y) as usize
+    }
+
+    fn insert(&mut self, point: Point, capacity: usize) {
+        let quadrant = self.quadrant(point);
+        if let Some(children) = &mut self.children {
+            return children[quadrant].insert(point, capacity);
+        }
+        self.points.push(point);
+        if self.points.len() > capacity && self.depth < MAX_DEPTH {
+            self.subdivide(capacity);
+        }
+    }
+
+    fn subdivide(&mut self, capacity: usize) {
+        let Rect {
+            x_min,
+            y_min,
+            x_max,
+            y_max,
+        } = self.boundary;
+        let (mid_x, mid_y) = self.midpoint();
+        let depth = self.depth + 1;
+        self.children = Some(Box::new([
+            Node::new(Rect::new(x_min, y_min, mid_x, mid_y), depth),
+            Node::new(Rect::new(mid_x, y_min, x_max, mid_y), depth),
+            Node::new(Rect::new(x_min, mid_y, mid_x, y_max), depth),
+            Node::new(Rect::new(mid_x, mid_y, x_max, y_max), depth),
+        ]));
+        for point in std::mem::take(&mut self.points) {
+            self.insert(point, capacity);
+        }
+    }
+
+    fn query_range(&self, range: &Rect, found: &mut Vec<Point>) {
+        if !self.boundary.intersects(range) {
+            return;
+        }
+        found.extend(self.points.iter().filter(|&&p| range.contains(p)));
+        if let Some(children) = &self.children {
+            for child in children.iter() {
+                child.query_range(range, found);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sorted(mut points: Vec<Point>) -> Vec<Point> {
+        points.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        points
+    }
+
+    fn grid() -> (QuadTree, Vec<Point>) {
+        let mut tree = QuadTree::new(Rect::new(0.0, 0.0, 10.0, 10.0), 4);
+        let mut points = Vec::new();
+        for x in 0..=10 {
+            for y in 0..=10 {
+                let point = (x as f64, y as f64);
+                assert!(tree.insert(point));
+                points.push(point);
+            }
+        }
+        (tree, points)
+    }
+
+    #[test]
+    fn query_grid() {
+        let (tree, points) = grid();
+        assert_eq!(tree.len(), 121);
+
+        let range = Rect::new(2.0, 3.0, 5.0, 6.0);
+        let expected: Vec<Point> = points
+            .iter()
+            .copied()
+            .filter(|&p| range.contains(p))
+            .collect();
+        let found = tree.query_range(&range);
+        assert_eq!(found.len(), 16);
+        assert_eq!(sorted(found), sorted(expected));
+
+        let range = Rect::new(2.5, 2.5, 3.5, 7.25);
+        assert_eq!(
+            sorted(tree.query_range(&range)),
+            vec![(3.0, 3.0), (3.0, 4.0), (3.0, 5.0), (3.0, 6.0), (3.0, 7.0)]
+        );
+    }
+
+    #[test]
+    fn query_everything_and_nothing() {
+        let (tree, points) = grid();
+        let all = tree.query_range(&Rect::new(-1.0, -1.0, 11.0, 11.0));
+        assert_eq!(sorted(all), sorted(points));
+        assert!(tree
+            .query_range(&Rect::new(20.0, 20.0, 30.0, 30.0))
+            .is_empty());
+        assert!(tree.query_range(&Rect::new(0.1, 0.1, 0.9, 0.9)).is_empty());
+    }
+
+    #[test]
+    fn boundaries() {
+        let mut tree = QuadTree::new(Rect::new(0.0, 0.0, 4.0, 4.0), 1);
+        assert!(!tree.insert((4.5, 1.0)));
+        assert!(tree.insert((4.0, 4.0)));
+        // Points on the split lines are stored exactly once
+        assert!(tree.insert((2.0, 2.0)));
+        assert!(tree.insert((2.0, 0.0)));
+        assert_eq!(tree.len(), 3);
+        assert_eq!(
+            tree.query_range(&Rect::new(2.0, 2.0, 2.0, 2.0)),
+            vec![(2.0, 2.0)]
+        );
+        assert_eq!(tree.query_range(&Rect::new(0.0, 0.0, 4.0, 4.0)).len(), 3);
+    }
+
+    #[test]
+    fn duplicate_points() {
+        let mut tree = QuadTree::new(Rect::new(0.0, 0.0, 1.0, 1.0), 2);
+        for _ in 0..100 {
+            tree.insert((0.5, 0.5));
+        }
+        assert_eq!(tree.query_range(&Rect::new(0.0, 0.0, 1.0, 1.0)).len(), 100);
+    }
+}
@@ -1,68 +1,200 @@
 use std::{
-    cmp::Ordering,
+    cmp::max,
     fmt::{Display, Formatter},
     ops::{Index, Range},
 };
 
-pub struct NodeData {
-    pub left: Option<Box<Rope>>,
-    pub right: Option<Box<Rope>>,
-    pub weight: usize,
+/// The largest number of chars stored in a single leaf.
+const CHUNK: usize = 64;
+
+/// A node of a `Rope`.
+enum RopeNode {
+    Leaf {
+        text: String,
+        /// Number of chars in `text`.
+        chars: usize,
+    },
+    Node {
+        left: Box<RopeNode>,
+        right: Box<RopeNode>,
+        /// Number of chars in the left subtree, also called the weight of the node.
+        weight: usize,
+        /// Number of chars in both subtrees.
+        chars: usize,
+        height: usize,
+    },
 }
 
-impl Display for NodeData {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        if let Some(l) = &self.left {
-            write!(f, "{l}")?;
+/// A Rope is a data structure designed for efficient manipulation of large strings
+/// of text by dividing the text into smaller segments represented as nodes in a binary tree.
+/// Each leaf holds a chunk of the text, and each node further up the tree holds the length of
+/// its left subtree (also known as its "weight"), which guides the search for an index.
+///
+/// The tree is kept height-balanced like an AVL tree: two ropes are joined by descending the
+/// spine of the taller one and rotating on the way back up. Splitting at an index and joining
+/// both take O(log n), and every edit is composed of those two operations.
+///
+/// All lengths and indices count chars rather than bytes, so a multi-byte character is never
+/// split apart.
+///
+/// # Examples
+///
+/// ```rust
+/// use rust_algorithms::data_structures::Rope;
+///
+/// let mut rope = Rope::from("hello world");
+/// rope.insert(5, ",");
+/// rope.delete(6..7);
+/// rope = rope.concat(Rope::from("!"));
+///
+/// assert_eq!(rope.to_string(), "hello,world!");
+/// assert_eq!(rope.substring(6..11), "world");
+/// assert_eq!(rope.len(), 12);
+/// ```
+#[derive(Default)]
+pub struct Rope {
+    root: Option<Box<RopeNode>>,
+}
+
+impl Rope {
+    /// Creates an empty rope.
+    pub fn new() -> Self {
+        Rope { root: None }
+    }
+
+    /// Returns the number of chars in the rope.
+    pub fn len(&self) -> usize {
+        self.root.as_ref().map_or(0, |n| n.chars())
+    }
+
+    /// Returns `true` if the rope contains no text.
+    pub fn is_empty(&self) -> bool {
+        self.root.is_none()
+    }
+
+    /// Appends `other` to the end of this rope in O(log n).
+    pub fn concat(self, other: Rope) -> Rope {
+        Rope {
+            root: join_options(self.root, other.root),
         }
-        if let Some(r) = &self.right {
-            write!(f, "{r}")?;
+    }
+
+    /// Splits the rope at a char index, leaving `[0, index)` in `self` and returning the rest.
+    ///
+    /// Panics if `index > len()`.
+    pub fn split_off(&mut self, index: usize) -> Rope {
+        assert!(index <= self.len(), "index out of bounds");
+        let (left, right) = split(self.root.take(), index);
+        self.root = left;
+        Rope { root: right }
+    }
+
+    /// Inserts `text` before the char at `index`.
+    ///
+    /// Panics if `index > len()`.
+    pub fn insert(&mut self, index: usize, text: &str) {
+        let right = self.split_off(index);
+        let middle = Rope::from(text);
+        self.root = join_options(join_options(self.root.take(), middle.root), right.root);
+    }
+
+    /// Removes the chars in `range`.
+    ///
+    /// Panics if the range is decreasing or extends past the end of the rope.
+    pub fn delete(&mut self, range: Range<usize>) {
+        assert!(range.start <= range.end, "range is decreasing");
+        let right = self.split_off(range.end);
+        self.split_off(range.start);
+        self.root = join_options(self.root.take(), right.root);
+    }
+
+    /// Copies the chars in `range` into a new `String`.
+    ///
+    /// Only the leaves overlapping the range are visited, which takes O(log n + k) for a range
+    /// of k chars. Panics if the range is decreasing or extends past the end of the rope.
+    pub fn substring(&self, range: Range<usize>) -> String {
+        assert!(
+            range.start <= range.end && range.end <= self.len(),
+            "range out of bounds"
+        );
+        let mut result = String::new();
+        if let Some(root) = &self.root {
+            root.append_range(range.start, range.end, &mut result);
         }
+        result
+    }
 
-        Ok(())
+    /// Returns the height of the tree, for testing the balance.
+    #[cfg(test)]
+    fn height(&self) -> usize {
+        self.root.as_ref().map_or(0, |n| n.height())
     }
 }
 
-/// A Rope is a data structure designed for efficient manipulation of large strings
-/// of text by dividing the text into smaller segments represented as nodes in a binary tree.
-/// Each leaf (end node) holds a string and a length (also known as a "weight"),
-/// and each node further up the tree holds the sum of the lengths of all the leaves in its left subtree.
-pub enum Rope {
-    Leaf(String),
-    Node(NodeData),
+impl From<&str> for Rope {
+    /// Builds a balanced rope from a string, cutting it into chunks of at most `CHUNK` chars.
+    fn from(text: &str) -> Self {
+        let mut leaves = Vec::new();
+        let mut start = 0;
+        let mut chars = 0;
+        for (i, _) in text.char_indices() {
+            if chars == CHUNK {
+                leaves.push(RopeNode::leaf(&text[start..i]));
+                start = i;
+                chars = 0;
+            }
+            chars += 1;
+        }
+        if start < text.len() {
+            leaves.push(RopeNode::leaf(&text[start..]));
+        }
+        let count = leaves.len();
+        Rope {
+            root: build(&mut leaves.into_iter().map(Box::new), count),
+        }
+    }
 }
 
 impl Display for Rope {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        match self {
-            Rope::Leaf(s) => write!(f, "{s}"),
-            Rope::Node(n) => write!(f, "{n}"),
+        let mut stack: Vec<&RopeNode> = self.root.iter().map(|n| n.as_ref()).collect();
+        while let Some(node) = stack.pop() {
+            match node {
+                RopeNode::Leaf { text, .. } => write!(f, "{text}")?,
+                RopeNode::Node { left, right, .. } => {
+                    stack.push(right);
+                    stack.push(left);
+                }
+            }
         }
+        Ok(())
     }
 }
 
 impl Index<usize> for Rope {
     type Output = str;
 
-    fn index(&self, index: usize) -> &str {
-        match self {
-            Rope::Leaf(data) => return &data[index..index + 1],
-            Rope::Node(NodeData {
-                left,
-                right,
-                weight,
-            }) => {
-                if index < *weight {
-                    if let Some(left) = left {
-                        return &left[index];
-                    } else {
-                        unreachable!("Rope weight is inconsistent with left child");
-                    }
-                } else {
-                    if let Some(right) = right {
-                        return &right[index - weight];
+    /// Returns the char at `index` as a string slice.
+    fn index(&self, mut index: usize) -> &str {
+        assert!(index < self.len(), "index out of bounds");
+        let mut node = self.root.as_ref().unwrap();
+        loop {
+            match node.as_ref() {
+                RopeNode::Leaf { text, .. } => {
+                    let (start, c) = text.char_indices().nth(index).unwrap();
+                    return &text[start..start + c.len_utf8()];
+                }
+                RopeNode::Node {
+                    left,
+                    right,
+                    weight,
+                    ..
+                } => {
+                    if index < *weight {
+                        node = left;
                     } else {
-                        unreachable!("Rope weight is inconsistent with right child");
+                        index -= weight;
+                        node = right;
                     }
                 }
             }
@@ -70,380 +202,306 @@ impl Index<usize> for Rope {
     }
 }
 
-impl Rope {
-    pub fn get_weight(&self) -> usize {
+impl RopeNode {
+    fn leaf(text: &str) -> Self {
+        RopeNode::Leaf {
+            text: text.to_string(),
+            chars: text.chars().count(),
+        }
+    }
+
+    fn chars(&self) -> usize {
         match self {
-            Rope::Node(node) => node.weight,
-            Rope::Leaf(str) => str.len(),
+            RopeNode::Leaf { chars, .. } | RopeNode::Node { chars, .. } => *chars,
         }
     }
 
-    pub fn split_at(self: Box<Rope>, index: usize) -> (Option<Box<Rope>>, Option<Box<Rope>>) {
-        if index == 0 {
-            return (None, Some(self));
+    fn height(&self) -> usize {
+        match self {
+            RopeNode::Leaf { .. } => 1,
+            RopeNode::Node { height, .. } => *height,
         }
+    }
 
-        match *self {
-            Rope::Leaf(ref str) => {
-                if index >= (str.len() - 1) {
-                    (Some(self), None)
-                } else if index == 0 {
-                    (None, Some(self))
-                } else {
-                    (
-                        Some(Box::new(Rope::Leaf(String::from(&str[0..index])))),
-                        Some(Box::new(Rope::Leaf(String::from(&str[index..])))),
-                    )
-                }
+    /// Appends the chars in `[start, end)` of this subtree to `result`.
+    fn append_range(&self, start: usize, end: usize, result: &mut String) {
+        if start >= end {
+            return;
+        }
+        match self {
+            RopeNode::Leaf { text, .. } => {
+                result.extend(text.chars().skip(start).take(end - start))
             }
-            Rope::Node(node) => match index.cmp(&(node.weight - 1)) {
-                Ordering::Equal => (node.left, node.right),
-                Ordering::Less => {
-                    let Some(left) = node.left else {
-                        unreachable!("Rope weight is inconsistent with left child");
-                    };
-
-                    let (left, right) = left.split_at(index);
-                    if left.is_none() && right.is_none() {
-                        unreachable!("Invalid split results");
-                    }
-
-                    if right.is_some() {
-                        let new_right = NodeData {
-                            left: right,
-                            right: node.right,
-                            weight: node.weight - index - 1,
-                        };
-                        let new_right = Some(Box::new(Rope::Node(new_right)));
-                        return (left, new_right);
-                    };
-
-                    (left, node.right)
+            RopeNode::Node {
+                left,
+                right,
+                weight,
+                ..
+            } => {
+                if start < *weight {
+                    left.append_range(start, end.min(*weight), result);
                 }
-                Ordering::Greater => {
-                    let Some(right) = node.right else {
-                        unreachable!("Rope weight is inconsistent with left child");
-                    };
-
-                    let (left, right) = right.split_at(index - node.weight);
-                    if left.is_none() && right.is_none() {
-                        unreachable!("Invalid split results");
-                    }
-
-                    if left.is_some() && right.is_some() {
-                        let weight = node.left.as_ref().map_or(0, |l| l.get_weight());
-                        let new_left = NodeData {
-                            weight,
-                            right: left,
-                            left: node.left,
-                        };
-                        let new_left = Some(Box::new(Rope::Node(new_left)));
-                        (new_left, right)
-                    } else if left.is_some() {
-                        let new_left = NodeData {
-                            left: node.left,
-                            right: left,
-                            weight: node.weight,
-                        };
-                        let new_left = Some(Box::new(Rope::Node(new_left)));
-                        (new_left, None)
-                    } else {
-                        (node.left, right)
-                    }
+                if end > *weight {
+                    right.append_range(start.saturating_sub(*weight), end - weight, result);
                 }
-            },
+            }
         }
     }
+}
+
+/// Creates a node over two subtrees whose heights differ by at most one.
+fn node(left: Box<RopeNode>, right: Box<RopeNode>) -> Box<RopeNode> {
+    Box::new(RopeNode::Node {
+        weight: left.chars(),
+        chars: left.chars() + right.chars(),
+        height: 1 + max(left.height(), right.height()),
+        left,
+        right,
+    })
+}
 
-    pub fn concat(self: Box<Rope>, target: Box<Rope>) -> Box<Rope> {
-        Box::new(Rope::Node(NodeData {
-            weight: self.get_weight(),
-            right: Some(target),
-            left: Some(self),
-        }))
+/// Separates an inner node into its children. Must not be called on a leaf.
+fn children(tree: RopeNode) -> (Box<RopeNode>, Box<RopeNode>) {
+    match tree {
+        RopeNode::Node { left, right, .. } => (left, right),
+        RopeNode::Leaf { .. } => unreachable!("a leaf has no children"),
     }
+}
 
-    pub fn insert(self: Box<Rope>, value: &str, index: usize) -> Box<Rope> {
-        if value.len() == 0 {
-            return self;
+/// Creates a node over two subtrees whose heights differ by at most two, rotating if needed.
+fn balance(left: Box<RopeNode>, right: Box<RopeNode>) -> Box<RopeNode> {
+    if left.height() > right.height() + 1 {
+        let (a, b) = children(*left);
+        if b.height() > a.height() {
+            // Left-Right case
+            let (b1, b2) = children(*b);
+            node(node(a, b1), node(b2, right))
+        } else {
+            node(a, node(b, right))
         }
-
-        let new_leaf = Box::new(Rope::Leaf(String::from(value)));
-        match self.split_at(index) {
-            (Some(left), Some(right)) => left.concat(new_leaf.concat(right)),
-            (None, Some(rope)) => new_leaf.concat(rope),
-            (Some(rope), None) => rope.concat(new_leaf),
-            _ => unreachable!("Split operation produced None, None"),
+    } else if right.height() > left.height() + 1 {
+        let (a, b) = children(*right);
+        if a.height() > b.height() {
+            // Right-Left case
+            let (a1, a2) = children(*a);
+            node(node(left, a1), node(a2, b))
+        } else {
+            node(node(left, a), b)
         }
+    } else {
+        node(left, right)
     }
+}
 
-    pub fn delete_at(
-        self: Box<Rope>,
-        start: usize,
-        length: usize,
-    ) -> Result<Box<Rope>, &'static str> {
-        if length <= 0 {
-            return Err("Length to remove must be positive");
-        }
-        if start + length - 1 >= self.to_string().len() {
-            return Err("Index out of range");
+/// Concatenates two trees of any heights into a balanced tree.
+fn join(left: Box<RopeNode>, right: Box<RopeNode>) -> Box<RopeNode> {
+    if let (RopeNode::Leaf { text: l, chars: a }, RopeNode::Leaf { text: r, chars: b }) =
+        (left.as_ref(), right.as_ref())
+    {
+        // Keep small neighbouring pieces in a single leaf
+        if a + b <= CHUNK {
+            return Box::new(RopeNode::Leaf {
+                text: format!("{l}{r}"),
+                chars: a + b,
+            });
         }
+    }
+    if left.height() > right.height() + 1 {
+        let (a, b) = children(*left);
+        balance(a, join(b, right))
+    } else if right.height() > left.height() + 1 {
+        let (a, b) = children(*right);
+        balance(join(left, a), b)
+    } else {
+        node(left, right)
+    }
+}
 
-        let (left, remain) = self.split_at(start);
-        let Some(remain) = remain else {
-            return Ok(left.unwrap_or_else(|| unreachable!("Split operation produced None, None")));
-        };
+fn join_options(
+    left: Option<Box<RopeNode>>,
+    right: Option<Box<RopeNode>>,
+) -> Option<Box<RopeNode>> {
+    match (left, right) {
+        (Some(left), Some(right)) => Some(join(left, right)),
+        (left, None) => left,
+        (None, right) => right,
+    }
+}
 
-        let (_, right) = remain.split_at(length);
-        if left.is_some() {
-            return Ok(Box::new(Rope::Node(NodeData {
-                weight: left.as_ref().unwrap().get_weight(),
-                left,
-                right,
-            })));
-        } else if right.is_some() {
-            Ok(right.unwrap())
-        } else {
-            Err("Cannot delete the entire input")
+/// Splits a tree into its first `index` chars and the rest.
+fn split(
+    tree: Option<Box<RopeNode>>,
+    index: usize,
+) -> (Option<Box<RopeNode>>, Option<Box<RopeNode>>) {
+    let tree = match tree {
+        Some(tree) => tree,
+        None => return (None, None),
+    };
+    if index == 0 {
+        return (None, Some(tree));
+    }
+    if index >= tree.chars() {
+        return (Some(tree), None);
+    }
+    match *tree {
+        RopeNode::Leaf { text, .. } => {
+            let (byte, _) = text.char_indices().nth(index).unwrap();
+            (
+                Some(Box::new(RopeNode::leaf(&text[..byte]))),
+                Some(Box::new(RopeNode::leaf(&text[byte..]))),
+            )
+        }
+        RopeNode::Node {
+            left,
+            right,
+            weight,
+            ..
+        } => {
+            if index <= weight {
+                let (a, b) = split(Some(left), index);
+                (a, join_options(b, Some(right)))
+            } else {
+                let (a, b) = split(Some(right), index - weight);
+                (join_options(Some(left), a), b)
+            }
         }
     }
+}
 
-    pub fn slice(&self, start: usize, size: usize) -> String {
-        self.to_string()[start..start + size].to_string()
+/// Builds a balanced tree from the next `count` leaves.
+fn build<I: Iterator<Item = Box<RopeNode>>>(leaves: &mut I, count: usize) -> Option<Box<RopeNode>> {
+    match count {
+        0 => None,
+        1 => leaves.next(),
+        _ => {
+            let left = build(leaves, count / 2).unwrap();
+            let right = build(leaves, count - count / 2).unwrap();
+            Some(node(left, right))
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{NodeData, Rope};
+    use super::{Rope, CHUNK};
+    use crate::math::PCG32;
 
-    macro_rules! boxed {
-        ($wrapped: expr) => {
-            Box::new($wrapped)
-        };
-    }
-
-    macro_rules! leaf {
-        ($wrapped: expr) => {
-            Some(boxed!(Rope::Leaf($wrapped)))
-        };
-    }
-
-    macro_rules! node {
-        (left: $left: expr, right: $right: expr, weight: $weight: expr $(,)?) => {
-            boxed!(Rope::Node(NodeData {
-                left: $left,
-                right: $right,
-                weight: $weight
-            }))
-        };
+    fn fragments(parts: &[&str]) -> Rope {
+        parts
+            .iter()
+            .fold(Rope::new(), |rope, part| rope.concat(Rope::from(*part)))
     }
 
     #[test]
     fn to_string() {
-        let rope = node! {
-            left: leaf!(String::from("hello ")),
-            right: leaf!(String::from("world")),
-            weight: 6,
-        };
+        let rope = fragments(&["hello ", "world"]);
         assert_eq!(rope.to_string(), "hello world");
+        assert_eq!(rope.len(), 11);
+        assert_eq!(Rope::new().to_string(), "");
     }
 
     #[test]
     fn concats() {
-        let left = node! {
-            left: leaf!(String::from("hello ")),
-            right: None,
-            weight: 6,
-        };
-
-        let right = boxed!(Rope::Leaf(String::from("world")));
-        let concat = left.concat(right);
-        let weight = concat.get_weight();
-
-        assert_eq!(weight, 6);
-    }
-
-    #[test]
-    fn split_at_start() {
-        let rope = node! {
-        left: Some(node! {
-            left: leaf!(String::from("hello ")),
-            right: None,
-            weight: 6,
-        }),
-        right: leaf!(String::from("world")),
-        weight: 6,
-        };
-
-        let (None, Some(first)) = rope.split_at(0) else {
-            panic!("Split at first element should return the original Rope and None")
-        };
-        assert_eq!(first.get_weight(), 6);
-        assert_eq!(first.to_string(), "hello world");
-    }
-
-    #[test]
-    fn split_at_length() {
-        let rope = node! {
-            left: Some(node! {
-                left: leaf!(String::from("hello ")),
-                right: None,
-                weight: 6,
-            }),
-            right: leaf!(String::from("world")),
-            weight: 6,
-        };
-
-        let (Some(first), None) = rope.split_at(("hello world").len()) else {
-            panic!("Split at last element should return the original Rope and None")
-        };
-        assert_eq!(first.get_weight(), 6);
-        assert_eq!(first.to_string(), "hello world");
-    }
-
-    #[test]
-    fn split_in_half() {
-        let rope = node! {
-            left: Some(node! {
-                left: leaf!(String::from("hello ")),
-                right: None,
-                weight: 6,
-            }),
-            right: leaf!(String::from("world")),
-            weight: 6,
-        };
-
-        let (Some(first), Some(second)) = rope.split_at(("hello world").len() / 2) else {
-            panic!("Split in half should return two non-None ropes")
-        };
-        assert_eq!(first.to_string(), "hello ");
-        assert_eq!(second.to_string(), "world");
-    }
-
-    #[test]
-    fn split_at_leaf() {
-        let rope = node! {
-            left: leaf!(String::from("hello!")),
-            right: None,
-            weight: 6,
-        };
-
-        let (left, right) = rope.split_at(2);
-        if left.is_none() || right.is_none() {
-            panic!("Invalid split results: left & right must be Some(_)");
-        }
-        assert_eq!(left.unwrap().to_string(), "he");
-        assert_eq!(right.unwrap().to_string(), "llo!");
+        let rope = Rope::from("hello ").concat(Rope::from("world"));
+        assert_eq!(rope.to_string(), "hello world");
+        let rope = rope.concat(Rope::new());
+        assert_eq!(rope.len(), 11);
     }
 
     #[test]
-    fn insert_at_start() {
-        let mut rope = node! {
-            left: Some(node! {
-                left: leaf!(String::from("hello ")),
-                right: None,
-                weight: 6,
-            }),
-            right: leaf!(String::from("world")),
-            weight: 6,
-        };
-
-        rope = rope.insert("He said: ", 0);
-        assert_eq!(rope.to_string(), "He said: hello world");
+    fn split_off() {
+        let mut rope = fragments(&["hello ", "world"]);
+        let second = rope.split_off(5);
+        assert_eq!(rope.to_string(), "hello");
+        assert_eq!(second.to_string(), " world");
+
+        let mut rope = fragments(&["hello ", "world"]);
+        assert!(rope.split_off(11).is_empty());
+        assert_eq!(rope.split_off(0).to_string(), "hello world");
+        assert!(rope.is_empty());
     }
 
     #[test]
-    fn insert_at_end() {
-        let mut rope = node! {
-            left: Some(node! {
-                left: leaf!(String::from("hello ")),
-                right: None,
-                weight: 6,
-            }),
-            right: leaf!(String::from("world")),
-            weight: 6,
-        };
-
-        rope = rope.insert("!!!", ("hello world").len());
-        assert_eq!(rope.to_string(), "hello world!!!");
+    fn insert_at_start_middle_and_end() {
+        let mut rope = fragments(&["foo ", "baz"]);
+        rope.insert(4, "bar ");
+        assert_eq!(rope.to_string(), "foo bar baz");
+        rope.insert(0, "He said: ");
+        assert_eq!(rope.to_string(), "He said: foo bar baz");
+        rope.insert(rope.len(), "!!!");
+        assert_eq!(rope.to_string(), "He said: foo bar baz!!!");
     }
 
     #[test]
-    fn insert_in_middle() {
-        let mut rope = node! {
-            left: Some(node! {
-                left: leaf!(String::from("foo ")),
-                right: None,
-                weight: 4,
-            }),
-            right: leaf!(String::from("baz")),
-            weight: 4,
-        };
-
-        rope = rope.insert("bar ", 3);
-        assert_eq!(rope.to_string(), "foo bar baz");
+    fn delete_at_start_middle_and_end() {
+        let mut rope = fragments(&["foo ", "bar ", "baz"]);
+        rope.delete(4..8);
+        assert_eq!(rope.to_string(), "foo baz");
+        rope.delete(0..4);
+        assert_eq!(rope.to_string(), "baz");
+        rope.delete(2..3);
+        assert_eq!(rope.to_string(), "ba");
+        rope.delete(0..2);
+        assert!(rope.is_empty());
     }
 
     #[test]
-    fn delete_in_middle() {
-        let rope = node! {
-            left: Some(node! {
-                left: leaf!(String::from("foo ")),
-                right: None,
-                weight: 4,
-            }),
-            right: leaf!(String::from("baz")),
-            weight: 4,
-        };
-
-        let rope = rope.delete_at(4, 3).unwrap();
-        assert_eq!(rope.to_string(), "foo ")
+    fn indexing_and_substring() {
+        let rope = fragments(&["hello ", "world"]);
+        let chars: String = (0..rope.len()).map(|i| &rope[i]).collect();
+        assert_eq!(chars, "hello world");
+        assert_eq!(rope.substring(0..5), "hello");
+        assert_eq!(rope.substring(5..11), " world");
+        assert_eq!(rope.substring(3..3), "");
     }
 
     #[test]
-    fn delete_at_start() {
-        let rope = node! {
-            left: Some(node! {
-                left: leaf!(String::from("foo ")),
-                right: None,
-                weight: 4,
-            }),
-            right: leaf!(String::from("baz")),
-            weight: 4,
-        };
-        let rope = rope.delete_at(0, 4).unwrap();
-        assert_eq!(rope.to_string(), "baz")
+    fn utf8_boundaries() {
+        let mut rope = fragments(&["grüße, ", "мир ", "🌍!"]);
+        assert_eq!(rope.len(), 13);
+        assert_eq!(&rope[3], "ß");
+        assert_eq!(&rope[11], "🌍");
+        rope.insert(3, "—");
+        rope.delete(11..12);
+        assert_eq!(rope.to_string(), "grü—ße, мир🌍!");
+        assert_eq!(rope.substring(2..5), "ü—ß");
     }
 
     #[test]
-    fn indexing() {
-        let rope = node! {
-            left: leaf!(String::from("hello ")),
-            right: leaf!(String::from("world")),
-            weight: 6,
-        };
-        assert_eq!(&rope[0], "h");
-        assert_eq!(&rope[1], "e");
-        assert_eq!(&rope[2], "l");
-        assert_eq!(&rope[3], "l");
-        assert_eq!(&rope[4], "o");
-        assert_eq!(&rope[5], " ");
-        assert_eq!(&rope[6], "w");
-        assert_eq!(&rope[7], "o");
-        assert_eq!(&rope[8], "r");
-        assert_eq!(&rope[9], "l");
-        assert_eq!(&rope[10], "d");
+    fn matches_string_model() {
+        let mut rng = PCG32::new_default(42);
+        let mut rope = Rope::new();
+        let mut model: Vec<char> = Vec::new();
+        let pieces = ["a", "bc", "äöü", "日本", "longer piece of text ", "🦀"];
+        for _ in 0..3000 {
+            let len = model.len() as u32;
+            if rng.get_u32().is_multiple_of(3) && len > 0 {
+                let a = (rng.get_u32() % (len + 1)) as usize;
+                let b = (rng.get_u32() % (len + 1)) as usize;
+                let (lo, hi) = (a.min(b), a.max(b));
+                rope.delete(lo..hi);
+                model.drain(lo..hi);
+            } else {
+                let index = (rng.get_u32() % (len + 1)) as usize;
+                let piece = pieces[rng.get_u32() as usize % pieces.len()];
+                rope.insert(index, piece);
+                model.splice(index..index, piece.chars());
+            }
+            assert_eq!(rope.len(), model.len());
+        }
+        assert_eq!(rope.to_string(), model.iter().collect::<String>());
     }
 
     #[test]
-    fn slicing() {
-        let rope = node! {
-            left: leaf!(String::from("hello ")),
-            right: leaf!(String::from("world")),
-            weight: 6,
-        };
-        assert_eq!(rope.slice(0, 5), "hello");
-        assert_eq!(rope.slice(5, 6), " world");
+    fn stays_balanced() {
+        let mut rope = Rope::new();
+        for i in 0..5000 {
+            rope.insert(rope.len() / 2, &"x".repeat(CHUNK));
+            rope.insert(0, if i % 2 == 0 { "ab" } else { "c" });
+        }
+        // The rope holds about 10000 leaves, and an AVL tree of that size is at most 19 high
+        assert!(rope.height() <= 20, "height {}", rope.height());
+        let text = "x".repeat(100_000);
+        assert!(Rope::from(text.as_str()).height() <= 12);
     }
 }
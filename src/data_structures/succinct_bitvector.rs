@@ -0,0 +1,212 @@
+/// The number of bits in a word.
+const WORD: usize = 64;
+/// The number of words in a superblock, whose preceding ones are counted up front.
+const SUPERBLOCK: usize = 8;
+
+/// A bit vector answering rank and select queries.
+///
+/// The bits are packed into 64-bit words, and every superblock of 8 words stores how many ones
+/// come before it. `rank1` adds to that count the ones in at most 8 words, counted by the
+/// processor's population count, so it takes O(1). `select1` binary searches the superblock counts
+/// and then scans a single superblock, which takes O(log n). The counts take one `usize` per 512
+/// bits on top of the bits themselves.
+///
+/// # Examples
+///
+/// ```rust
+/// use rust_algorithms::data_structures::BitVector;
+///
+/// let bits = BitVector::from_bits(&[true, false, true, true, false]);
+///
+/// assert_eq!(bits.rank1(3), 2);
+/// assert_eq!(bits.select1(3), Some(3));
+/// assert_eq!(bits.select1(4), None);
+/// ```
+pub struct BitVector {
+    words: Vec<u64>,
+    len: usize,
+    /// `superblocks[s]` is the number of ones in the first `s * SUPERBLOCK` words.
+    superblocks: Vec<usize>,
+}
+
+impl BitVector {
+    /// Creates a bit vector of `len` zeros.
+    pub fn new(len: usize) -> Self {
+        let words = len.div_ceil(WORD);
+        BitVector {
+            words: vec![0; words],
+            len,
+            superblocks: vec![0; words / SUPERBLOCK + 1],
+        }
+    }
+
+    /// Creates a bit vector holding `bits`, counting the ones of every superblock in O(n).
+    pub fn from_bits(bits: &[bool]) -> Self {
+        let mut vector = BitVector::new(bits.len());
+        for (i, _) in bits.iter().enumerate().filter(|(_, &bit)| bit) {
+            vector.words[i / WORD] |= 1 << (i % WORD);
+        }
+        let mut ones = 0;
+        for (s, count) in vector.superblocks.iter_mut().enumerate() {
+            *count = ones;
+            let end = ((s + 1) * SUPERBLOCK).min(vector.words.len());
+            ones += vector.words[(s * SUPERBLOCK).min(end)..end]
+                .iter()
+                .map(|w| w.count_ones() as usize)
+                .sum::<usize>();
+        }
+        vector
+    }
+
+    /// Returns the number of bits.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if the vector holds no bits.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the bit at position `i`.
+    pub fn get(&self, i: usize) -> bool {
+        assert!(i < self.len, "index out of bounds");
+        (self.words[i / WORD] >> (i % WORD)) & 1 == 1
+    }
+
+    /// Sets the bit at position `i` to `value`.
+    ///
+    /// Changing a bit updates the counts of all later superblocks, which takes O(n / 512). To set
+    /// many bits, building the vector with `from_bits` is faster.
+    pub fn set(&mut self, i: usize, value: bool) {
+        assert!(i < self.len, "index out of bounds");
+        if self.get(i) == value {
+            return;
+        }
+        self.words[i / WORD] ^= 1 << (i % WORD);
+        for count in &mut self.superblocks[i / WORD / SUPERBLOCK + 1..] {
+            if value {
+                *count += 1;
+            } else {
+                *count -= 1;
+            }
+        }
+    }
+
+    /// Counts the ones among the first `i` bits.
+    pub fn rank1(&self, i: usize) -> usize {
+        assert!(i <= self.len, "index out of bounds");
+        let (word, bit) = (i / WORD, i % WORD);
+        let start = word / SUPERBLOCK * SUPERBLOCK;
+        let mut ones = self.superblocks[word / SUPERBLOCK];
+        ones += self.words[start..word]
+            .iter()
+            .map(|w| w.count_ones() as usize)
+            .sum::<usize>();
+        if bit > 0 {
+            ones += (self.words[word] & ((1 << bit) - 1)).count_ones() as usize;
+        }
+        ones
+    }
+
+    /// Returns the position of the `k`-th one, counting from 1, or `None` if there are fewer than
+    /// `k` ones.
+    ///
+    /// This is the inverse of `rank1`: `rank1(select1(k) + 1) == k`.
+    pub fn select1(&self, k: usize) -> Option<usize> {
+        if k == 0 || k > self.rank1(self.len) {
+            return None;
+        }
+        // The last superblock with fewer than k ones before it holds the k-th one
+        let s = self.superblocks.partition_point(|&ones| ones < k) - 1;
+        let mut remaining = k - self.superblocks[s];
+        for (w, &word) in self.words.iter().enumerate().skip(s * SUPERBLOCK) {
+            let ones = word.count_ones() as usize;
+            if remaining <= ones {
+                let mut word = word;
+                for _ in 1..remaining {
+                    word &= word - 1;
+                }
+                return Some(w * WORD + word.trailing_zeros() as usize);
+            }
+            remaining -= ones;
+        }
+        unreachable!("the superblock counts cover every one")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::BitVector;
+    use crate::math::PCG32;
+
+    /// Compares every rank and select query with counts over `bits`.
+    fn check(vector: &BitVector, bits: &[bool]) {
+        assert_eq!(vector.len(), bits.len());
+        let mut ones = 0;
+        let mut positions = Vec::new();
+        for (i, &bit) in bits.iter().enumerate() {
+            assert_eq!(vector.rank1(i), ones, "rank1({})", i);
+            assert_eq!(vector.get(i), bit);
+            if bit {
+                ones += 1;
+                positions.push(i);
+            }
+        }
+        assert_eq!(vector.rank1(bits.len()), ones);
+        for (k, &position) in positions.iter().enumerate() {
+            assert_eq!(vector.select1(k + 1), Some(position), "select1({})", k + 1);
+        }
+        assert_eq!(vector.select1(0), None);
+        assert_eq!(vector.select1(ones + 1), None);
+    }
+
+    #[test]
+    fn known_pattern() {
+        // Every third bit is set
+        let bits: Vec<bool> = (0..1000).map(|i| i % 3 == 0).collect();
+        let vector = BitVector::from_bits(&bits);
+        assert_eq!(vector.rank1(64), 22);
+        assert_eq!(vector.rank1(1000), 334);
+        assert_eq!(vector.select1(1), Some(0));
+        assert_eq!(vector.select1(334), Some(999));
+        check(&vector, &bits);
+    }
+
+    #[test]
+    fn word_and_superblock_edges() {
+        let mut rng = PCG32::new_default(275);
+        for &len in [0usize, 1, 63, 64, 65, 127, 128, 511, 512, 513, 1024, 1537].iter() {
+            let bits: Vec<bool> = (0..len).map(|_| !rng.get_u32().is_multiple_of(4)).collect();
+            check(&BitVector::from_bits(&bits), &bits);
+            // Ones exactly at the edges of words
+            let edges: Vec<bool> = (0..len)
+                .map(|i| i.is_multiple_of(64) || i % 64 == 63)
+                .collect();
+            check(&BitVector::from_bits(&edges), &edges);
+            check(&BitVector::from_bits(&vec![true; len]), &vec![true; len]);
+            check(&BitVector::new(len), &vec![false; len]);
+        }
+    }
+
+    #[test]
+    fn set() {
+        let mut rng = PCG32::new_default(2750);
+        let mut bits = vec![false; 1500];
+        let mut vector = BitVector::new(bits.len());
+        for _ in 0..2000 {
+            let i = rng.get_u32() as usize % bits.len();
+            let value = rng.get_u32().is_multiple_of(2);
+            bits[i] = value;
+            vector.set(i, value);
+        }
+        check(&vector, &bits);
+        assert!(BitVector::new(0).is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "index out of bounds")]
+    fn get_out_of_bounds() {
+        BitVector::new(64).get(64);
+    }
+}
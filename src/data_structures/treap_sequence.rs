@@ -0,0 +1,300 @@
+use crate::math::PCG32;
+use std::mem;
+
+/// An optional owned subtree of a `TreapSequence`.
+type Link<T> = Option<Box<TreapNode<T>>>;
+
+/// An internal node of a `TreapSequence`.
+struct TreapNode<T> {
+    value: T,
+    /// Random heap priority; every node's priority is at least that of its children.
+    priority: u32,
+    /// Number of nodes in the subtree rooted at this node.
+    size: usize,
+    /// Whether the order of this subtree must still be reversed, pushed down lazily.
+    reversed: bool,
+    left: Link<T>,
+    right: Link<T>,
+}
+
+/// A sequence stored in an implicit treap.
+///
+/// A treap is a binary tree that is ordered like a search tree by position and like a heap by
+/// random priorities, which keeps its expected height logarithmic. The position of each element
+/// is never stored; it follows from the subtree sizes. Splitting the sequence at an index and
+/// concatenating two sequences takes O(log n) expected time, and every other operation is built
+/// from those two. Reversing a range only flips a flag on one subtree, which is pushed down to the
+/// children the next time the subtree is split or merged.
+///
+/// # Examples
+///
+/// ```rust
+/// use rust_algorithms::data_structures::TreapSequence;
+///
+/// let mut sequence = TreapSequence::new();
+/// for (i, c) in "abcdef".chars().enumerate() {
+///     sequence.insert(i, c);
+/// }
+/// sequence.reverse_range(1, 5);
+/// sequence.remove(0);
+///
+/// assert_eq!(sequence.get(0), Some(&'e'));
+/// assert_eq!(sequence.get(4), Some(&'f'));
+/// assert_eq!(sequence.len(), 5);
+/// ```
+pub struct TreapSequence<T> {
+    root: Link<T>,
+    rng: PCG32,
+}
+
+impl<T> TreapSequence<T> {
+    /// Creates an empty sequence.
+    pub fn new() -> Self {
+        TreapSequence {
+            root: None,
+            rng: PCG32::new_default(0x5eed),
+        }
+    }
+
+    /// Returns the number of elements in the sequence.
+    pub fn len(&self) -> usize {
+        size(&self.root)
+    }
+
+    /// Returns `true` if the sequence contains no elements.
+    pub fn is_empty(&self) -> bool {
+        self.root.is_none()
+    }
+
+    /// Returns the element at `index`, or `None` if `index` is out of bounds.
+    pub fn get(&self, mut index: usize) -> Option<&T> {
+        let mut current = &self.root;
+        // Pending reversals of all ancestors combine into one flag for the current subtree
+        let mut flipped = false;
+        while let Some(node) = current {
+            flipped ^= node.reversed;
+            let (left, right) = if flipped {
+                (&node.right, &node.left)
+            } else {
+                (&node.left, &node.right)
+            };
+            let left_size = size(left);
+            if index < left_size {
+                current = left;
+            } else if index == left_size {
+                return Some(&node.value);
+            } else {
+                index -= left_size + 1;
+                current = right;
+            }
+        }
+        None
+    }
+
+    /// Inserts `value` at `index`, shifting all later elements one position back.
+    ///
+    /// Panics if `index > len()`.
+    pub fn insert(&mut self, index: usize, value: T) {
+        assert!(index <= self.len(), "insertion index out of bounds");
+        let node = Box::new(TreapNode {
+            value,
+            priority: self.rng.get_u32(),
+            size: 1,
+            reversed: false,
+            left: None,
+            right: None,
+        });
+        let (left, right) = split(self.root.take(), index);
+        self.root = merge(merge(left, Some(node)), right);
+    }
+
+    /// Removes and returns the element at `index`, shifting all later elements one position
+    /// forward, or returns `None` if `index` is out of bounds.
+    pub fn remove(&mut self, index: usize) -> Option<T> {
+        if index >= self.len() {
+            return None;
+        }
+        let (left, rest) = split(self.root.take(), index);
+        let (middle, right) = split(rest, 1);
+        self.root = merge(left, right);
+        middle.map(|node| node.value)
+    }
+
+    /// Reverses the order of the elements in `lo..hi`.
+    ///
+    /// Panics if `lo > hi` or `hi > len()`.
+    pub fn reverse_range(&mut self, lo: usize, hi: usize) {
+        assert!(lo <= hi && hi <= self.len(), "range out of bounds");
+        let (left, rest) = split(self.root.take(), lo);
+        let (mut middle, right) = split(rest, hi - lo);
+        if let Some(node) = &mut middle {
+            node.reversed ^= true;
+        }
+        self.root = merge(merge(left, middle), right);
+    }
+}
+
+/// Returns the size of a possibly empty subtree.
+fn size<T>(tree: &Link<T>) -> usize {
+    tree.as_ref().map_or(0, |n| n.size)
+}
+
+impl<T> TreapNode<T> {
+    /// Applies a pending reversal by swapping the children and passing the flag on to them.
+    fn push_down(&mut self) {
+        if self.reversed {
+            mem::swap(&mut self.left, &mut self.right);
+            for child in self.left.iter_mut().chain(self.right.iter_mut()) {
+                child.reversed ^= true;
+            }
+            self.reversed = false;
+        }
+    }
+
+    /// Recomputes the `size` field.
+    fn update(&mut self) {
+        self.size = 1 + size(&self.left) + size(&self.right);
+    }
+}
+
+/// Splits a tree into its first `k` elements and the rest.
+fn split<T>(tree: Link<T>, k: usize) -> (Link<T>, Link<T>) {
+    match tree {
+        None => (None, None),
+        Some(mut node) => {
+            node.push_down();
+            let left_size = size(&node.left);
+            if k <= left_size {
+                let (left, right) = split(node.left.take(), k);
+                node.left = right;
+                node.update();
+                (left, Some(node))
+            } else {
+                let (left, right) = split(node.right.take(), k - left_size - 1);
+                node.right = left;
+                node.update();
+                (Some(node), right)
+            }
+        }
+    }
+}
+
+/// Concatenates two trees, keeping the node with the higher priority on top.
+fn merge<T>(left: Link<T>, right: Link<T>) -> Link<T> {
+    match (left, right) {
+        (None, tree) | (tree, None) => tree,
+        (Some(mut left), Some(mut right)) => {
+            if left.priority >= right.priority {
+                left.push_down();
+                left.right = merge(left.right.take(), Some(right));
+                left.update();
+                Some(left)
+            } else {
+                right.push_down();
+                right.left = merge(Some(left), right.left.take());
+                right.update();
+                Some(right)
+            }
+        }
+    }
+}
+
+impl<T> Default for TreapSequence<T> {
+    /// Creates an empty sequence.
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn to_vec<T: Clone>(sequence: &TreapSequence<T>) -> Vec<T> {
+        (0..sequence.len())
+            .map(|i| sequence.get(i).unwrap().clone())
+            .collect()
+    }
+
+    fn from_slice<T: Clone>(values: &[T]) -> TreapSequence<T> {
+        let mut sequence = TreapSequence::new();
+        for (i, value) in values.iter().enumerate() {
+            sequence.insert(i, value.clone());
+        }
+        sequence
+    }
+
+    #[test]
+    fn reverse_middle_range() {
+        let mut sequence = from_slice(&[0, 1, 2, 3, 4, 5, 6, 7, 8, 9]);
+        sequence.reverse_range(2, 7);
+        assert_eq!(to_vec(&sequence), vec![0, 1, 6, 5, 4, 3, 2, 7, 8, 9]);
+        sequence.reverse_range(0, 4);
+        assert_eq!(to_vec(&sequence), vec![5, 6, 1, 0, 4, 3, 2, 7, 8, 9]);
+        sequence.reverse_range(3, 3);
+        sequence.reverse_range(0, 10);
+        assert_eq!(to_vec(&sequence), vec![9, 8, 7, 2, 3, 4, 0, 1, 6, 5]);
+    }
+
+    #[test]
+    fn insert_and_remove_shift_indices() {
+        let mut sequence = from_slice(&['a', 'b', 'c', 'd']);
+        sequence.insert(0, 'x');
+        sequence.insert(3, 'y');
+        sequence.insert(6, 'z');
+        assert_eq!(to_vec(&sequence), vec!['x', 'a', 'b', 'y', 'c', 'd', 'z']);
+        assert_eq!(sequence.remove(3), Some('y'));
+        assert_eq!(sequence.remove(0), Some('x'));
+        assert_eq!(sequence.remove(4), Some('z'));
+        assert_eq!(sequence.remove(4), None);
+        assert_eq!(to_vec(&sequence), vec!['a', 'b', 'c', 'd']);
+        assert_eq!(sequence.get(4), None);
+    }
+
+    #[test]
+    fn matches_vec_model() {
+        let mut rng = PCG32::new_default(17);
+        let mut sequence = TreapSequence::new();
+        let mut model = Vec::new();
+        for step in 0..2000_u32 {
+            let len = model.len() as u32;
+            match rng.get_u32() % 4 {
+                0 | 1 => {
+                    let index = (rng.get_u32() % (len + 1)) as usize;
+                    sequence.insert(index, step);
+                    model.insert(index, step);
+                }
+                2 if len > 0 => {
+                    let index = (rng.get_u32() % len) as usize;
+                    assert_eq!(sequence.remove(index), Some(model.remove(index)));
+                }
+                _ => {
+                    let a = (rng.get_u32() % (len + 1)) as usize;
+                    let b = (rng.get_u32() % (len + 1)) as usize;
+                    let (lo, hi) = (a.min(b), a.max(b));
+                    sequence.reverse_range(lo, hi);
+                    model[lo..hi].reverse();
+                }
+            }
+            assert_eq!(sequence.len(), model.len());
+        }
+        assert_eq!(to_vec(&sequence), model);
+    }
+
+    #[test]
+    fn empty_sequence() {
+        let mut sequence: TreapSequence<i32> = TreapSequence::default();
+        assert!(sequence.is_empty());
+        assert_eq!(sequence.get(0), None);
+        assert_eq!(sequence.remove(0), None);
+        sequence.reverse_range(0, 0);
+        assert!(sequence.is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "out of bounds")]
+    fn insert_past_end() {
+        let mut sequence = from_slice(&[1, 2]);
+        sequence.insert(3, 0);
+    }
+}
@@ -0,0 +1,236 @@
+/// A wavelet tree over a sequence of symbols from `0..alphabet_size`.
+///
+/// Every node covers a range of symbols and splits it in half. For each position of the
+/// subsequence it sees, a node records whether that symbol belongs to the lower half (which is
+/// passed on to the left child) or the upper half (right child), stored as prefix counts of the
+/// lower half. This answers `access`, `rank` and `select` by walking down (and for `select`, back up)
+/// a path of `O(log alphabet_size)` nodes.
+///
+/// # Examples
+///
+/// ```rust
+/// use rust_algorithms::data_structures::WaveletTree;
+///
+/// let tree = WaveletTree::new(&[3, 1, 3, 2, 1, 3], 4);
+///
+/// assert_eq!(tree.access(3), 2);
+/// assert_eq!(tree.rank(3, 5), 2);
+/// assert_eq!(tree.select(3, 2), Some(2));
+/// ```
+pub struct WaveletTree {
+    root: Node,
+    len: usize,
+}
+
+struct Node {
+    /// The symbols `lo..hi` covered by this node.
+    lo: usize,
+    hi: usize,
+    /// `zeros[i]` is how many of the first `i` symbols of this node go to the left child.
+    zeros: Vec<usize>,
+    children: Option<Box<(Node, Node)>>,
+}
+
+impl WaveletTree {
+    /// Builds a wavelet tree over `sequence`, whose symbols must all be below `alphabet_size`.
+    pub fn new(sequence: &[usize], alphabet_size: usize) -> Self {
+        assert!(
+            sequence.iter().all(|&s| s < alphabet_size),
+            "symbols must be smaller than the alphabet size"
+        );
+        WaveletTree {
+            root: Node::new(sequence.to_vec(), 0, alphabet_size.max(1)),
+            len: sequence.len(),
+        }
+    }
+
+    /// Returns the symbol at position `i`.
+    pub fn access(&self, i: usize) -> usize {
+        assert!(i < self.len, "index out of bounds");
+        let mut node = &self.root;
+        let mut i = i;
+        while let Some(children) = &node.children {
+            if node.zeros[i + 1] > node.zeros[i] {
+                i = node.zeros[i];
+                node = &children.0;
+            } else {
+                i -= node.zeros[i];
+                node = &children.1;
+            }
+        }
+        node.lo
+    }
+
+    /// Counts the occurrences of `symbol` among the first `i` positions.
+    pub fn rank(&self, symbol: usize, i: usize) -> usize {
+        assert!(i <= self.len, "index out of bounds");
+        if symbol >= self.root.hi {
+            return 0;
+        }
+        let mut node = &self.root;
+        let mut i = i;
+        while let Some(children) = &node.children {
+            if symbol < node.mid() {
+                i = node.zeros[i];
+                node = &children.0;
+            } else {
+                i -= node.zeros[i];
+                node = &children.1;
+            }
+        }
+        i
+    }
+
+    /// Returns the position of the `k`-th occurrence of `symbol`, counting from 1, or `None` if
+    /// `symbol` occurs fewer than `k` times.
+    pub fn select(&self, symbol: usize, k: usize) -> Option<usize> {
+        if k == 0 || symbol >= self.root.hi {
+            return None;
+        }
+        // A root without children does not check the count itself
+        self.root.select(symbol, k - 1).filter(|&i| i < self.len)
+    }
+
+    /// Returns the length of the sequence.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Checks if the sequence is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+}
+
+impl Node {
+    fn new(sequence: Vec<usize>, lo: usize, hi: usize) -> Self {
+        if hi - lo == 1 {
+            return Node {
+                lo,
+                hi,
+                zeros: Vec::new(),
+                children: None,
+            };
+        }
+        let mid = lo + (hi - lo) / 2;
+        let mut zeros = Vec::with_capacity(sequence.len() + 1);
+        zeros.push(0);
+        for &s in &sequence {
+            zeros.push(zeros[zeros.len() - 1] + usize::from(s < mid));
+        }
+        let (left, right): (Vec<usize>, Vec<usize>) = sequence.into_iter().partition(|&s| s < mid);
+        Node {
+            lo,
+            hi,
+            zeros,
+            children: Some(Box::new((
+                Node::new(left, lo, mid),
+                Node::new(right, mid, hi),
+            ))),
+        }
+    }
+
+    fn mid(&self) -> usize {
+        self.lo + (self.hi - self.lo) / 2
+    }
+
+    /// Returns the position in this node of the `k`-th (from 0) occurrence of `symbol`.
+    fn select(&self, symbol: usize, k: usize) -> Option<usize> {
+        let children = match &self.children {
+            Some(children) => children,
+            // In a leaf every element is `symbol`; the parent knows how many there are
+            None => return Some(k),
+        };
+        let len = self.zeros.len() - 1;
+        let go_left = symbol < self.mid();
+        let child_pos = if go_left {
+            children.0.select(symbol, k)?
+        } else {
+            children.1.select(symbol, k)?
+        };
+        // Count of elements sent to the chosen child among the first `i` positions
+        let sent = |i: usize| {
+            if go_left {
+                self.zeros[i]
+            } else {
+                i - self.zeros[i]
+            }
+        };
+        if child_pos >= sent(len) {
+            return None;
+        }
+        // Find the smallest `i` with `sent(i + 1) == child_pos + 1`
+        let (mut lo, mut hi) = (0, len);
+        while lo < hi {
+            let mid = lo + (hi - lo) / 2;
+            if sent(mid + 1) > child_pos {
+                hi = mid;
+            } else {
+                lo = mid + 1;
+            }
+        }
+        Some(lo)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::PCG32;
+
+    fn check_against_brute_force(sequence: &[usize], alphabet_size: usize) {
+        let tree = WaveletTree::new(sequence, alphabet_size);
+        assert_eq!(tree.len(), sequence.len());
+        for (i, &s) in sequence.iter().enumerate() {
+            assert_eq!(tree.access(i), s);
+        }
+        for symbol in 0..alphabet_size + 1 {
+            for i in 0..=sequence.len() {
+                let count = sequence[..i].iter().filter(|&&s| s == symbol).count();
+                assert_eq!(tree.rank(symbol, i), count);
+            }
+            let positions: Vec<usize> = (0..sequence.len())
+                .filter(|&i| sequence[i] == symbol)
+                .collect();
+            for k in 1..=positions.len() {
+                assert_eq!(tree.select(symbol, k), Some(positions[k - 1]));
+            }
+            assert_eq!(tree.select(symbol, positions.len() + 1), None);
+            assert_eq!(tree.select(symbol, 0), None);
+        }
+    }
+
+    #[test]
+    fn small_sequence() {
+        let sequence = [3, 1, 3, 2, 1, 3];
+        let tree = WaveletTree::new(&sequence, 4);
+        assert_eq!(tree.access(0), 3);
+        assert_eq!(tree.access(4), 1);
+        assert_eq!(tree.rank(3, 6), 3);
+        assert_eq!(tree.rank(1, 4), 1);
+        assert_eq!(tree.rank(0, 6), 0);
+        assert_eq!(tree.select(1, 2), Some(4));
+        assert_eq!(tree.select(2, 1), Some(3));
+        assert_eq!(tree.select(0, 1), None);
+        check_against_brute_force(&sequence, 4);
+    }
+
+    #[test]
+    fn random_sequences() {
+        let mut rng = PCG32::new_default(17);
+        for &alphabet_size in [1, 2, 5, 16, 33].iter() {
+            let sequence: Vec<usize> = (0..80)
+                .map(|_| rng.get_u32() as usize % alphabet_size)
+                .collect();
+            check_against_brute_force(&sequence, alphabet_size);
+        }
+    }
+
+    #[test]
+    fn empty_sequence() {
+        let tree = WaveletTree::new(&[], 8);
+        assert!(tree.is_empty());
+        assert_eq!(tree.rank(3, 0), 0);
+        assert_eq!(tree.select(3, 1), None);
+    }
+}
@@ -81,6 +81,17 @@ impl<T: Clone + Copy + Ord> HuffmanDictionary<T> {
     /// The list of alphabet symbols and their respective frequency should
     /// be given as input
     pub fn new(alphabet: &[(T, u64)]) -> Self {
+        Self::try_new(alphabet).expect(
+            "the alphabet must be non-empty, with a total frequency and codes that fit in u64",
+        )
+    }
+
+    /// Like `new`, but returns `None` if the alphabet is empty, the frequencies
+    /// add up to more than `u64::MAX`, or some code would need more than 64 bits
+    pub fn try_new(alphabet: &[(T, u64)]) -> Option<Self> {
+        alphabet
+            .iter()
+            .try_fold(0u64, |total, &(_, freq)| total.checked_add(freq))?;
         let mut alph: BTreeMap<T, HuffmanValue> = BTreeMap::new();
         let mut queue: BinaryHeap<HuffmanNode<T>> = BinaryHeap::new();
         for (symbol, freq) in alphabet.iter() {
@@ -102,12 +113,15 @@ impl<T: Clone + Copy + Ord> HuffmanDictionary<T> {
                 frequency: sm_freq,
             });
         }
-        let root = queue.pop().unwrap();
+        let root = queue.pop()?;
+        if depth(&root) > 64 {
+            return None;
+        }
         root.get_alphabet(0, 0, &root, &mut alph);
-        HuffmanDictionary {
+        Some(HuffmanDictionary {
             alphabet: alph,
             root,
-        }
+        })
     }
     pub fn encode(&self, data: &[T]) -> HuffmanEncoding {
         let mut result = HuffmanEncoding::new();
@@ -116,6 +130,13 @@ impl<T: Clone + Copy + Ord> HuffmanDictionary<T> {
         result
     }
 }
+
+/// The number of edges on the longest path from `node` down to a leaf.
+fn depth<T>(node: &HuffmanNode<T>) -> u32 {
+    let child = |c: &Option<Box<HuffmanNode<T>>>| c.as_ref().map_or(0, |c| depth(c) + 1);
+    child(&node.left).max(child(&node.right))
+}
+
 pub struct HuffmanEncoding {
     pub num_bits: u64,
     pub data: Vec<u64>,
@@ -140,7 +161,7 @@ impl HuffmanEncoding {
         let val = data.value;
         *self.data.last_mut().unwrap() |= val.wrapping_shl(shift);
         if (shift + data.bits) >= 64 {
-            self.data.push(val.wrapping_shr(64 - shift));
+            self.data.push(val.checked_shr(64 - shift).unwrap_or(0));
         }
         self.num_bits += data.bits as u64;
     }
@@ -221,4 +242,23 @@ mod tests {
         let decoded = encoded.decode(&dict).unwrap();
         assert_eq!(decoded, bytes);
     }
+    #[test]
+    fn try_new() {
+        assert!(HuffmanDictionary::<u8>::try_new(&[]).is_none());
+        assert!(HuffmanDictionary::try_new(&[(0u8, u64::MAX), (1, 1)]).is_none());
+        // Fibonacci frequencies give the deepest possible tree, one level per symbol
+        let mut fib = vec![(0u8, 1u64), (1, 1)];
+        let mut i = 2;
+        while fib.len() < 65 {
+            fib.push((i, fib[i as usize - 1].1 + fib[i as usize - 2].1));
+            i += 1;
+        }
+        let dict = HuffmanDictionary::try_new(&fib).unwrap();
+        assert_eq!(dict.alphabet.values().map(|v| v.bits).max(), Some(64));
+        let encoded = dict.encode(&[0, 1, 0]);
+        assert_eq!(encoded.num_bits, 192);
+        assert_eq!(encoded.decode(&dict).unwrap(), vec![0, 1, 0]);
+        fib.push((i, fib[64].1 + fib[63].1));
+        assert!(HuffmanDictionary::try_new(&fib).is_none());
+    }
 }
@@ -0,0 +1,162 @@
+/// A directed graph as an adjacency list: `graph[u]` holds the vertices that edges from `u` point
+/// to. Vertices are numbered from 0 to `graph.len() - 1`.
+type Graph = [Vec<usize>];
+
+// Enumerates every topological ordering of the graph, that is every ordering of the vertices in
+// which each edge points from an earlier vertex to a later one.
+//
+// The orderings are built by backtracking: any vertex without remaining incoming edges may come
+// next, so each one in turn is appended, its outgoing edges are removed while the remaining
+// vertices are ordered, and they are restored afterwards. Every choice leads to at least one
+// complete ordering, so the time is proportional to the size of the output. That output grows
+// factorially, though: a graph without edges has `n!` orderings, so this is only practical for
+// small or nearly linear graphs.
+//
+// Returns the orderings in lexicographic order, a single empty ordering for an empty graph, and no
+// orderings if the graph has a cycle.
+pub fn all_topological_sorts(graph: &Graph) -> Vec<Vec<usize>> {
+    let n = graph.len();
+    let mut in_degree = vec![0; n];
+    for edges in graph {
+        for &v in edges {
+            in_degree[v] += 1;
+        }
+    }
+    let mut orderings = Vec::new();
+    if is_acyclic(graph, &in_degree) {
+        let mut placed = vec![false; n];
+        let mut order = Vec::with_capacity(n);
+        extend(
+            graph,
+            &mut in_degree,
+            &mut placed,
+            &mut order,
+            &mut orderings,
+        );
+    }
+    orderings
+}
+
+// Appends every completion of the partial ordering `order` to `orderings`.
+fn extend(
+    graph: &Graph,
+    in_degree: &mut [usize],
+    placed: &mut [bool],
+    order: &mut Vec<usize>,
+    orderings: &mut Vec<Vec<usize>>,
+) {
+    if order.len() == graph.len() {
+        orderings.push(order.clone());
+        return;
+    }
+    for u in 0..graph.len() {
+        if placed[u] || in_degree[u] > 0 {
+            continue;
+        }
+        placed[u] = true;
+        order.push(u);
+        graph[u].iter().for_each(|&v| in_degree[v] -= 1);
+        extend(graph, in_degree, placed, order, orderings);
+        graph[u].iter().for_each(|&v| in_degree[v] += 1);
+        order.pop();
+        placed[u] = false;
+    }
+}
+
+// Checks with Kahn's algorithm that every vertex can be removed once its incoming edges are gone.
+fn is_acyclic(graph: &Graph, in_degree: &[usize]) -> bool {
+    let mut in_degree = in_degree.to_vec();
+    let mut ready: Vec<usize> = (0..graph.len()).filter(|&u| in_degree[u] == 0).collect();
+    let mut removed = 0;
+    while let Some(u) = ready.pop() {
+        removed += 1;
+        for &v in &graph[u] {
+            in_degree[v] -= 1;
+            if in_degree[v] == 0 {
+                ready.push(v);
+            }
+        }
+    }
+    removed == graph.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::all_topological_sorts;
+    use crate::math::PCG32;
+
+    #[test]
+    fn two_chains() {
+        // 0 -> 1 and 2 -> 3 interleave in every way that keeps each chain in order
+        let graph = vec![vec![1], vec![], vec![3], vec![]];
+        assert_eq!(
+            all_topological_sorts(&graph),
+            vec![
+                vec![0, 1, 2, 3],
+                vec![0, 2, 1, 3],
+                vec![0, 2, 3, 1],
+                vec![2, 0, 1, 3],
+                vec![2, 0, 3, 1],
+                vec![2, 3, 0, 1],
+            ]
+        );
+    }
+
+    #[test]
+    fn diamond_and_chain() {
+        let diamond = vec![vec![1, 2], vec![3], vec![3], vec![]];
+        assert_eq!(
+            all_topological_sorts(&diamond),
+            vec![vec![0, 1, 2, 3], vec![0, 2, 1, 3]]
+        );
+        // Parallel edges do not add orderings
+        let chain = vec![vec![2], vec![], vec![1, 1]];
+        assert_eq!(all_topological_sorts(&chain), vec![vec![0, 2, 1]]);
+    }
+
+    #[test]
+    fn edgeless_and_empty() {
+        assert_eq!(all_topological_sorts(&vec![vec![]; 4]).len(), 24);
+        assert_eq!(all_topological_sorts(&[]), vec![Vec::<usize>::new()]);
+    }
+
+    #[test]
+    fn cycles() {
+        assert!(all_topological_sorts(&[vec![1], vec![2], vec![0]]).is_empty());
+        assert!(all_topological_sorts(&[vec![], vec![1]]).is_empty());
+        // A cycle away from the other vertices still rules out every ordering
+        assert!(all_topological_sorts(&[vec![1], vec![], vec![3], vec![2]]).is_empty());
+    }
+
+    #[test]
+    fn matches_brute_force() {
+        let mut rng = PCG32::new_default(272);
+        let n = 6;
+        for _ in 0..10 {
+            // Edges only go from smaller to larger vertices, so the graph is acyclic
+            let graph: Vec<Vec<usize>> = (0..n)
+                .map(|u| {
+                    (u + 1..n)
+                        .filter(|_| rng.get_u32().is_multiple_of(4))
+                        .collect()
+                })
+                .collect();
+            let mut expected = Vec::new();
+            for code in 0..6usize.pow(6) {
+                let order: Vec<usize> = (0..n)
+                    .map(|i| code / 6usize.pow(5 - i as u32) % 6)
+                    .collect();
+                let mut position = vec![usize::MAX; n];
+                for (i, &u) in order.iter().enumerate() {
+                    position[u] = i;
+                }
+                let valid = position.iter().all(|&p| p != usize::MAX)
+                    && (0..n).all(|u| graph[u].iter().all(|&v| position[u] < position[v]));
+                if valid {
+                    expected.push(order);
+                }
+            }
+            assert_eq!(all_topological_sorts(&graph), expected);
+        }
+    }
+}
@@ -0,0 +1,144 @@
+/// An undirected graph as an adjacency list: `graph[u]` holds the neighbours of vertex `u`.
+/// Every edge is expected in the lists of both of its endpoints.
+type Graph = [Vec<usize>];
+
+// Computes a vertex cover at most twice as large as a minimum one.
+//
+// Scans the edges and, whenever neither endpoint of an edge is covered yet, adds both endpoints.
+// The picked edges share no endpoints, and any cover needs at least one endpoint of each of them,
+// which gives the factor of two. Runs in O(V + E).
+//
+// Returns the vertices of the cover in ascending order.
+pub fn vertex_cover_2approx(graph: &Graph) -> Vec<usize> {
+    let mut covered = vec![false; graph.len()];
+    for (u, neighbours) in graph.iter().enumerate() {
+        for &v in neighbours {
+            if !covered[u] && !covered[v] {
+                covered[u] = true;
+                covered[v] = true;
+            }
+        }
+    }
+    (0..graph.len()).filter(|&u| covered[u]).collect()
+}
+
+// Greedily covers the elements `0..universe` with the given sets.
+//
+// Repeatedly picks the set containing the most uncovered elements, preferring the lowest index on
+// ties, which uses at most H(n) ~ ln(n) times as many sets as an optimal cover. Elements contained
+// in none of the sets remain uncovered.
+//
+// Returns the indices of the chosen sets in the order they were picked. Panics if a set contains an
+// element outside of `0..universe`.
+pub fn greedy_set_cover(universe: usize, sets: &[Vec<usize>]) -> Vec<usize> {
+    assert!(
+        sets.iter().flatten().all(|&x| x < universe),
+        "set elements must lie in 0..universe"
+    );
+    let mut covered = vec![false; universe];
+    let mut chosen = Vec::new();
+    loop {
+        let best = sets
+            .iter()
+            .enumerate()
+            .map(|(i, set)| (set.iter().filter(|&&x| !covered[x]).count(), i))
+            .max_by_key(|&(gain, i)| (gain, std::cmp::Reverse(i)));
+        match best {
+            Some((gain, i)) if gain > 0 => {
+                for &x in &sets[i] {
+                    covered[x] = true;
+                }
+                chosen.push(i);
+            }
+            _ => return chosen,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn undirected(n: usize, edges: &[(usize, usize)]) -> Vec<Vec<usize>> {
+        let mut graph = vec![vec![]; n];
+        for &(u, v) in edges {
+            graph[u].push(v);
+            graph[v].push(u);
+        }
+        graph
+    }
+
+    #[test]
+    fn vertex_cover_covers_all_edges() {
+        let edges = [
+            (0, 1),
+            (0, 2),
+            (1, 3),
+            (2, 3),
+            (3, 4),
+            (4, 5),
+            (5, 6),
+            (2, 6),
+        ];
+        let graph = undirected(7, &edges);
+        let cover = vertex_cover_2approx(&graph);
+        for &(u, v) in edges.iter() {
+            assert!(cover.contains(&u) || cover.contains(&v));
+        }
+        // A minimum cover of this graph has 4 vertices, e.g. {0, 3, 2, 5}
+        assert!(cover.len() <= 8);
+        assert!(cover.windows(2).all(|w| w[0] < w[1]));
+    }
+
+    #[test]
+    fn vertex_cover_of_star_and_empty() {
+        let graph = undirected(5, &[(0, 1), (0, 2), (0, 3), (0, 4)]);
+        let cover = vertex_cover_2approx(&graph);
+        assert_eq!(cover.len(), 2);
+        assert!(cover.contains(&0));
+
+        assert!(vertex_cover_2approx(&undirected(3, &[])).is_empty());
+    }
+
+    #[test]
+    fn set_cover() {
+        let sets = vec![
+            vec![0, 1, 2, 3, 4, 5],
+            vec![0, 1, 2],
+            vec![3, 4],
+            vec![5, 6, 7],
+            vec![6, 7, 8, 9],
+        ];
+        let cover = greedy_set_cover(10, &sets);
+        assert_eq!(cover, vec![0, 4]);
+    }
+
+    #[test]
+    fn set_cover_is_complete() {
+        let sets = vec![
+            vec![0, 3],
+            vec![1, 4],
+            vec![2, 5],
+            vec![0, 1, 2],
+            vec![3, 4, 5],
+        ];
+        let cover = greedy_set_cover(6, &sets);
+        let mut covered: Vec<usize> = cover.iter().flat_map(|&i| sets[i].clone()).collect();
+        covered.sort_unstable();
+        covered.dedup();
+        assert_eq!(covered, (0..6).collect::<Vec<_>>());
+        assert_eq!(cover, vec![3, 4]);
+    }
+
+    #[test]
+    fn set_cover_with_uncoverable_element() {
+        assert_eq!(greedy_set_cover(3, &[vec![0], vec![0, 1]]), vec![1]);
+        assert!(greedy_set_cover(0, &[]).is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "set elements must lie in 0..universe")]
+    fn set_cover_element_outside_universe() {
+        greedy_set_cover(3, &[vec![0, 1], vec![2, 3]]);
+    }
+}
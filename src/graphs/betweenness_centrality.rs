@@ -0,0 +1,129 @@
+use super::WeightedGraph;
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+// Computes the betweenness centrality of every vertex with Brandes' algorithm.
+//
+// The betweenness of `v` sums, over all ordered pairs of other vertices `s` and `t`, the
+// fraction of shortest paths from `s` to `t` that pass through `v`. From every source, Dijkstra
+// counts the shortest paths to each vertex and records their predecessors; walking the vertices
+// back from the farthest, each one then passes its dependency on to its predecessors in
+// proportion to their path counts. This takes O(V * E * log V) instead of considering every
+// pair of vertices separately. For an unweighted graph, give every edge weight 1.
+//
+// The scores are normalized by dividing by `(n - 1) * (n - 2)`, the number of ordered pairs of
+// other vertices, so they lie between 0 and 1, where 1 means every shortest path between other
+// vertices passes through the vertex. An undirected graph must list every edge in both
+// directions, and then gets the same normalized scores as with unordered pairs. Graphs with fewer
+// than three vertices score all zeros. Edge weights must be positive.
+pub fn betweenness_centrality(graph: &WeightedGraph<u64>) -> Vec<f64> {
+    let n = graph.len();
+    let mut centrality = vec![0.0; n];
+    if n < 3 {
+        return centrality;
+    }
+    for source in 0..n {
+        // Shortest path counts, predecessors on shortest paths, and vertices by distance
+        let mut dist = vec![u64::MAX; n];
+        let mut paths = vec![0.0; n];
+        let mut predecessors: Vec<Vec<usize>> = vec![Vec::new(); n];
+        let mut order = Vec::with_capacity(n);
+        let mut heap = BinaryHeap::new();
+        dist[source] = 0;
+        paths[source] = 1.0;
+        heap.push(Reverse((0, source)));
+        while let Some(Reverse((d, u))) = heap.pop() {
+            if d > dist[u] {
+                continue;
+            }
+            order.push(u);
+            for &(v, weight) in &graph[u] {
+                let alt = d + weight;
+                if alt < dist[v] {
+                    dist[v] = alt;
+                    paths[v] = paths[u];
+                    predecessors[v].clear();
+                    predecessors[v].push(u);
+                    heap.push(Reverse((alt, v)));
+                } else if alt == dist[v] {
+                    paths[v] += paths[u];
+                    predecessors[v].push(u);
+                }
+            }
+        }
+
+        let mut dependency = vec![0.0; n];
+        for &w in order.iter().rev() {
+            for &v in &predecessors[w] {
+                dependency[v] += paths[v] / paths[w] * (1.0 + dependency[w]);
+            }
+            if w != source {
+                centrality[w] += dependency[w];
+            }
+        }
+    }
+    let pairs = ((n - 1) * (n - 2)) as f64;
+    centrality.iter_mut().for_each(|c| *c /= pairs);
+    centrality
+}
+
+#[cfg(test)]
+mod tests {
+    use super::betweenness_centrality;
+
+    fn undirected(n: usize, edges: &[(usize, usize, u64)]) -> Vec<Vec<(usize, u64)>> {
+        let mut graph = vec![Vec::new(); n];
+        for &(u, v, weight) in edges {
+            graph[u].push((v, weight));
+            graph[v].push((u, weight));
+        }
+        graph
+    }
+
+    fn assert_close(actual: &[f64], expected: &[f64]) {
+        assert_eq!(actual.len(), expected.len());
+        for (a, e) in actual.iter().zip(expected) {
+            assert!((a - e).abs() < 1e-12, "{:?} != {:?}", actual, expected);
+        }
+    }
+
+    #[test]
+    fn path_graph() {
+        // 0 - 1 - 2 - 3 - 4: the middle lies between 2 * 2 pairs, its neighbours between 3
+        let graph = undirected(5, &[(0, 1, 1), (1, 2, 1), (2, 3, 1), (3, 4, 1)]);
+        let scores = betweenness_centrality(&graph);
+        assert_close(&scores, &[0.0, 0.5, 4.0 / 6.0, 0.5, 0.0]);
+    }
+
+    #[test]
+    fn complete_and_star_graphs() {
+        let edges: Vec<_> = (0..5)
+            .flat_map(|u| (u + 1..5).map(move |v| (u, v, 1)))
+            .collect();
+        assert_close(&betweenness_centrality(&undirected(5, &edges)), &[0.0; 5]);
+
+        // Every path between two leaves passes through the centre
+        let star = undirected(4, &[(0, 1, 1), (0, 2, 1), (0, 3, 1)]);
+        assert_close(&betweenness_centrality(&star), &[1.0, 0.0, 0.0, 0.0]);
+    }
+
+    #[test]
+    fn weighted_ties() {
+        // 0 - 1 - 2 is cheaper than 0 - 3 - 2, and 1 reaches 3 equally fast through 0 or 2
+        let graph = undirected(4, &[(0, 1, 1), (1, 2, 1), (0, 3, 5), (3, 2, 5)]);
+        let scores = betweenness_centrality(&graph);
+        assert_close(&scores, &[1.0 / 6.0, 1.0 / 3.0, 1.0 / 6.0, 0.0]);
+    }
+
+    #[test]
+    fn directed_and_small_graphs() {
+        // Only 0 -> 1 -> 2 passes through 1
+        let graph = vec![vec![(1, 1)], vec![(2, 1)], vec![]];
+        assert_close(&betweenness_centrality(&graph), &[0.0, 0.5, 0.0]);
+        assert!(betweenness_centrality(&[]).is_empty());
+        assert_close(
+            &betweenness_centrality(&[vec![(1, 1)], vec![]]),
+            &[0.0, 0.0],
+        );
+    }
+}
@@ -0,0 +1,121 @@
+use super::WeightedGraph;
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashSet};
+
+// Finds the shortest path from `source` to `target` with Dijkstra's algorithm, never entering a
+// vertex in `banned_vertices` and never taking an edge `(u, v)` in `banned_edges`. Banning an
+// edge only bans that direction, and bans every parallel edge from `u` to `v`.
+//
+// Returns the cost and vertices of the path, or `None` if every path to `target` is blocked,
+// including when `source` or `target` itself is banned.
+pub fn dijkstra_avoiding(
+    graph: &WeightedGraph<u64>,
+    source: usize,
+    target: usize,
+    banned_vertices: &HashSet<usize>,
+    banned_edges: &HashSet<(usize, usize)>,
+) -> Option<(u64, Vec<usize>)> {
+    if banned_vertices.contains(&source) {
+        return None;
+    }
+    let mut dist = vec![u64::MAX; graph.len()];
+    let mut prev = vec![usize::MAX; graph.len()];
+    let mut heap = BinaryHeap::new();
+    dist[source] = 0;
+    heap.push(Reverse((0, source)));
+    while let Some(Reverse((d, u))) = heap.pop() {
+        if d > dist[u] {
+            continue;
+        }
+        if u == target {
+            let mut path = vec![target];
+            while *path.last().unwrap() != source {
+                path.push(prev[*path.last().unwrap()]);
+            }
+            path.reverse();
+            return Some((d, path));
+        }
+        for &(v, weight) in &graph[u] {
+            if banned_vertices.contains(&v) || banned_edges.contains(&(u, v)) {
+                continue;
+            }
+            if d + weight < dist[v] {
+                dist[v] = d + weight;
+                prev[v] = u;
+                heap.push(Reverse((d + weight, v)));
+            }
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::dijkstra_avoiding;
+    use std::collections::HashSet;
+
+    // A direct edge 0 -> 3 of weight 2, and two longer detours through 1 or 2
+    fn example() -> Vec<Vec<(usize, u64)>> {
+        vec![
+            vec![(3, 2), (1, 1), (2, 2)],
+            vec![(3, 3)],
+            vec![(3, 4)],
+            vec![],
+        ]
+    }
+
+    #[test]
+    fn nothing_banned() {
+        let none = HashSet::new();
+        assert_eq!(
+            dijkstra_avoiding(&example(), 0, 3, &none, &HashSet::new()),
+            Some((2, vec![0, 3]))
+        );
+        assert_eq!(
+            dijkstra_avoiding(&example(), 1, 1, &none, &HashSet::new()),
+            Some((0, vec![1]))
+        );
+    }
+
+    #[test]
+    fn detours() {
+        let graph = example();
+        let banned_edges: HashSet<_> = vec![(0, 3)].into_iter().collect();
+        assert_eq!(
+            dijkstra_avoiding(&graph, 0, 3, &HashSet::new(), &banned_edges),
+            Some((4, vec![0, 1, 3]))
+        );
+        let banned_vertices: HashSet<_> = vec![1].into_iter().collect();
+        assert_eq!(
+            dijkstra_avoiding(&graph, 0, 3, &banned_vertices, &banned_edges),
+            Some((6, vec![0, 2, 3]))
+        );
+        // Banning the reverse direction has no effect
+        let reversed: HashSet<_> = vec![(3, 0)].into_iter().collect();
+        assert_eq!(
+            dijkstra_avoiding(&graph, 0, 3, &HashSet::new(), &reversed),
+            Some((2, vec![0, 3]))
+        );
+    }
+
+    #[test]
+    fn disconnected_by_bans() {
+        let graph = example();
+        let banned_edges: HashSet<_> = vec![(0, 3), (2, 3)].into_iter().collect();
+        let banned_vertices: HashSet<_> = vec![1].into_iter().collect();
+        assert_eq!(
+            dijkstra_avoiding(&graph, 0, 3, &banned_vertices, &banned_edges),
+            None
+        );
+        let banned_target: HashSet<_> = vec![3].into_iter().collect();
+        assert_eq!(
+            dijkstra_avoiding(&graph, 0, 3, &banned_target, &HashSet::new()),
+            None
+        );
+        let banned_source: HashSet<_> = vec![0].into_iter().collect();
+        assert_eq!(
+            dijkstra_avoiding(&graph, 0, 3, &banned_source, &HashSet::new()),
+            None
+        );
+    }
+}
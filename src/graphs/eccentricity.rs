@@ -0,0 +1,115 @@
+use std::collections::VecDeque;
+
+/// An unweighted graph as an adjacency list: `graph[u]` holds the neighbours of vertex `u`.
+type Graph = [Vec<usize>];
+
+// Computes the eccentricity of every vertex, the largest distance from it to any other vertex,
+// with a breadth-first search from each vertex. Returns `None` if the graph is disconnected.
+fn eccentricities(graph: &Graph) -> Option<Vec<u64>> {
+    let n = graph.len();
+    let mut result = Vec::with_capacity(n);
+    let mut distance = vec![u64::MAX; n];
+    let mut queue = VecDeque::new();
+    for source in 0..n {
+        distance.iter_mut().for_each(|d| *d = u64::MAX);
+        distance[source] = 0;
+        queue.push_back(source);
+        let mut farthest = 0;
+        let mut reached = 1;
+        while let Some(u) = queue.pop_front() {
+            farthest = distance[u];
+            for &v in &graph[u] {
+                if distance[v] == u64::MAX {
+                    distance[v] = distance[u] + 1;
+                    reached += 1;
+                    queue.push_back(v);
+                }
+            }
+        }
+        if reached < n {
+            return None;
+        }
+        result.push(farthest);
+    }
+    Some(result)
+}
+
+// Returns the diameter of the graph, the longest shortest path between any two vertices.
+//
+// Runs a breadth-first search from every vertex, which takes O(V * (V + E)). Returns `None` for an
+// empty or disconnected graph.
+pub fn diameter(graph: &Graph) -> Option<u64> {
+    eccentricities(graph)?.into_iter().max()
+}
+
+// Returns the center of the graph, the vertices whose largest distance to any other vertex is
+// smallest, in ascending order.
+//
+// Runs a breadth-first search from every vertex, which takes O(V * (V + E)). Returns an empty
+// vector for an empty or disconnected graph.
+pub fn center(graph: &Graph) -> Vec<usize> {
+    let eccentricities = match eccentricities(graph) {
+        Some(e) => e,
+        None => return Vec::new(),
+    };
+    let radius = eccentricities.iter().copied().min().unwrap_or(0);
+    (0..graph.len())
+        .filter(|&u| eccentricities[u] == radius)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn undirected(n: usize, edges: &[(usize, usize)]) -> Vec<Vec<usize>> {
+        let mut graph = vec![Vec::new(); n];
+        for &(u, v) in edges {
+            graph[u].push(v);
+            graph[v].push(u);
+        }
+        graph
+    }
+
+    fn path(n: usize) -> Vec<Vec<usize>> {
+        let edges: Vec<_> = (1..n).map(|v| (v - 1, v)).collect();
+        undirected(n, &edges)
+    }
+
+    #[test]
+    fn path_graph() {
+        assert_eq!(diameter(&path(5)), Some(4));
+        assert_eq!(center(&path(5)), vec![2]);
+        assert_eq!(diameter(&path(6)), Some(5));
+        assert_eq!(center(&path(6)), vec![2, 3]);
+    }
+
+    #[test]
+    fn star_graph() {
+        let star = undirected(6, &[(3, 0), (3, 1), (3, 2), (3, 4), (3, 5)]);
+        assert_eq!(diameter(&star), Some(2));
+        assert_eq!(center(&star), vec![3]);
+    }
+
+    #[test]
+    fn cycle_graph() {
+        let cycle = undirected(6, &[(0, 1), (1, 2), (2, 3), (3, 4), (4, 5), (5, 0)]);
+        assert_eq!(diameter(&cycle), Some(3));
+        assert_eq!(center(&cycle), vec![0, 1, 2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn single_vertex() {
+        assert_eq!(diameter(&path(1)), Some(0));
+        assert_eq!(center(&path(1)), vec![0]);
+    }
+
+    #[test]
+    fn empty_or_disconnected() {
+        assert_eq!(diameter(&[]), None);
+        assert!(center(&[]).is_empty());
+        let graph = undirected(4, &[(0, 1), (2, 3)]);
+        assert_eq!(diameter(&graph), None);
+        assert!(center(&graph).is_empty());
+    }
+}
@@ -0,0 +1,172 @@
+use super::WeightedGraph;
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NegativeCycle;
+
+impl fmt::Display for NegativeCycle {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "the graph contains a negative weight cycle")
+    }
+}
+
+// Computes the shortest distances between all pairs of vertices with Johnson's algorithm.
+//
+// Edge weights may be negative. A Bellman-Ford pass from a virtual vertex connected to every vertex
+// yields potentials `h` such that `w(u, v) + h[u] - h[v]` is never negative, so Dijkstra can then
+// be run from every vertex. This takes O(V * E * log V), which beats Floyd-Warshall on sparse
+// graphs.
+//
+// Returns `dist[u][v]`, which is `None` when `v` is unreachable from `u`, or `NegativeCycle` if
+// some cycle has a negative total weight.
+pub fn johnson(graph: &WeightedGraph<i64>) -> Result<Vec<Vec<Option<i64>>>, NegativeCycle> {
+    let potential = potentials(graph)?;
+    Ok((0..graph.len())
+        .map(|source| {
+            dijkstra_reweighted(graph, &potential, source)
+                .into_iter()
+                .enumerate()
+                .map(|(target, dist)| dist.map(|d| d - potential[source] + potential[target]))
+                .collect()
+        })
+        .collect())
+}
+
+/// Runs Bellman-Ford from a virtual vertex with a 0-weight edge to every vertex.
+fn potentials(graph: &WeightedGraph<i64>) -> Result<Vec<i64>, NegativeCycle> {
+    let mut potential = vec![0; graph.len()];
+    // The virtual vertex makes V + 1 vertices, so V rounds of relaxation suffice
+    for _ in 0..graph.len() {
+        let mut changed = false;
+        for (u, edges) in graph.iter().enumerate() {
+            for &(v, weight) in edges {
+                if potential[u] + weight < potential[v] {
+                    potential[v] = potential[u] + weight;
+                    changed = true;
+                }
+            }
+        }
+        if !changed {
+            return Ok(potential);
+        }
+    }
+    for (u, edges) in graph.iter().enumerate() {
+        for &(v, weight) in edges {
+            if potential[u] + weight < potential[v] {
+                return Err(NegativeCycle);
+            }
+        }
+    }
+    Ok(potential)
+}
+
+/// Dijkstra from `source` using the non-negative weights `w(u, v) + h[u] - h[v]`.
+fn dijkstra_reweighted(
+    graph: &WeightedGraph<i64>,
+    potential: &[i64],
+    source: usize,
+) -> Vec<Option<i64>> {
+    let mut dist = vec![None; graph.len()];
+    let mut prio = BinaryHeap::new();
+    dist[source] = Some(0);
+    prio.push(Reverse((0, source)));
+
+    while let Some(Reverse((d, u))) = prio.pop() {
+        if dist[u] != Some(d) {
+            continue;
+        }
+        for &(v, weight) in &graph[u] {
+            let next = d + weight + potential[u] - potential[v];
+            if dist[v].is_none_or(|current| next < current) {
+                dist[v] = Some(next);
+                prio.push(Reverse((next, v)));
+            }
+        }
+    }
+    dist
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{johnson, NegativeCycle, WeightedGraph};
+    use crate::math::PCG32;
+
+    fn floyd_warshall(graph: &WeightedGraph<i64>) -> Vec<Vec<Option<i64>>> {
+        let n = graph.len();
+        let mut dist = vec![vec![None; n]; n];
+        for (u, edges) in graph.iter().enumerate() {
+            dist[u][u] = Some(0);
+            for &(v, weight) in edges {
+                if dist[u][v].is_none_or(|d| weight < d) {
+                    dist[u][v] = Some(weight);
+                }
+            }
+        }
+        for k in 0..n {
+            for i in 0..n {
+                for j in 0..n {
+                    if let (Some(a), Some(b)) = (dist[i][k], dist[k][j]) {
+                        if dist[i][j].is_none_or(|d| a + b < d) {
+                            dist[i][j] = Some(a + b);
+                        }
+                    }
+                }
+            }
+        }
+        dist
+    }
+
+    #[test]
+    fn negative_edges() {
+        let graph: Vec<Vec<(usize, i64)>> = vec![
+            vec![(1, 3), (2, 8), (4, -4)],
+            vec![(3, 1), (4, 7)],
+            vec![(1, 4)],
+            vec![(0, 2), (2, -5)],
+            vec![(3, 6)],
+        ];
+        let dist = johnson(&graph).unwrap();
+        assert_eq!(dist, floyd_warshall(&graph));
+        assert_eq!(dist[0], vec![Some(0), Some(1), Some(-3), Some(2), Some(-4)]);
+    }
+
+    #[test]
+    fn unreachable_vertices() {
+        let graph: Vec<Vec<(usize, i64)>> = vec![vec![(1, -2)], vec![], vec![(0, 5)]];
+        let dist = johnson(&graph).unwrap();
+        assert_eq!(dist[0], vec![Some(0), Some(-2), None]);
+        assert_eq!(dist[1], vec![None, Some(0), None]);
+        assert_eq!(dist[2], vec![Some(5), Some(3), Some(0)]);
+    }
+
+    #[test]
+    fn random_dags() {
+        let mut rng = PCG32::new_default(7);
+        for _ in 0..20 {
+            let n = 12;
+            let mut graph: Vec<Vec<(usize, i64)>> = vec![vec![]; n];
+            // Edges only go from lower to higher vertices, so negative weights cannot form cycles
+            for (u, edges) in graph.iter_mut().enumerate() {
+                for v in u + 1..n {
+                    if rng.get_u32().is_multiple_of(3) {
+                        edges.push((v, (rng.get_u32() % 21) as i64 - 10));
+                    }
+                }
+            }
+            assert_eq!(johnson(&graph).unwrap(), floyd_warshall(&graph));
+        }
+    }
+
+    #[test]
+    fn negative_cycle() {
+        let graph: Vec<Vec<(usize, i64)>> = vec![vec![(1, 1)], vec![(2, -3)], vec![(0, 1)], vec![]];
+        assert_eq!(johnson(&graph), Err(NegativeCycle));
+    }
+
+    #[test]
+    fn empty() {
+        assert_eq!(johnson(&[]), Ok(vec![]));
+    }
+}
@@ -0,0 +1,136 @@
+use super::{dijkstra_avoiding, WeightedGraph};
+use std::cmp::Reverse;
+use std::collections::{BTreeSet, BinaryHeap, HashSet};
+
+// Finds the `k` shortest loopless paths from `source` to `target` with Yen's algorithm.
+//
+// The shortest path is found with Dijkstra. Every further path deviates from one of the paths
+// found so far at some spur vertex: it shares the root of that path up to the spur vertex, then
+// takes a spur path to `target` that avoids the edges already used by paths sharing the same root,
+// as well as the vertices of the root itself. The cheapest such candidate becomes the next path.
+// This takes O(k * V * (E + V) * log V).
+//
+// Returns up to `k` distinct paths as `(cost, vertices)` pairs, sorted by cost. Fewer paths are
+// returned when fewer exist, and none when `target` is unreachable.
+pub fn k_shortest_paths(
+    graph: &WeightedGraph<u64>,
+    source: usize,
+    target: usize,
+    k: usize,
+) -> Vec<(u64, Vec<usize>)> {
+    let mut paths: Vec<(u64, Vec<usize>)> = Vec::new();
+    if k == 0 {
+        return paths;
+    }
+    match dijkstra_avoiding(graph, source, target, &HashSet::new(), &HashSet::new()) {
+        Some(path) => paths.push(path),
+        None => return paths,
+    }
+
+    let mut candidates = BinaryHeap::new();
+    let mut seen = BTreeSet::new();
+    while paths.len() < k {
+        let (_, last) = &paths[paths.len() - 1];
+        let mut root_cost = 0;
+        for i in 0..last.len() - 1 {
+            let root = &last[..=i];
+            // Edges leaving the spur vertex that were taken by a known path with the same root
+            let banned_edges: HashSet<_> = paths
+                .iter()
+                .filter(|(_, path)| path.len() > i + 1 && path[..=i] == *root)
+                .map(|(_, path)| (path[i], path[i + 1]))
+                .collect();
+            let banned_vertices: HashSet<_> = root[..i].iter().copied().collect();
+
+            if let Some((spur_cost, spur)) =
+                dijkstra_avoiding(graph, last[i], target, &banned_vertices, &banned_edges)
+            {
+                let path: Vec<usize> = root[..i].iter().copied().chain(spur).collect();
+                if seen.insert(path.clone()) {
+                    candidates.push(Reverse((root_cost + spur_cost, path)));
+                }
+            }
+            root_cost += edge_weight(graph, last[i], last[i + 1]);
+        }
+
+        match candidates.pop() {
+            Some(Reverse(path)) => paths.push(path),
+            None => break,
+        }
+    }
+    paths
+}
+
+// Returns the weight of the cheapest edge from `u` to `v`.
+fn edge_weight(graph: &WeightedGraph<u64>, u: usize, v: usize) -> u64 {
+    graph[u]
+        .iter()
+        .filter(|&&(w, _)| w == v)
+        .map(|&(_, weight)| weight)
+        .min()
+        .unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::k_shortest_paths;
+
+    // The example from Wikipedia, with vertices C, D, E, F, G, H numbered from 0 to 5
+    fn example() -> Vec<Vec<(usize, u64)>> {
+        vec![
+            vec![(1, 3), (2, 2)],
+            vec![(3, 4)],
+            vec![(1, 1), (3, 2), (4, 3)],
+            vec![(4, 2), (5, 1)],
+            vec![(5, 2)],
+            vec![],
+        ]
+    }
+
+    #[test]
+    fn shortest_routes() {
+        let paths = k_shortest_paths(&example(), 0, 5, 3);
+        assert_eq!(
+            paths,
+            vec![
+                (5, vec![0, 2, 3, 5]),
+                (7, vec![0, 2, 4, 5]),
+                (8, vec![0, 1, 3, 5]),
+            ]
+        );
+    }
+
+    #[test]
+    fn fewer_paths_than_requested() {
+        let paths = k_shortest_paths(&example(), 0, 5, 10);
+        let costs: Vec<u64> = paths.iter().map(|(cost, _)| *cost).collect();
+        assert_eq!(costs, vec![5, 7, 8, 8, 8, 11, 11]);
+        for (i, (_, path)) in paths.iter().enumerate() {
+            let mut vertices = path.clone();
+            vertices.sort_unstable();
+            vertices.dedup();
+            assert_eq!(vertices.len(), path.len());
+            assert!(paths[..i].iter().all(|(_, other)| other != path));
+        }
+    }
+
+    #[test]
+    fn unreachable_target() {
+        assert!(k_shortest_paths(&example(), 5, 0, 3).is_empty());
+        assert!(k_shortest_paths(&example(), 0, 5, 0).is_empty());
+    }
+
+    #[test]
+    fn source_is_target() {
+        assert_eq!(k_shortest_paths(&example(), 2, 2, 3), vec![(0, vec![2])]);
+    }
+
+    #[test]
+    fn parallel_edges() {
+        let graph = vec![vec![(1, 4), (1, 1), (2, 1)], vec![(2, 1)], vec![]];
+        assert_eq!(
+            k_shortest_paths(&graph, 0, 2, 3),
+            vec![(1, vec![0, 2]), (2, vec![0, 1, 2])]
+        );
+    }
+}
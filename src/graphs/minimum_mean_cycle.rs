@@ -0,0 +1,85 @@
+use super::WeightedGraph;
+
+// Finds the smallest mean edge weight over all cycles of the graph with Karp's algorithm.
+//
+// Let `walk[k][v]` be the lightest walk of exactly `k` edges ending at `v`, starting from any
+// vertex. Karp's theorem states that the minimum cycle mean is the minimum over all vertices `v`
+// of the maximum over `k < n` of `(walk[n][v] - walk[k][v]) / (n - k)`, taking only the finite
+// entries into account. The table is filled in O(V * E) by dynamic programming over `k`.
+//
+// Edge weights may be negative. Returns `None` if the graph has no cycle, since then no walk of
+// `n` edges exists.
+pub fn min_mean_cycle(graph: &WeightedGraph<i64>) -> Option<f64> {
+    let n = graph.len();
+    let mut walk: Vec<Vec<Option<i64>>> = vec![vec![Some(0); n]];
+    for k in 0..n {
+        let mut next = vec![None; n];
+        for u in 0..n {
+            let length = match walk[k][u] {
+                Some(length) => length,
+                None => continue,
+            };
+            for &(v, weight) in &graph[u] {
+                if next[v].is_none_or(|best| length + weight < best) {
+                    next[v] = Some(length + weight);
+                }
+            }
+        }
+        walk.push(next);
+    }
+
+    (0..n)
+        .filter_map(|v| {
+            let last = walk[n][v]? as f64;
+            // walk[0][v] is always finite, so the maximum is taken over at least one value
+            let mean = (0..n)
+                .filter_map(|k| walk[k][v].map(|length| (last - length as f64) / (n - k) as f64))
+                .fold(f64::NEG_INFINITY, f64::max);
+            Some(mean)
+        })
+        .min_by(|a, b| a.partial_cmp(b).unwrap())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::min_mean_cycle;
+
+    fn assert_close(actual: Option<f64>, expected: f64) {
+        let actual = actual.expect("graph should contain a cycle");
+        assert!(
+            (actual - expected).abs() < 1e-9,
+            "{} != {}",
+            actual,
+            expected
+        );
+    }
+
+    #[test]
+    fn two_cycles() {
+        // 0 -> 1 -> 2 -> 0 has mean 3, while 2 -> 3 -> 2 has mean 2
+        let graph = vec![
+            vec![(1, 2)],
+            vec![(2, 3)],
+            vec![(0, 4), (3, 1)],
+            vec![(2, 3), (4, 10)],
+            vec![],
+        ];
+        assert_close(min_mean_cycle(&graph), 2.0);
+    }
+
+    #[test]
+    fn fractional_and_negative_means() {
+        // A triangle of weight -1 competes with a heavier self-loop
+        let graph = vec![vec![(1, 1), (0, 5)], vec![(2, -3)], vec![(0, 1)]];
+        assert_close(min_mean_cycle(&graph), -1.0 / 3.0);
+        let graph = vec![vec![(0, 7)]];
+        assert_close(min_mean_cycle(&graph), 7.0);
+    }
+
+    #[test]
+    fn acyclic() {
+        let graph = vec![vec![(1, 1), (2, 5)], vec![(2, 1)], vec![]];
+        assert_eq!(min_mean_cycle(&graph), None);
+        assert_eq!(min_mean_cycle(&[]), None);
+    }
+}
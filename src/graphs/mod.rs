@@ -1,35 +1,58 @@
 //! This module provides graph based operations.
+mod all_topological_sorts;
+mod approximate_cover;
 mod bellman_ford;
+mod betweenness_centrality;
 mod breadth_first_search;
 mod centroid_decomposition;
+mod constrained_shortest_path;
 mod depth_first_search;
 mod depth_first_search_tic_tac_toe;
 mod dijkstra;
 mod dinic_maxflow;
 mod disjoint_set_union;
+mod eccentricity;
 mod graph_enumeration;
 mod heavy_light_decomposition;
+mod johnson;
+mod k_shortest_paths;
 mod lowest_common_ancestor;
+mod minimum_mean_cycle;
 mod minimum_spanning_tree;
+mod page_rank;
 mod prim;
 mod prufer_code;
 mod representation;
 mod strongly_connected_components;
 mod topological_sort;
 
+pub use self::all_topological_sorts::all_topological_sorts;
+pub use self::approximate_cover::{greedy_set_cover, vertex_cover_2approx};
 pub use self::bellman_ford::bellman_ford;
+pub use self::betweenness_centrality::betweenness_centrality;
 pub use self::breadth_first_search::breadth_first_search;
 pub use self::centroid_decomposition::CentroidDecomposition;
+pub use self::constrained_shortest_path::dijkstra_avoiding;
 pub use self::depth_first_search::depth_first_search;
 pub use self::depth_first_search_tic_tac_toe::minimax;
 pub use self::dijkstra::dijkstra;
 pub use self::dinic_maxflow::DinicMaxFlow;
 pub use self::disjoint_set_union::DisjointSetUnion;
+pub use self::eccentricity::{center, diameter};
 pub use self::graph_enumeration::enumerate_graph;
 pub use self::heavy_light_decomposition::HeavyLightDecomposition;
+pub use self::johnson::{johnson, NegativeCycle};
+pub use self::k_shortest_paths::k_shortest_paths;
 pub use self::lowest_common_ancestor::*;
+pub use self::minimum_mean_cycle::min_mean_cycle;
 pub use self::minimum_spanning_tree::kruskal;
+pub use self::page_rank::page_rank;
 pub use self::prim::{prim, prim_with_start};
 pub use self::prufer_code::{prufer_decode, prufer_encode};
+pub use self::representation::{Edge, Graph, Vertex};
 pub use self::strongly_connected_components::StronglyConnectedComponents;
 pub use self::topological_sort::topological_sort;
+
+/// A weighted directed graph as an adjacency list: `graph[u]` holds the `(v, weight)` edges
+/// leaving vertex `u`. Vertices are numbered from 0 to `graph.len() - 1`.
+pub type WeightedGraph<W> = [Vec<(usize, W)>];
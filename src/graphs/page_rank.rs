@@ -0,0 +1,81 @@
+/// A directed graph as an adjacency list: `graph[u]` holds the targets of the links out of `u`.
+type Graph = [Vec<usize>];
+
+// Computes the PageRank of every vertex with the power method.
+//
+// Each iteration, every vertex passes a `damping` share of its rank evenly to the vertices it
+// links to, and the remaining `1 - damping` is spread evenly over all vertices. Vertices without
+// outgoing links pass their share to every vertex instead, so no rank is lost and the ranks
+// always sum to 1. Runs in O(iterations * (V + E)).
+pub fn page_rank(graph: &Graph, damping: f64, iterations: usize) -> Vec<f64> {
+    let n = graph.len();
+    if n == 0 {
+        return Vec::new();
+    }
+    let mut ranks = vec![1.0 / n as f64; n];
+    for _ in 0..iterations {
+        let dangling: f64 = (0..n)
+            .filter(|&u| graph[u].is_empty())
+            .map(|u| ranks[u])
+            .sum();
+        let base = (1.0 - damping + damping * dangling) / n as f64;
+        let mut next = vec![base; n];
+        for (u, targets) in graph.iter().enumerate() {
+            let share = damping * ranks[u] / targets.len() as f64;
+            for &v in targets {
+                next[v] += share;
+            }
+        }
+        ranks = next;
+    }
+    ranks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_graph() -> Vec<Vec<usize>> {
+        // Vertex 2 is linked to by every other vertex; vertex 4 has no outgoing links
+        vec![vec![1, 2], vec![2], vec![0], vec![0, 2, 4], vec![]]
+    }
+
+    #[test]
+    fn ranks_sum_to_one() {
+        let ranks = page_rank(&sample_graph(), 0.85, 50);
+        assert!((ranks.iter().sum::<f64>() - 1.0).abs() < 1e-9);
+        assert!(ranks.iter().all(|&r| r > 0.0));
+    }
+
+    #[test]
+    fn most_linked_vertex_ranks_highest() {
+        let ranks = page_rank(&sample_graph(), 0.85, 50);
+        let best = (0..ranks.len())
+            .max_by(|&a, &b| ranks[a].partial_cmp(&ranks[b]).unwrap())
+            .unwrap();
+        assert_eq!(best, 2);
+    }
+
+    #[test]
+    fn converges() {
+        let graph = sample_graph();
+        let ranks = page_rank(&graph, 0.85, 100);
+        let more = page_rank(&graph, 0.85, 101);
+        for (a, b) in ranks.iter().zip(more.iter()) {
+            assert!((a - b).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn symmetric_cycle_is_uniform() {
+        let graph = vec![vec![1], vec![2], vec![3], vec![0]];
+        for rank in page_rank(&graph, 0.85, 20) {
+            assert!((rank - 0.25).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn empty_graph() {
+        assert!(page_rank(&[], 0.85, 10).is_empty());
+    }
+}
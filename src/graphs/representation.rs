@@ -1,3 +1,5 @@
+use crate::math::PCG32;
+
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 pub struct Vertex(u32);
 
@@ -14,6 +16,58 @@ impl Graph {
     pub fn new(vertices: Vec<Vertex>, edges: Vec<Edge>) -> Self {
         Graph { vertices, edges }
     }
+
+    // Returns a new graph in which `v` is merged into `u`.
+    //
+    // Every edge ending at `v` is redirected to `u`, so parallel edges may appear, and the edges
+    // between `u` and `v` themselves, which would become self-loops, are dropped. Edges are
+    // treated as undirected, as needed for minimum cuts.
+    pub fn contract_edge(&self, u: usize, v: usize) -> Graph {
+        assert_ne!(u, v, "cannot contract a vertex with itself");
+        let (u, v) = (u as u32, v as u32);
+        let merge = |x: u32| if x == v { u } else { x };
+        Graph {
+            vertices: self.vertices.iter().copied().filter(|w| w.0 != v).collect(),
+            edges: self
+                .edges
+                .iter()
+                .map(|e| Edge(merge(e.0), merge(e.1)))
+                .filter(|e| e.0 != e.1)
+                .collect(),
+        }
+    }
+
+    // Estimates the size of a minimum cut with Karger's randomized contraction algorithm.
+    //
+    // Each run contracts uniformly random edges until two vertices remain, and the edges left
+    // between them form a cut. A single run finds a minimum cut with probability at least
+    // 2 / (n * (n - 1)), so the smallest cut over `iterations` runs is a minimum cut with
+    // high probability once `iterations` is a few times n^2. Every run takes O(V * E). The edges
+    // are drawn from a `PCG32` seeded with `seed`, so the same seed always gives the same cut.
+    //
+    // Returns 0 for disconnected graphs and graphs with fewer than two vertices.
+    pub fn karger_min_cut(&self, iterations: usize, seed: u64) -> usize {
+        let mut rng = PCG32::new_default(seed);
+        let mut best = self.edges.len();
+        for _ in 0..iterations {
+            let mut graph = self.clone();
+            while graph.vertices.len() > 2 && !graph.edges.is_empty() {
+                let edge = graph.edges[rng.get_u64() as usize % graph.edges.len()];
+                graph = graph.contract_edge(edge.0 as usize, edge.1 as usize);
+            }
+            let cut = if graph.vertices.len() > 2 {
+                0
+            } else {
+                graph.edges.len()
+            };
+            best = best.min(cut);
+        }
+        if self.vertices.len() < 2 {
+            0
+        } else {
+            best
+        }
+    }
 }
 
 impl From<u32> for Vertex {
@@ -38,3 +92,58 @@ impl From<(u32, u32)> for Edge {
         Edge(item.0, item.1)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{Edge, Graph, Vertex};
+
+    fn graph(n: u32, edges: &[(u32, u32)]) -> Graph {
+        Graph::new(
+            (0..n).map(Vertex::from).collect(),
+            edges.iter().copied().map(Edge::from).collect(),
+        )
+    }
+
+    #[test]
+    fn contract_edge() {
+        // A square 0-1-2-3 with the diagonal 0-2
+        let square = graph(4, &[(0, 1), (1, 2), (2, 3), (3, 0), (0, 2)]);
+        let contracted = square.contract_edge(0, 2);
+        assert_eq!(contracted.vertices, vec![Vertex(0), Vertex(1), Vertex(3)]);
+        // The diagonal disappears and both sides now form parallel edges
+        assert_eq!(
+            contracted.edges,
+            vec![Edge(0, 1), Edge(1, 0), Edge(0, 3), Edge(3, 0)]
+        );
+
+        let contracted = contracted.contract_edge(1, 0);
+        assert_eq!(contracted.vertices, vec![Vertex(1), Vertex(3)]);
+        assert_eq!(contracted.edges, vec![Edge(1, 3), Edge(3, 1)]);
+    }
+
+    #[test]
+    fn karger_min_cut() {
+        // Two complete graphs on four vertices, joined by two edges
+        let mut edges = Vec::new();
+        for offset in [0, 4].iter() {
+            for a in 0..4 {
+                for b in a + 1..4 {
+                    edges.push((a + offset, b + offset));
+                }
+            }
+        }
+        edges.push((0, 4));
+        edges.push((3, 7));
+        let g = graph(8, &edges);
+        assert_eq!(g.karger_min_cut(500, 252), 2);
+
+        let cycle = graph(5, &[(0, 1), (1, 2), (2, 3), (3, 4), (4, 0)]);
+        assert_eq!(cycle.karger_min_cut(200, 252), 2);
+    }
+
+    #[test]
+    fn karger_disconnected() {
+        assert_eq!(graph(4, &[(0, 1), (2, 3)]).karger_min_cut(20, 252), 0);
+        assert_eq!(graph(1, &[]).karger_min_cut(20, 252), 0);
+    }
+}
@@ -7,6 +7,7 @@
 /// Wikipedia reference: https://en.wikipedia.org/wiki/Baby-step_giant-step
 /// When a is the primitive root modulo n, the answer is unique.
 /// Otherwise it will return the smallest positive solution
+use super::binary_gcd;
 use std::collections::HashMap;
 
 pub fn baby_step_giant_step(a: usize, b: usize, n: usize) -> Option<usize> {
@@ -33,9 +34,68 @@ pub fn baby_step_giant_step(a: usize, b: usize, n: usize) -> Option<usize> {
     None
 }
 
+fn mul_mod(a: u64, b: u64, modulus: u64) -> u64 {
+    ((a as u128 * b as u128) % modulus as u128) as u64
+}
+
+/// Discrete logarithm
+///
+/// Returns the smallest x >= 0 with base^x = target (mod modulus), or `None` if there is none.
+/// Unlike `baby_step_giant_step`, the base does not need to be coprime to the modulus: common
+/// factors are divided out first, which adds one step per prime factor power they share, and
+/// the remaining coprime problem is solved with baby-step giant-step in O(sqrt(modulus)).
+/// Intermediate products are computed in u128, so any u64 modulus is supported.
+pub fn discrete_log(base: u64, target: u64, modulus: u64) -> Option<u64> {
+    assert!(modulus > 0, "modulus must be positive");
+    let (mut base, mut target, mut modulus) = (base % modulus, target % modulus, modulus);
+    // Invariant: base^x = target (mod modulus) iff coefficient * base^(x - offset) = target
+    let mut coefficient = 1 % modulus;
+    let mut offset = 0;
+    loop {
+        if target == coefficient {
+            return Some(offset);
+        }
+        let g = binary_gcd(base, modulus);
+        if g == 1 {
+            break;
+        }
+        if target % g != 0 {
+            return None;
+        }
+        target /= g;
+        modulus /= g;
+        offset += 1;
+        coefficient = mul_mod(coefficient, base / g, modulus);
+        base %= modulus;
+    }
+
+    let m = (modulus as f64).sqrt() as u64 + 1;
+    // baby step: remember the largest j for every value of target * base^j
+    let mut baby_steps = HashMap::new();
+    let mut value = target;
+    for j in 0..=m {
+        baby_steps.insert(value, j);
+        value = mul_mod(value, base, modulus);
+    }
+    // giant step: coefficient * base^(m * i) for increasing i
+    let mut giant_step = 1;
+    for _ in 0..m {
+        giant_step = mul_mod(giant_step, base, modulus);
+    }
+    let mut value = coefficient;
+    for i in 1..=m {
+        value = mul_mod(value, giant_step, modulus);
+        if let Some(&j) = baby_steps.get(&value) {
+            return Some(offset + m * i - j);
+        }
+    }
+    None
+}
+
 #[cfg(test)]
 mod tests {
-    use super::baby_step_giant_step;
+    use super::{baby_step_giant_step, discrete_log};
+    use crate::math::fast_power;
 
     #[test]
     fn small_numbers() {
@@ -69,4 +129,39 @@ mod tests {
             Some(14215560)
         );
     }
+
+    #[test]
+    fn discrete_log_small_primes() {
+        assert_eq!(discrete_log(5, 3, 11), Some(2));
+        assert_eq!(discrete_log(2, 1, 13), Some(0));
+        assert_eq!(discrete_log(3, 13, 17), Some(4));
+        assert_eq!(discrete_log(3, 311401496, 998244353), Some(178105253));
+        // 2 only generates the quadratic residues modulo 7
+        assert_eq!(discrete_log(2, 3, 7), None);
+    }
+
+    #[test]
+    fn discrete_log_is_smallest() {
+        for &modulus in [7_u64, 11, 13, 29, 31].iter() {
+            for base in 1..modulus {
+                for target in 0..modulus {
+                    let expected = (0..modulus).find(|&x| {
+                        fast_power(base as usize, x as usize, modulus as usize) as u64 == target
+                    });
+                    assert_eq!(discrete_log(base, target, modulus), expected);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn discrete_log_composite_modulus() {
+        // 2^x mod 24 runs through 1, 2, 4, 8, 16, 8, 16, ...
+        assert_eq!(discrete_log(2, 16, 24), Some(4));
+        assert_eq!(discrete_log(2, 8, 24), Some(3));
+        assert_eq!(discrete_log(2, 3, 24), None);
+        assert_eq!(discrete_log(6, 0, 36), Some(2));
+        assert_eq!(discrete_log(5, 0, 1), Some(0));
+        assert_eq!(discrete_log(3, 83, 100), Some(9));
+    }
 }
@@ -0,0 +1,170 @@
+use std::ops::{Add, Mul, MulAssign, Sub};
+
+/// A complex number with `f64` parts.
+///
+/// # Examples
+///
+/// ```rust
+/// use rust_algorithms::math::Complex;
+///
+/// let z = Complex::new(3.0, 4.0);
+///
+/// assert_eq!(z.abs(), 5.0);
+/// assert_eq!(z * z.conjugate(), Complex::new(25.0, 0.0));
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Complex {
+    pub re: f64,
+    pub im: f64,
+}
+
+impl Complex {
+    /// Creates the complex number `re + im * i`.
+    #[inline]
+    pub fn new(re: f64, im: f64) -> Self {
+        Self { re, im }
+    }
+
+    /// Creates the complex number with magnitude `r` and angle `theta` in radians.
+    #[inline]
+    pub fn from_polar(r: f64, theta: f64) -> Self {
+        Self {
+            re: r * theta.cos(),
+            im: r * theta.sin(),
+        }
+    }
+
+    /// Returns the complex conjugate `re - im * i`.
+    #[inline]
+    pub fn conjugate(&self) -> Complex {
+        Complex {
+            re: self.re,
+            im: -self.im,
+        }
+    }
+
+    /// Returns the square of the magnitude, which avoids the square root of `abs`.
+    #[inline]
+    pub fn square_norm(&self) -> f64 {
+        self.re * self.re + self.im * self.im
+    }
+
+    /// Returns the magnitude.
+    #[inline]
+    pub fn abs(&self) -> f64 {
+        self.re.hypot(self.im)
+    }
+
+    /// Returns the angle in radians, in `(-pi, pi]`.
+    #[inline]
+    pub fn arg(&self) -> f64 {
+        self.im.atan2(self.re)
+    }
+
+    /// Returns the multiplicative inverse `1 / self`.
+    #[inline]
+    pub fn inverse(&self) -> Complex {
+        let nrm = self.square_norm();
+        Complex {
+            re: self.re / nrm,
+            im: -self.im / nrm,
+        }
+    }
+}
+
+impl Add<Complex> for Complex {
+    type Output = Complex;
+
+    #[inline]
+    fn add(self, other: Complex) -> Complex {
+        Complex {
+            re: self.re + other.re,
+            im: self.im + other.im,
+        }
+    }
+}
+
+impl Sub<Complex> for Complex {
+    type Output = Complex;
+
+    #[inline]
+    fn sub(self, other: Complex) -> Complex {
+        Complex {
+            re: self.re - other.re,
+            im: self.im - other.im,
+        }
+    }
+}
+
+impl Mul<Complex> for Complex {
+    type Output = Complex;
+
+    #[inline]
+    fn mul(self, other: Complex) -> Complex {
+        Complex {
+            re: self.re * other.re - self.im * other.im,
+            im: self.re * other.im + self.im * other.re,
+        }
+    }
+}
+
+impl MulAssign<Complex> for Complex {
+    #[inline]
+    fn mul_assign(&mut self, other: Complex) {
+        *self = *self * other;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Complex;
+    use std::f64::consts::PI;
+
+    fn assert_close(a: Complex, b: Complex) {
+        assert!((a - b).abs() < 1e-12, "{:?} != {:?}", a, b);
+    }
+
+    #[test]
+    fn arithmetic() {
+        let a = Complex::new(1.0, 2.0);
+        let b = Complex::new(-3.0, 0.5);
+        let zero = Complex::default();
+        let one = Complex::new(1.0, 0.0);
+        let i = Complex::new(0.0, 1.0);
+
+        assert_eq!(a + b, Complex::new(-2.0, 2.5));
+        assert_eq!(a - b, Complex::new(4.0, 1.5));
+        assert_eq!(a * b, Complex::new(-4.0, -5.5));
+        assert_eq!(a + zero, a);
+        assert_eq!(a * one, a);
+        assert_eq!(a * b, b * a);
+        assert_eq!(i * i, Complex::new(-1.0, 0.0));
+        assert_eq!(a - a, zero);
+        assert_close(a * a.inverse(), one);
+
+        let mut c = a;
+        c *= b;
+        assert_eq!(c, a * b);
+    }
+
+    #[test]
+    fn conjugate_and_abs() {
+        let z = Complex::new(3.0, 4.0);
+        assert_eq!(z.abs(), 5.0);
+        assert_eq!(z.square_norm(), 25.0);
+        assert_eq!(z.conjugate(), Complex::new(3.0, -4.0));
+        assert_eq!(z * z.conjugate(), Complex::new(25.0, 0.0));
+        assert_eq!(Complex::default().abs(), 0.0);
+    }
+
+    #[test]
+    fn polar_round_trip() {
+        for &(r, theta) in [(1.0, 0.0), (2.0, PI / 3.0), (0.5, -2.0), (5.0, PI)].iter() {
+            let z = Complex::from_polar(r, theta);
+            assert!((z.abs() - r).abs() < 1e-12);
+            assert!((z.arg() - theta).abs() < 1e-12);
+            assert_close(Complex::from_polar(z.abs(), z.arg()), z);
+        }
+        assert_close(Complex::from_polar(2.0, PI / 2.0), Complex::new(0.0, 2.0));
+    }
+}
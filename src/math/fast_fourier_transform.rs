@@ -1,89 +1,4 @@
-use std::ops::{Add, Mul, MulAssign, Sub};
-
-// f64 complex
-#[derive(Clone, Copy, Debug)]
-pub struct Complex64 {
-    pub re: f64,
-    pub im: f64,
-}
-
-impl Complex64 {
-    #[inline]
-    pub fn new(re: f64, im: f64) -> Self {
-        Self { re, im }
-    }
-
-    #[inline]
-    pub fn square_norm(&self) -> f64 {
-        self.re * self.re + self.im * self.im
-    }
-
-    #[inline]
-    pub fn norm(&self) -> f64 {
-        self.square_norm().sqrt()
-    }
-
-    #[inline]
-    pub fn inverse(&self) -> Complex64 {
-        let nrm = self.square_norm();
-        Complex64 {
-            re: self.re / nrm,
-            im: -self.im / nrm,
-        }
-    }
-}
-
-impl Default for Complex64 {
-    #[inline]
-    fn default() -> Self {
-        Self { re: 0.0, im: 0.0 }
-    }
-}
-
-impl Add<Complex64> for Complex64 {
-    type Output = Complex64;
-
-    #[inline]
-    fn add(self, other: Complex64) -> Complex64 {
-        Complex64 {
-            re: self.re + other.re,
-            im: self.im + other.im,
-        }
-    }
-}
-
-impl Sub<Complex64> for Complex64 {
-    type Output = Complex64;
-
-    #[inline]
-    fn sub(self, other: Complex64) -> Complex64 {
-        Complex64 {
-            re: self.re - other.re,
-            im: self.im - other.im,
-        }
-    }
-}
-
-impl Mul<Complex64> for Complex64 {
-    type Output = Complex64;
-
-    #[inline]
-    fn mul(self, other: Complex64) -> Complex64 {
-        Complex64 {
-            re: self.re * other.re - self.im * other.im,
-            im: self.re * other.im + self.im * other.re,
-        }
-    }
-}
-
-impl MulAssign<Complex64> for Complex64 {
-    #[inline]
-    fn mul_assign(&mut self, other: Complex64) {
-        let tmp = self.re * other.im + self.im * other.re;
-        self.re = self.re * other.re - self.im * other.im;
-        self.im = tmp;
-    }
-}
+use super::Complex;
 
 pub fn fast_fourier_transform_input_permutation(length: usize) -> Vec<usize> {
     let mut result = Vec::new();
@@ -110,20 +25,20 @@ pub fn fast_fourier_transform_input_permutation(length: usize) -> Vec<usize> {
     result
 }
 
-pub fn fast_fourier_transform(input: &[f64], input_permutation: &[usize]) -> Vec<Complex64> {
+pub fn fast_fourier_transform(input: &[f64], input_permutation: &[usize]) -> Vec<Complex> {
     let n = input.len();
     let mut result = Vec::new();
     result.reserve_exact(n);
     for position in input_permutation {
-        result.push(Complex64::new(input[*position], 0.0));
+        result.push(Complex::new(input[*position], 0.0));
     }
     let mut segment_length = 1_usize;
     while segment_length < n {
         segment_length <<= 1;
         let angle: f64 = std::f64::consts::TAU / segment_length as f64;
-        let w_len = Complex64::new(angle.cos(), angle.sin());
+        let w_len = Complex::from_polar(1.0, angle);
         for segment_start in (0..n).step_by(segment_length) {
-            let mut w = Complex64::new(1.0, 0.0);
+            let mut w = Complex::new(1.0, 0.0);
             for position in segment_start..(segment_start + segment_length / 2) {
                 let a = result[position];
                 let b = result[position + segment_length / 2] * w;
@@ -136,10 +51,7 @@ pub fn fast_fourier_transform(input: &[f64], input_permutation: &[usize]) -> Vec
     result
 }
 
-pub fn inverse_fast_fourier_transform(
-    input: &[Complex64],
-    input_permutation: &[usize],
-) -> Vec<f64> {
+pub fn inverse_fast_fourier_transform(input: &[Complex], input_permutation: &[usize]) -> Vec<f64> {
     let n = input.len();
     let mut result = Vec::new();
     result.reserve_exact(n);
@@ -150,9 +62,9 @@ pub fn inverse_fast_fourier_transform(
     while segment_length < n {
         segment_length <<= 1;
         let angle: f64 = -std::f64::consts::TAU / segment_length as f64;
-        let w_len = Complex64::new(angle.cos(), angle.sin());
+        let w_len = Complex::from_polar(1.0, angle);
         for segment_start in (0..n).step_by(segment_length) {
-            let mut w = Complex64::new(1.0, 0.0);
+            let mut w = Complex::new(1.0, 0.0);
             for position in segment_start..(segment_start + segment_length / 2) {
                 let a = result[position];
                 let b = result[position + segment_length / 2] * w;
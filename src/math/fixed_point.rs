@@ -0,0 +1,187 @@
+/// Computes the square root of an unsigned fixed-point number with Newton's method.
+///
+/// A fixed-point number with `fractional_bits` fractional bits stores the real number
+/// `x / 2^fractional_bits` as the integer `x`, so with 16 fractional bits `1.5` is stored as
+/// `3 << 15`. The square root of `x / 2^f` is stored as `sqrt(x * 2^f)`, which is computed as the
+/// integer square root of `x << f` without any floating-point arithmetic: starting from a power of
+/// two above the root, `y = (y + n / y) / 2` decreases until it reaches the floor of the root,
+/// doubling the number of correct bits in every step.
+///
+/// The result is truncated, so it is below the exact root by less than one unit in the last place,
+/// `2^-fractional_bits`, and exact when the root is representable.
+///
+/// # Panics
+///
+/// Panics if `fractional_bits` is 64 or more.
+///
+/// # Examples
+///
+/// ```rust
+/// use rust_algorithms::math::fixed_sqrt;
+///
+/// // 2.25 and 1.5 with 16 fractional bits
+/// assert_eq!(fixed_sqrt(9 << 14, 16), 3 << 15);
+/// ```
+pub fn fixed_sqrt(x: u64, fractional_bits: u32) -> u64 {
+    assert!(
+        fractional_bits < 64,
+        "fractional_bits must be less than 64, got {}",
+        fractional_bits
+    );
+    let n = (x as u128) << fractional_bits;
+    if n == 0 {
+        return 0;
+    }
+    let bits = 128 - n.leading_zeros();
+    let mut root = 1u128 << bits.div_ceil(2);
+    loop {
+        let next = (root + n / root) / 2;
+        if next >= root {
+            return root as u64;
+        }
+        root = next;
+    }
+}
+
+/// Computes the reciprocal `1 / x` of an unsigned fixed-point number with Newton's method.
+///
+/// With the representation of `fixed_sqrt`, the reciprocal of `x / 2^f` is stored as
+/// `2^(2f) / x`. It is found without any division, which makes it suitable where division is
+/// slow or unavailable: starting from a power of two at most half below the reciprocal, each
+/// step `y = y * (2 - x * y)` squares the relative error, and dividing by `2^(2f)` inside it is a
+/// shift. A final correction adds the units lost to truncation along the way.
+///
+/// The result is truncated, so it is below the exact reciprocal by less than one unit in the last
+/// place, and saturates at `u64::MAX` when the reciprocal does not fit, which only happens for
+/// `x == 1` with 32 fractional bits.
+///
+/// # Panics
+///
+/// Panics if `x` is 0 or `fractional_bits` is more than 32.
+///
+/// # Examples
+///
+/// ```rust
+/// use rust_algorithms::math::fixed_reciprocal;
+///
+/// // The reciprocal of 4.0 is 0.25 with 16 fractional bits
+/// assert_eq!(fixed_reciprocal(4 << 16, 16), 1 << 14);
+/// ```
+pub fn fixed_reciprocal(x: u64, fractional_bits: u32) -> u64 {
+    assert!(x != 0, "cannot take the reciprocal of zero");
+    assert!(
+        fractional_bits <= 32,
+        "fractional_bits must be at most 32, got {}",
+        fractional_bits
+    );
+    let shift = 2 * fractional_bits;
+    let one = 1u128 << shift;
+    let x = x as u128;
+    if x >= one {
+        return (x == one) as u64;
+    }
+    // x lies in [2^(bits - 1), 2^bits), so this guess lies in [1 / (2x), 1 / x)
+    let bits = 128 - x.leading_zeros();
+    let mut y = 1u128 << (shift - bits);
+    loop {
+        // y never exceeds the reciprocal, so x * y never exceeds one
+        let next = y + ((y * (one - x * y)) >> shift);
+        if next == y {
+            break;
+        }
+        y = next;
+    }
+    while (y + 1) * x <= one {
+        y += 1;
+    }
+    y.min(u64::MAX as u128) as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{fixed_reciprocal, fixed_sqrt};
+    use crate::math::PCG32;
+
+    fn to_fixed(value: f64, fractional_bits: u32) -> f64 {
+        value * (1u64 << fractional_bits) as f64
+    }
+
+    fn from_fixed(x: u64, fractional_bits: u32) -> f64 {
+        x as f64 / (1u64 << fractional_bits) as f64
+    }
+
+    #[test]
+    fn sqrt_perfect_squares() {
+        assert_eq!(fixed_sqrt(0, 16), 0);
+        assert_eq!(fixed_sqrt(1 << 16, 16), 1 << 16);
+        assert_eq!(fixed_sqrt(4 << 16, 16), 2 << 16);
+        assert_eq!(fixed_sqrt(9 << 32, 32), 3 << 32);
+        assert_eq!(fixed_sqrt(1 << 14, 16), 1 << 15);
+        assert_eq!(fixed_sqrt(144, 0), 12);
+        assert_eq!(fixed_sqrt(u64::MAX, 0), u32::MAX as u64);
+        assert_eq!(fixed_sqrt(1 << 63, 63), 1 << 63);
+    }
+
+    #[test]
+    fn sqrt_matches_float() {
+        let mut rng = PCG32::new_default(268);
+        for &bits in [0, 8, 16, 24, 32].iter() {
+            for _ in 0..500 {
+                let x = rng.get_u64() >> (rng.get_u32() % 48);
+                let root = fixed_sqrt(x, bits);
+                let expected = to_fixed(from_fixed(x, bits).sqrt(), bits);
+                assert!(
+                    (root as f64 - expected).abs() <= 1.0,
+                    "sqrt of {} with {} bits",
+                    x,
+                    bits
+                );
+                // The root is truncated exactly
+                let n = (x as u128) << bits;
+                let root = root as u128;
+                assert!(root * root <= n && n < (root + 1) * (root + 1));
+            }
+        }
+    }
+
+    #[test]
+    fn reciprocal_exact_values() {
+        assert_eq!(fixed_reciprocal(1 << 16, 16), 1 << 16);
+        assert_eq!(fixed_reciprocal(2 << 16, 16), 1 << 15);
+        assert_eq!(fixed_reciprocal(1 << 15, 16), 2 << 16);
+        assert_eq!(fixed_reciprocal(3 << 16, 16), 21845);
+        assert_eq!(fixed_reciprocal(7, 0), 0);
+        assert_eq!(fixed_reciprocal(1, 0), 1);
+        assert_eq!(fixed_reciprocal(u64::MAX, 32), 1);
+        assert_eq!(fixed_reciprocal(1, 32), u64::MAX);
+        assert_eq!(fixed_reciprocal(2, 32), 1 << 63);
+    }
+
+    #[test]
+    fn reciprocal_matches_float() {
+        let mut rng = PCG32::new_default(2680);
+        for &bits in [4, 16, 24, 32].iter() {
+            for _ in 0..500 {
+                let x = (rng.get_u64() >> (rng.get_u32() % 64)).max(1);
+                let y = fixed_reciprocal(x, bits);
+                let expected = to_fixed(1.0 / from_fixed(x, bits), bits);
+                if expected < u64::MAX as f64 {
+                    assert!(
+                        (y as f64 - expected).abs() <= 1.0 + expected * 1e-15,
+                        "reciprocal of {} with {} bits",
+                        x,
+                        bits
+                    );
+                    let one = 1u128 << (2 * bits);
+                    assert!(y as u128 * x as u128 <= one && one < (y as u128 + 1) * x as u128);
+                }
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "cannot take the reciprocal of zero")]
+    fn reciprocal_of_zero() {
+        fixed_reciprocal(0, 16);
+    }
+}
@@ -0,0 +1,64 @@
+/// gray_code(n) returns the reflected binary Gray code sequence of `n`-bit numbers, in which
+/// consecutive entries (including the last and the first) differ in exactly one bit.
+/// About Gray codes: https://en.wikipedia.org/wiki/Gray_code
+///
+/// Arguments:
+///     * `n` - number of bits, at most 31
+/// Complexity
+///     - time complexity: O(2^n),
+///     - space complexity: O(2^n),
+pub fn gray_code(n: u32) -> Vec<u32> {
+    assert!(n < 32, "gray codes are limited to 31 bits");
+    (0..1u32 << n).map(binary_to_gray).collect()
+}
+
+/// Converts a binary number to its Gray code.
+pub fn binary_to_gray(x: u32) -> u32 {
+    x ^ (x >> 1)
+}
+
+/// Converts a Gray code back to the binary number it encodes.
+pub fn gray_to_binary(x: u32) -> u32 {
+    // Every binary bit is the xor of all Gray code bits above and including it
+    let mut x = x;
+    let mut shift = 1;
+    while shift < u32::BITS {
+        x ^= x >> shift;
+        shift <<= 1;
+    }
+    x
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn small_sequences() {
+        assert_eq!(gray_code(0), vec![0]);
+        assert_eq!(gray_code(1), vec![0, 1]);
+        assert_eq!(gray_code(3), vec![0, 1, 3, 2, 6, 7, 5, 4]);
+    }
+
+    #[test]
+    fn single_bit_steps() {
+        let codes = gray_code(4);
+        assert_eq!(codes.len(), 16);
+        for i in 0..codes.len() {
+            let next = codes[(i + 1) % codes.len()];
+            assert_eq!((codes[i] ^ next).count_ones(), 1);
+        }
+        // Every 4-bit number appears exactly once
+        let mut sorted = codes;
+        sorted.sort_unstable();
+        assert_eq!(sorted, (0..16).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn conversions_are_inverses() {
+        for x in (0..100_000).chain(u32::MAX - 1000..=u32::MAX) {
+            assert_eq!(gray_to_binary(binary_to_gray(x)), x);
+            assert_eq!(binary_to_gray(gray_to_binary(x)), x);
+        }
+    }
+}
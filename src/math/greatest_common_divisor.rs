@@ -22,6 +22,34 @@ pub fn greatest_common_divisor_iterative(mut a: i64, mut b: i64) -> i64 {
     b.abs()
 }
 
+/// Binary GCD (Stein's algorithm).
+///
+/// Replaces the divisions of the Euclidean algorithm by shifts and subtractions: common factors of
+/// two are pulled out up front, and afterwards the smaller odd operand is repeatedly subtracted
+/// from the larger one, whose remaining factors of two are then dropped.
+/// binary_gcd(0, n) = binary_gcd(n, 0) = n.
+pub fn binary_gcd(mut a: u64, mut b: u64) -> u64 {
+    if a == 0 {
+        return b;
+    }
+    if b == 0 {
+        return a;
+    }
+    let shift = (a | b).trailing_zeros();
+    a >>= a.trailing_zeros();
+    loop {
+        b >>= b.trailing_zeros();
+        // Both are odd now, so their difference is even and non-negative
+        if a > b {
+            std::mem::swap(&mut a, &mut b);
+        }
+        b -= a;
+        if b == 0 {
+            return a << shift;
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -81,4 +109,31 @@ mod tests {
         assert_eq!(greatest_common_divisor_iterative(-40, 40), 40);
         assert_eq!(greatest_common_divisor_iterative(12, -27), 3);
     }
+
+    #[test]
+    fn binary_gcd_zero_operands() {
+        assert_eq!(binary_gcd(0, 0), 0);
+        assert_eq!(binary_gcd(0, 7), 7);
+        assert_eq!(binary_gcd(12, 0), 12);
+        assert_eq!(binary_gcd(0, u64::MAX), u64::MAX);
+    }
+
+    #[test]
+    fn binary_gcd_matches_euclidean() {
+        for a in 0..300_i64 {
+            for b in 0..300_i64 {
+                let expected = greatest_common_divisor_iterative(a, b) as u64;
+                assert_eq!(binary_gcd(a as u64, b as u64), expected);
+            }
+        }
+        assert_eq!(binary_gcd(1 << 40, 3 << 20), 1 << 20);
+        assert_eq!(binary_gcd(u64::MAX, u64::MAX - 1), 1);
+        assert_eq!(
+            binary_gcd(
+                2_u64.pow(10) * 3_u64.pow(20),
+                2_u64.pow(15) * 3_u64.pow(7) * 5
+            ),
+            2_u64.pow(10) * 3_u64.pow(7)
+        );
+    }
 }
@@ -0,0 +1,124 @@
+/// Solves a linear program in two variables by enumerating the vertices of the feasible region.
+///
+/// Every constraint `(a, b, c)` describes the half-plane `a * x + b * y <= c`. The optimum of a
+/// bounded, feasible linear program is attained at a vertex of the feasible region, where two
+/// constraint lines intersect, so it suffices to evaluate the objective `(p, q) -> p * x + q * y`
+/// at every feasible intersection. Regions without vertices (such as strips) are covered by also
+/// trying the point of each constraint line closest to the origin, and the origin itself.
+///
+/// Returns `(x, y, objective_value)` of an optimal point, or `None` if the constraints are
+/// infeasible or the objective is unbounded. Runs in O(m^3) for m constraints.
+pub fn solve_lp_2d(
+    objective: (f64, f64),
+    constraints: &[(f64, f64, f64)],
+    maximize: bool,
+) -> Option<(f64, f64, f64)> {
+    const EPS: f64 = 1e-9;
+    // Turn minimization into maximization of the negated objective
+    let sign = if maximize { 1.0 } else { -1.0 };
+    let (p, q) = (sign * objective.0, sign * objective.1);
+
+    let feasible = |x: f64, y: f64| {
+        constraints
+            .iter()
+            .all(|&(a, b, c)| a * x + b * y <= c + EPS * (1.0 + c.abs()))
+    };
+
+    // The objective is unbounded if it increases along some direction of the recession cone
+    // `a * dx + b * dy <= 0`. The cone is spanned by directions along the constraint lines, the
+    // inward normals, or anything at all when there are no constraints.
+    let mut directions = vec![(p, q)];
+    for &(a, b, _) in constraints {
+        directions.extend([(b, -a), (-b, a), (-a, -b)].iter());
+    }
+    let unbounded = directions.iter().any(|&(dx, dy)| {
+        p * dx + q * dy > EPS && constraints.iter().all(|&(a, b, _)| a * dx + b * dy <= EPS)
+    });
+
+    let mut candidates = vec![(0.0, 0.0)];
+    for (i, &(a1, b1, c1)) in constraints.iter().enumerate() {
+        let norm = a1 * a1 + b1 * b1;
+        if norm > EPS {
+            candidates.push((c1 * a1 / norm, c1 * b1 / norm));
+        }
+        for &(a2, b2, c2) in &constraints[i + 1..] {
+            // Intersection of the two lines by Cramer's rule
+            let det = a1 * b2 - a2 * b1;
+            if det.abs() > EPS {
+                candidates.push(((c1 * b2 - c2 * b1) / det, (a1 * c2 - a2 * c1) / det));
+            }
+        }
+    }
+
+    let best = candidates
+        .into_iter()
+        .filter(|&(x, y)| feasible(x, y))
+        .map(|(x, y)| (x, y, p * x + q * y))
+        .fold(
+            None,
+            |best: Option<(f64, f64, f64)>, candidate| match best {
+                Some(b) if b.2 >= candidate.2 => Some(b),
+                _ => Some(candidate),
+            },
+        )?;
+    if unbounded {
+        return None;
+    }
+    Some((best.0, best.1, sign * best.2))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(actual: (f64, f64, f64), expected: (f64, f64, f64)) {
+        assert!((actual.0 - expected.0).abs() < 1e-6, "{:?}", actual);
+        assert!((actual.1 - expected.1).abs() < 1e-6, "{:?}", actual);
+        assert!((actual.2 - expected.2).abs() < 1e-6, "{:?}", actual);
+    }
+
+    #[test]
+    fn maximize() {
+        // The classic Wyndor Glass example
+        let constraints = [
+            (1.0, 0.0, 4.0),
+            (0.0, 2.0, 12.0),
+            (3.0, 2.0, 18.0),
+            (-1.0, 0.0, 0.0),
+            (0.0, -1.0, 0.0),
+        ];
+        let result = solve_lp_2d((3.0, 5.0), &constraints, true).unwrap();
+        assert_close(result, (2.0, 6.0, 36.0));
+    }
+
+    #[test]
+    fn minimize() {
+        // x >= 1, y >= 2, x + y >= 4
+        let constraints = [(-1.0, 0.0, -1.0), (0.0, -1.0, -2.0), (-1.0, -1.0, -4.0)];
+        let result = solve_lp_2d((2.0, 1.0), &constraints, false).unwrap();
+        assert_close(result, (1.0, 3.0, 5.0));
+    }
+
+    #[test]
+    fn infeasible() {
+        let constraints = [(1.0, 0.0, 1.0), (-1.0, 0.0, -2.0), (0.0, 1.0, 5.0)];
+        assert_eq!(solve_lp_2d((1.0, 1.0), &constraints, true), None);
+    }
+
+    #[test]
+    fn unbounded() {
+        let constraints = [(0.0, 1.0, 1.0), (-1.0, 0.0, 0.0)];
+        assert_eq!(solve_lp_2d((1.0, 0.0), &constraints, true), None);
+        // The same region is bounded in the other direction
+        let result = solve_lp_2d((1.0, 0.0), &constraints, false).unwrap();
+        assert!(result.0.abs() < 1e-6 && result.2.abs() < 1e-6);
+    }
+
+    #[test]
+    fn strip_without_vertices() {
+        // 1 <= y <= 3, maximizing y has optimal value 3 everywhere on the upper line
+        let constraints = [(0.0, 1.0, 3.0), (0.0, -1.0, -1.0)];
+        let result = solve_lp_2d((0.0, 1.0), &constraints, true).unwrap();
+        assert_close(result, (0.0, 3.0, 3.0));
+    }
+}
@@ -1,37 +1,47 @@
 //! This module provides many mathematical operations.
 mod armstrong_number;
 mod baby_step_giant_step;
+mod complex;
 mod derivative_method;
 mod extended_euclidean_algorithm;
 mod fast_fourier_transform;
 mod fast_power;
+mod fixed_point;
 mod gaussian_elimination;
 mod gcd_of_n_numbers;
+mod gray_code;
 mod greatest_common_divisor;
 mod karatsuba_multiplication;
 mod lcm_of_n_numbers;
+mod linear_programming;
 mod linear_sieve;
 mod matrix_ops;
 mod miller_rabin;
 mod newton_raphson;
 mod nthprime;
 mod pascal_triangle;
+mod pell_equation;
 mod perfect_numbers;
 mod pollard_rho;
+mod polynomial;
 mod prime_check;
+mod prime_factorization;
 mod prime_factors;
 mod prime_numbers;
 mod quadratic_residue;
 mod random;
+mod romberg_integration;
 mod sieve_of_eratosthenes;
 mod simpson_integration;
 mod square_root;
+mod statistics;
 mod trapezoidal_integration;
 mod trial_division;
 mod zellers_congruence_algorithm;
 
 pub use self::armstrong_number::is_armstrong_number;
-pub use self::baby_step_giant_step::baby_step_giant_step;
+pub use self::baby_step_giant_step::{baby_step_giant_step, discrete_log};
+pub use self::complex::Complex;
 pub use self::derivative_method::derivative_method;
 pub use self::extended_euclidean_algorithm::extended_euclidean_algorithm;
 pub use self::fast_fourier_transform::{
@@ -39,13 +49,16 @@ pub use self::fast_fourier_transform::{
     inverse_fast_fourier_transform,
 };
 pub use self::fast_power::fast_power;
+pub use self::fixed_point::{fixed_reciprocal, fixed_sqrt};
 pub use self::gaussian_elimination::gaussian_elimination;
 pub use self::gcd_of_n_numbers::gcd;
+pub use self::gray_code::{binary_to_gray, gray_code, gray_to_binary};
 pub use self::greatest_common_divisor::{
-    greatest_common_divisor_iterative, greatest_common_divisor_recursive,
+    binary_gcd, greatest_common_divisor_iterative, greatest_common_divisor_recursive,
 };
 pub use self::karatsuba_multiplication::multiply;
 pub use self::lcm_of_n_numbers::lcm;
+pub use self::linear_programming::solve_lp_2d;
 pub use self::linear_sieve::LinearSieve;
 pub use self::matrix_ops::{
     matrix_add, matrix_multiply, matrix_scalar_multiplication, matrix_subtract, matrix_transpose,
@@ -54,16 +67,26 @@ pub use self::miller_rabin::miller_rabin;
 pub use self::newton_raphson::find_root;
 pub use self::nthprime::nthprime;
 pub use self::pascal_triangle::pascal_triangle;
+pub use self::pell_equation::pell_fundamental;
 pub use self::perfect_numbers::perfect_numbers;
-pub use self::pollard_rho::{pollard_rho_factorize, pollard_rho_get_one_factor};
+pub use self::pollard_rho::{
+    factorize, pollard_rho, pollard_rho_factorize, pollard_rho_get_one_factor,
+};
+pub use self::polynomial::Polynomial;
 pub use self::prime_check::prime_check;
+pub use self::prime_factorization::prime_factorization;
 pub use self::prime_factors::prime_factors;
 pub use self::prime_numbers::prime_numbers;
 pub use self::quadratic_residue::cipolla;
 pub use self::random::PCG32;
+pub use self::romberg_integration::romberg;
 pub use self::sieve_of_eratosthenes::sieve_of_eratosthenes;
-pub use self::simpson_integration::simpson_integration;
+pub use self::simpson_integration::{simpson, simpson_integration};
 pub use self::square_root::square_root;
+pub use self::statistics::{
+    checked_mean, mean, median, percentile, population_std_dev, population_variance,
+    sample_std_dev, sample_variance,
+};
 pub use self::trapezoidal_integration::trapezoidal_integral;
 pub use self::trial_division::trial_division;
 pub use self::zellers_congruence_algorithm::zellers_congruence_algorithm;
@@ -0,0 +1,120 @@
+use super::fixed_sqrt;
+
+/// Finds the fundamental solution of Pell's equation `x^2 - d * y^2 = 1`, the one with the
+/// smallest positive `x` and `y`.
+///
+/// The continued fraction of `sqrt(d)` is periodic for non-square `d`, with its terms computed
+/// exactly from integers by `m' = den * a - m`, `den' = (d - m'^2) / den` and
+/// `a' = (a0 + m') / den'`. A period ends at the first term `2 * a0`, at index `r`, and the
+/// fundamental solution is the convergent `x / y` right before it when `r` is even, and the one
+/// right before the end of the second period when `r` is odd. No solution ever needs to be squared
+/// to be recognized, which would overflow long before the solution itself does.
+///
+/// The solutions can be huge even for small `d`, as for `d = 61`, where `x = 1766319049`.
+///
+/// # Returns
+///
+/// The fundamental solution `(x, y)`, or `None` if `d` is a perfect square, which leaves only the
+/// trivial solution `(1, 0)`.
+///
+/// # Panics
+///
+/// Panics if the fundamental solution does not fit in `u128`.
+///
+/// # Examples
+///
+/// ```rust
+/// use rust_algorithms::math::pell_fundamental;
+///
+/// assert_eq!(pell_fundamental(7), Some((8, 3)));
+/// assert_eq!(pell_fundamental(9), None);
+/// ```
+pub fn pell_fundamental(d: u64) -> Option<(u128, u128)> {
+    let a0 = fixed_sqrt(d, 0) as u128;
+    let d = d as u128;
+    if a0 * a0 == d {
+        return None;
+    }
+    let (mut m, mut den, mut a) = (0, 1, a0);
+    // The last two convergents, starting from a0 / 1
+    let (mut x_prev, mut x) = (1u128, a0);
+    let (mut y_prev, mut y) = (0u128, 1u128);
+    let mut terms = 1;
+    loop {
+        m = den * a - m;
+        den = (d - m * m) / den;
+        a = (a0 + m) / den;
+        if a == 2 * a0 && terms % 2 == 0 {
+            return Some((x, y));
+        }
+        let next = |prev: u128, current: u128| {
+            a.checked_mul(current)
+                .and_then(|v| v.checked_add(prev))
+                .expect("the fundamental solution does not fit in u128")
+        };
+        let (x_next, y_next) = (next(x_prev, x), next(y_prev, y));
+        x_prev = x;
+        x = x_next;
+        y_prev = y;
+        y = y_next;
+        terms += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::pell_fundamental;
+
+    #[test]
+    fn known_solutions() {
+        assert_eq!(pell_fundamental(2), Some((3, 2)));
+        assert_eq!(pell_fundamental(3), Some((2, 1)));
+        // The period of sqrt(5) has odd length
+        assert_eq!(pell_fundamental(5), Some((9, 4)));
+        assert_eq!(pell_fundamental(13), Some((649, 180)));
+        assert_eq!(pell_fundamental(61), Some((1766319049, 226153980)));
+        assert_eq!(
+            pell_fundamental(109),
+            Some((158070671986249, 15140424455100))
+        );
+    }
+
+    #[test]
+    fn perfect_squares() {
+        for &d in [0, 1, 4, 9, 144, 1 << 62, u32::MAX as u64 * u32::MAX as u64].iter() {
+            assert_eq!(pell_fundamental(d), None, "{} is a square", d);
+        }
+    }
+
+    #[test]
+    fn solves_the_equation_minimally() {
+        for d in 2..1000u64 {
+            let (x, y) = match pell_fundamental(d) {
+                Some(solution) => solution,
+                None => continue,
+            };
+            let d = d as u128;
+            // Some solutions fit but their squares do not
+            if let (Some(xx), Some(dyy)) = (
+                x.checked_mul(x),
+                d.checked_mul(y).and_then(|dy| dy.checked_mul(y)),
+            ) {
+                assert_eq!(xx, dyy + 1, "d = {}", d);
+            }
+            if d < 50 {
+                // No smaller y gives a square 1 + d * y^2
+                for smaller in 1..y {
+                    let square = 1 + d * smaller * smaller;
+                    let root = (square as f64).sqrt() as u128;
+                    assert!((root.saturating_sub(1)..=root + 1).all(|r| r * r != square));
+                }
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "the fundamental solution does not fit in u128")]
+    fn overflow() {
+        pell_fundamental(4729494);
+    }
+}
@@ -195,6 +195,53 @@ pub fn pollard_rho_factorize(
     result
 }
 
+/// Witnesses for which Miller-Rabin is deterministic for every `u64`.
+const MILLER_RABIN_BASES: [u64; 12] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37];
+
+/// Returns a non-trivial factor of `n`, or `None` if `n` is prime, 0 or 1.
+///
+/// Primes are filtered out with a deterministic Miller-Rabin test first, since the rho iteration
+/// would never terminate on them. The factor itself is found by Pollard's rho with Brent's cycle
+/// detection, multiplying in `u128` to avoid overflow, in about O(n^(1/4)) steps.
+pub fn pollard_rho(n: u64) -> Option<u64> {
+    if n < 4 || miller_rabin(n, &MILLER_RABIN_BASES) == 0 {
+        return None;
+    }
+    if n.is_multiple_of(2) {
+        return Some(2);
+    }
+    let mut seed = 314159_u32;
+    Some(pollard_rho_get_one_factor(n, &mut seed, false))
+}
+
+/// Factorizes `n` into `(prime, exponent)` pairs in increasing order of the primes.
+///
+/// Unlike trial division, this handles large semiprimes by repeatedly splitting composite
+/// factors with `pollard_rho`. Returns an empty vector for 0 and 1.
+pub fn factorize(n: u64) -> Vec<(u64, u32)> {
+    let mut primes = Vec::new();
+    let mut to_be_factored = if n > 1 { vec![n] } else { vec![] };
+    while let Some(last) = to_be_factored.pop() {
+        match pollard_rho(last) {
+            Some(factor) => {
+                to_be_factored.push(factor);
+                to_be_factored.push(last / factor);
+            }
+            None => primes.push(last),
+        }
+    }
+    primes.sort_unstable();
+
+    let mut factors: Vec<(u64, u32)> = Vec::new();
+    for p in primes {
+        match factors.last_mut() {
+            Some((prime, exponent)) if *prime == p => *exponent += 1,
+            _ => factors.push((p, 1)),
+        }
+    }
+    factors
+}
+
 #[cfg(test)]
 mod test {
     use super::super::LinearSieve;
@@ -276,4 +323,59 @@ mod test {
             ));
         }
     }
+
+    #[test]
+    fn pollard_rho_finds_factor() {
+        for &n in [
+            4_u64,
+            15,
+            91,
+            1_000_000_016_000_000_063,
+            2_761_929_023_323_646_159,
+        ]
+        .iter()
+        {
+            let factor = pollard_rho(n).unwrap();
+            assert!(check_is_proper_factor(n, factor));
+        }
+        for &n in [
+            0_u64,
+            1,
+            2,
+            3,
+            13,
+            1_000_000_007,
+            18_446_744_073_709_551_557,
+        ]
+        .iter()
+        {
+            assert_eq!(pollard_rho(n), None);
+        }
+    }
+
+    #[test]
+    fn factorize_reconstructs_number() {
+        let numbers = [
+            2_u64,
+            360,
+            1 << 40,
+            3_869_305_776_707_280_953,
+            1_024 * 243 * 1_000_000_007,
+            999_999_937 * 999_999_937,
+            18_446_744_073_709_551_557,
+            u64::MAX,
+        ];
+        for &n in numbers.iter() {
+            let factors = factorize(n);
+            let product = factors.iter().fold(1_u64, |acc, &(p, e)| acc * p.pow(e));
+            assert_eq!(product, n);
+            assert!(factors.windows(2).all(|w| w[0].0 < w[1].0));
+            assert!(factors
+                .iter()
+                .all(|&(p, _)| miller_rabin(p, &MILLER_RABIN_BASES) == 0));
+        }
+        assert_eq!(factorize(360), vec![(2, 3), (3, 2), (5, 1)]);
+        assert_eq!(factorize(1), vec![]);
+        assert_eq!(factorize(0), vec![]);
+    }
 }
@@ -0,0 +1,228 @@
+use std::ops::{Add, Mul};
+
+/// A polynomial with real coefficients.
+///
+/// The coefficients are stored from the constant term upwards, so `coefficients[i]` belongs to
+/// `x^i`. Trailing zero coefficients are dropped, which makes the zero polynomial the empty
+/// vector and lets equal polynomials compare equal.
+///
+/// # Examples
+///
+/// ```rust
+/// use rust_algorithms::math::Polynomial;
+///
+/// // x^2 - 1
+/// let p = Polynomial::new(vec![-1.0, 0.0, 1.0]);
+///
+/// assert_eq!(p.evaluate(3.0), 8.0);
+/// assert_eq!(p.derivative(), Polynomial::new(vec![0.0, 2.0]));
+/// assert_eq!(&p * &p, Polynomial::new(vec![1.0, 0.0, -2.0, 0.0, 1.0]));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Polynomial {
+    coefficients: Vec<f64>,
+}
+
+impl Polynomial {
+    /// Creates a polynomial from its coefficients, starting with the constant term.
+    pub fn new(mut coefficients: Vec<f64>) -> Self {
+        while coefficients.last() == Some(&0.0) {
+            coefficients.pop();
+        }
+        Polynomial { coefficients }
+    }
+
+    /// Returns the coefficients, starting with the constant term.
+    pub fn coefficients(&self) -> &[f64] {
+        &self.coefficients
+    }
+
+    /// Returns the degree, or `None` for the zero polynomial.
+    pub fn degree(&self) -> Option<usize> {
+        self.coefficients.len().checked_sub(1)
+    }
+
+    /// Evaluates the polynomial at `x` with Horner's method, using one multiplication and one
+    /// addition per coefficient.
+    pub fn evaluate(&self, x: f64) -> f64 {
+        self.coefficients
+            .iter()
+            .rev()
+            .fold(0.0, |acc, &c| acc * x + c)
+    }
+
+    /// Returns the derivative of the polynomial.
+    pub fn derivative(&self) -> Polynomial {
+        Polynomial::new(
+            self.coefficients
+                .iter()
+                .enumerate()
+                .skip(1)
+                .map(|(i, &c)| c * i as f64)
+                .collect(),
+        )
+    }
+
+    /// Finds the real roots in `[a, b]`.
+    ///
+    /// The interval is divided into `steps` equal parts, and every part on whose ends the
+    /// polynomial changes sign is narrowed down by bisection until it is as small as floating
+    /// point allows. Roots at which the sign does not change, such as the double root of `x^2`,
+    /// are only found if they happen to lie exactly on the end of a part, and roots closer
+    /// together than `(b - a) / steps` may be missed.
+    ///
+    /// Returns the roots found in ascending order.
+    pub fn real_roots_in(&self, a: f64, b: f64, steps: usize) -> Vec<f64> {
+        let mut roots = Vec::new();
+        if self.coefficients.is_empty() || steps == 0 {
+            return roots;
+        }
+        let width = (b - a) / steps as f64;
+        let mut left = a;
+        let mut f_left = self.evaluate(left);
+        if f_left == 0.0 {
+            roots.push(left);
+        }
+        for i in 1..=steps {
+            let right = if i == steps { b } else { a + width * i as f64 };
+            let f_right = self.evaluate(right);
+            if f_right == 0.0 {
+                roots.push(right);
+            } else if f_left != 0.0 && (f_left < 0.0) != (f_right < 0.0) {
+                roots.push(self.bisect(left, right, f_left));
+            }
+            left = right;
+            f_left = f_right;
+        }
+        roots
+    }
+
+    /// Narrows down a root in `[lo, hi]`, given that the polynomial has opposite signs on the
+    /// ends and takes the value `f_lo` at `lo`.
+    fn bisect(&self, mut lo: f64, mut hi: f64, f_lo: f64) -> f64 {
+        for _ in 0..200 {
+            let mid = lo + (hi - lo) / 2.0;
+            if mid <= lo || mid >= hi {
+                break;
+            }
+            let f_mid = self.evaluate(mid);
+            if f_mid == 0.0 {
+                return mid;
+            }
+            if (f_mid < 0.0) == (f_lo < 0.0) {
+                lo = mid;
+            } else {
+                hi = mid;
+            }
+        }
+        lo + (hi - lo) / 2.0
+    }
+}
+
+impl Add for &Polynomial {
+    type Output = Polynomial;
+
+    fn add(self, other: &Polynomial) -> Polynomial {
+        let (longer, shorter) = if self.coefficients.len() >= other.coefficients.len() {
+            (self, other)
+        } else {
+            (other, self)
+        };
+        let mut coefficients = longer.coefficients.clone();
+        for (c, &d) in coefficients.iter_mut().zip(&shorter.coefficients) {
+            *c += d;
+        }
+        Polynomial::new(coefficients)
+    }
+}
+
+impl Mul for &Polynomial {
+    type Output = Polynomial;
+
+    fn mul(self, other: &Polynomial) -> Polynomial {
+        if self.coefficients.is_empty() || other.coefficients.is_empty() {
+            return Polynomial::new(Vec::new());
+        }
+        let mut coefficients = vec![0.0; self.coefficients.len() + other.coefficients.len() - 1];
+        for (i, &c) in self.coefficients.iter().enumerate() {
+            for (j, &d) in other.coefficients.iter().enumerate() {
+                coefficients[i + j] += c * d;
+            }
+        }
+        Polynomial::new(coefficients)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Polynomial;
+
+    fn x_squared_minus_one() -> Polynomial {
+        Polynomial::new(vec![-1.0, 0.0, 1.0])
+    }
+
+    #[test]
+    fn evaluate() {
+        let p = x_squared_minus_one();
+        let points = [
+            (-2.0, 3.0),
+            (-1.0, 0.0),
+            (0.0, -1.0),
+            (0.5, -0.75),
+            (1.0, 0.0),
+            (3.0, 8.0),
+        ];
+        for &(x, y) in points.iter() {
+            assert_eq!(p.evaluate(x), y);
+        }
+        assert_eq!(Polynomial::new(vec![]).evaluate(5.0), 0.0);
+    }
+
+    #[test]
+    fn derivative() {
+        let p = x_squared_minus_one();
+        assert_eq!(p.derivative(), Polynomial::new(vec![0.0, 2.0]));
+        assert_eq!(p.derivative().derivative(), Polynomial::new(vec![2.0]));
+        assert_eq!(p.derivative().derivative().derivative().degree(), None);
+    }
+
+    #[test]
+    fn arithmetic() {
+        let p = x_squared_minus_one();
+        let q = Polynomial::new(vec![1.0, 1.0]);
+        assert_eq!(&p + &q, Polynomial::new(vec![0.0, 1.0, 1.0]));
+        assert_eq!(&q + &p, &p + &q);
+        // Cancelling leading terms lowers the degree
+        let r = Polynomial::new(vec![0.0, 0.0, -1.0]);
+        assert_eq!((&p + &r).degree(), Some(0));
+        assert_eq!(&p * &q, Polynomial::new(vec![-1.0, -1.0, 1.0, 1.0]));
+        assert_eq!((&p * &Polynomial::new(vec![])).degree(), None);
+    }
+
+    #[test]
+    fn real_roots() {
+        let roots = x_squared_minus_one().real_roots_in(-2.0, 2.0, 7);
+        assert_eq!(roots.len(), 2);
+        assert!((roots[0] + 1.0).abs() < 1e-12);
+        assert!((roots[1] - 1.0).abs() < 1e-12);
+
+        // Roots lying exactly on the grid are reported once
+        assert_eq!(
+            x_squared_minus_one().real_roots_in(-2.0, 2.0, 4),
+            vec![-1.0, 1.0]
+        );
+
+        // (x - 0.5)(x + 3)(x - 2.25)
+        let p = &(&Polynomial::new(vec![-0.5, 1.0]) * &Polynomial::new(vec![3.0, 1.0]))
+            * &Polynomial::new(vec![-2.25, 1.0]);
+        let roots = p.real_roots_in(-10.0, 10.0, 100);
+        let expected = [-3.0, 0.5, 2.25];
+        assert_eq!(roots.len(), 3);
+        for (root, expected) in roots.iter().zip(expected.iter()) {
+            assert!((root - expected).abs() < 1e-9);
+        }
+        assert!(Polynomial::new(vec![1.0, 0.0, 1.0])
+            .real_roots_in(-5.0, 5.0, 10)
+            .is_empty());
+    }
+}
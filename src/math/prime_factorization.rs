@@ -0,0 +1,62 @@
+// Factorizes a number into `(prime, exponent)` pairs in increasing order of the primes,
+// using trial division up to the square root of the remaining cofactor.
+pub fn prime_factorization(n: u64) -> Vec<(u64, u32)> {
+    let mut factors = Vec::new();
+    if n == 0 {
+        return factors;
+    }
+    let mut n = n;
+    let mut i = 2;
+    while i <= n / i {
+        if n.is_multiple_of(i) {
+            let mut exponent = 0;
+            while n.is_multiple_of(i) {
+                n /= i;
+                exponent += 1;
+            }
+            factors.push((i, exponent));
+        }
+        i += if i == 2 { 1 } else { 2 };
+    }
+    // Whatever remains has no factor below its square root, so it is prime
+    if n > 1 {
+        factors.push((n, 1));
+    }
+    factors
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn composite() {
+        assert_eq!(prime_factorization(360), vec![(2, 3), (3, 2), (5, 1)]);
+        assert_eq!(prime_factorization(2560), vec![(2, 9), (5, 1)]);
+        assert_eq!(prime_factorization(100001), vec![(11, 1), (9091, 1)]);
+    }
+
+    #[test]
+    fn prime() {
+        assert_eq!(prime_factorization(2), vec![(2, 1)]);
+        assert_eq!(prime_factorization(2003), vec![(2003, 1)]);
+        assert_eq!(prime_factorization(1_000_000_007), vec![(1_000_000_007, 1)]);
+    }
+
+    #[test]
+    fn trivial() {
+        assert_eq!(prime_factorization(1), vec![]);
+        assert_eq!(prime_factorization(0), vec![]);
+    }
+
+    #[test]
+    fn reconstructs() {
+        for n in 1..1000u64 {
+            let product: u64 = prime_factorization(n)
+                .iter()
+                .map(|&(p, e)| p.pow(e))
+                .product();
+            assert_eq!(product, n);
+        }
+    }
+}
@@ -20,6 +20,23 @@ pub fn simpson_integration<F: Fn(f64) -> f64>(
     result
 }
 
+/// Approximates the integral of `f` over `[a, b]` with the composite Simpson's rule.
+///
+/// The interval is split into `n` subintervals, and `f` is replaced by a parabola over each
+/// consecutive pair of them. This is exact for polynomials up to degree three, and the error
+/// shrinks like O(h^4) in the subinterval width h for smooth functions.
+///
+/// # Panics
+///
+/// Panics if `n` is zero or odd, since the subintervals have to pair up.
+pub fn simpson<F: Fn(f64) -> f64>(f: F, a: f64, b: f64, n: usize) -> f64 {
+    assert!(
+        n > 0 && n.is_multiple_of(2),
+        "the number of subintervals must be positive and even"
+    );
+    simpson_integration(a, b, n as u64, f)
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -51,4 +68,28 @@ mod tests {
         let expected = 2.0 * PI * 5.0;
         assert!(almost_equal(result, expected, EPSILON));
     }
+
+    #[test]
+    fn simpson_rule() {
+        use std::f64::consts::PI;
+        // Exact up to rounding for polynomials of degree three at most
+        assert!(almost_equal(
+            simpson(|x| x * x, 0.0, 1.0, 2),
+            1.0 / 3.0,
+            EPSILON
+        ));
+        assert!(almost_equal(
+            simpson(|x| x.powi(3), -1.0, 2.0, 4),
+            3.75,
+            EPSILON
+        ));
+        // The error for sin over [0, pi] with 100 subintervals is about 1e-8
+        assert!(almost_equal(simpson(f64::sin, 0.0, PI, 100), 2.0, 1e-7));
+    }
+
+    #[test]
+    #[should_panic(expected = "the number of subintervals must be positive and even")]
+    fn simpson_rejects_odd_subintervals() {
+        simpson(|x| x, 0.0, 1.0, 5);
+    }
 }
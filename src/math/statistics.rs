@@ -0,0 +1,160 @@
+use crate::searching::kth_smallest;
+
+/// Returns the arithmetic mean of `data`.
+///
+/// # Panics
+///
+/// Panics if `data` is empty. Use `checked_mean` to get `None` instead.
+pub fn mean(data: &[f64]) -> f64 {
+    checked_mean(data).expect("cannot take the mean of no values")
+}
+
+/// Returns the arithmetic mean of `data`, or `None` if it is empty.
+pub fn checked_mean(data: &[f64]) -> Option<f64> {
+    if data.is_empty() {
+        return None;
+    }
+    Some(data.iter().sum::<f64>() / data.len() as f64)
+}
+
+/// Sums the squared deviations from the mean, and divides by the number of values minus
+/// `correction`.
+fn variance(data: &[f64], correction: usize) -> Option<f64> {
+    if data.len() <= correction {
+        return None;
+    }
+    let mean = checked_mean(data)?;
+    let squares: f64 = data.iter().map(|x| (x - mean) * (x - mean)).sum();
+    Some(squares / (data.len() - correction) as f64)
+}
+
+/// Returns the variance of `data` as a whole population, or `None` if it is empty.
+///
+/// This is the mean of the squared deviations from the mean.
+pub fn population_variance(data: &[f64]) -> Option<f64> {
+    variance(data, 0)
+}
+
+/// Returns the variance of `data` as a sample of a larger population, or `None` if it holds fewer
+/// than two values.
+///
+/// The squared deviations are divided by `n - 1` instead of `n` (Bessel's correction), which
+/// makes this an unbiased estimate of the population variance.
+pub fn sample_variance(data: &[f64]) -> Option<f64> {
+    variance(data, 1)
+}
+
+/// Returns the standard deviation of `data` as a whole population, or `None` if it is empty.
+pub fn population_std_dev(data: &[f64]) -> Option<f64> {
+    population_variance(data).map(f64::sqrt)
+}
+
+/// Returns the standard deviation of `data` as a sample, or `None` if it holds fewer than two
+/// values.
+pub fn sample_std_dev(data: &[f64]) -> Option<f64> {
+    sample_variance(data).map(f64::sqrt)
+}
+
+/// Returns the median of `data`, or `None` if it is empty.
+///
+/// For an even number of values this is the mean of the two middle ones. The middle values are
+/// found with quickselect in O(n) on average instead of sorting, which reorders `data`.
+pub fn median(data: &mut [f64]) -> Option<f64> {
+    percentile(data, 50.0)
+}
+
+/// Returns the `p`-th percentile of `data`, or `None` if it is empty.
+///
+/// The percentile lies at position `(n - 1) * p / 100` of the sorted values, interpolating
+/// linearly between the two neighbouring values when that position is fractional. Thus the 0th
+/// percentile is the minimum, the 100th the maximum and the 50th the median. The neighbouring
+/// values are found with quickselect in O(n) on average, which reorders `data`. Values must not
+/// be NaN.
+///
+/// # Panics
+///
+/// Panics if `p` lies outside of `[0, 100]`.
+pub fn percentile(data: &mut [f64], p: f64) -> Option<f64> {
+    assert!(
+        (0.0..=100.0).contains(&p),
+        "percentile must lie between 0 and 100"
+    );
+    if data.is_empty() {
+        return None;
+    }
+    let position = (data.len() - 1) as f64 * p / 100.0;
+    let below = position.floor() as usize;
+    let fraction = position - below as f64;
+    // kth_smallest counts from 1
+    let lower = kth_smallest(data, below + 1)?;
+    if fraction == 0.0 {
+        return Some(lower);
+    }
+    let upper = kth_smallest(data, below + 2)?;
+    Some(lower + (upper - lower) * fraction)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DATA: [f64; 8] = [4.0, 9.0, 2.0, 5.0, 4.0, 7.0, 4.0, 5.0];
+
+    fn assert_close(actual: Option<f64>, expected: f64) {
+        let actual = actual.unwrap();
+        assert!(
+            (actual - expected).abs() < 1e-12,
+            "{} != {}",
+            actual,
+            expected
+        );
+    }
+
+    #[test]
+    fn mean_and_variance() {
+        assert_close(Some(mean(&DATA)), 5.0);
+        assert_close(checked_mean(&DATA), 5.0);
+        assert_close(population_variance(&DATA), 4.0);
+        assert_close(population_std_dev(&DATA), 2.0);
+        assert_close(sample_variance(&DATA), 32.0 / 7.0);
+        assert_close(sample_std_dev(&DATA), (32.0f64 / 7.0).sqrt());
+        assert_close(population_variance(&[3.0]), 0.0);
+    }
+
+    #[test]
+    fn median_and_percentiles() {
+        let mut data = DATA;
+        assert_close(median(&mut data), 4.5);
+        assert_close(median(&mut [3.0, 1.0, 2.0]), 2.0);
+        assert_close(percentile(&mut data, 50.0), 4.5);
+        assert_close(percentile(&mut data, 0.0), 2.0);
+        assert_close(percentile(&mut data, 25.0), 4.0);
+        assert_close(percentile(&mut data, 90.0), 7.6);
+        assert_close(percentile(&mut data, 100.0), 9.0);
+        // Only the order of the values changed
+        data.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        assert_eq!(data, [2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0]);
+    }
+
+    #[test]
+    fn empty_input() {
+        assert_eq!(checked_mean(&[]), None);
+        assert_eq!(population_variance(&[]), None);
+        assert_eq!(sample_variance(&[1.0]), None);
+        assert_eq!(sample_std_dev(&[]), None);
+        assert_eq!(median(&mut []), None);
+        assert_eq!(percentile(&mut [], 10.0), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "percentile must lie between 0 and 100")]
+    fn percentile_out_of_range() {
+        percentile(&mut [1.0, 2.0], 101.0);
+    }
+
+    #[test]
+    #[should_panic(expected = "cannot take the mean of no values")]
+    fn mean_of_nothing() {
+        mean(&[]);
+    }
+}
@@ -1,6 +1,13 @@
 use std::cmp::min;
 use std::cmp::Ordering;
 
+/// Searches a sorted slice for `item` by narrowing the interval with Fibonacci numbers.
+///
+/// The split points are found by subtracting consecutive Fibonacci numbers instead of halving,
+/// so only additions and subtractions are needed, and successive probes lie closer together
+/// than with binary search. Takes O(log n) comparisons.
+///
+/// Returns the index of an element equal to `item`, or `None` if there is none.
 pub fn fibonacci_search<T: Ord>(item: &T, arr: &[T]) -> Option<usize> {
     let len = arr.len();
     if len == 0 {
@@ -42,6 +49,8 @@ pub fn fibonacci_search<T: Ord>(item: &T, arr: &[T]) -> Option<usize> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::math::PCG32;
+    use crate::searching::binary_search;
 
     #[test]
     fn empty() {
@@ -81,4 +90,30 @@ mod tests {
         let index = fibonacci_search(&5, &vec![1, 2, 3, 4]);
         assert_eq!(index, None);
     }
+
+    #[test]
+    fn agrees_with_binary_search() {
+        let mut rng = PCG32::new_default(61);
+        for len in 0..80 {
+            let mut arr: Vec<u32> = (0..len).map(|_| rng.get_u32() % 100).collect();
+            arr.sort_unstable();
+            arr.dedup();
+            // Every element, both ends, and values in between or outside that are absent
+            for item in 0..=101 {
+                let fibonacci = fibonacci_search(&item, &arr);
+                assert_eq!(fibonacci, arr.binary_search(&item).ok(), "{:?}", arr);
+                assert_eq!(fibonacci, binary_search(&item, &arr));
+            }
+        }
+    }
+
+    #[test]
+    fn duplicates() {
+        let arr = [1, 3, 3, 3, 3, 5, 8, 8];
+        for &item in arr.iter() {
+            let index = fibonacci_search(&item, &arr).unwrap();
+            assert_eq!(arr[index], item);
+        }
+        assert_eq!(fibonacci_search(&4, &arr), None);
+    }
 }
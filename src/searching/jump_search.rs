@@ -1,16 +1,21 @@
 use std::cmp::min;
 
+/// Searches a sorted slice by jumping ahead in blocks of `floor(sqrt(n))` elements until a block
+/// whose last element is not smaller than `item` is found, then scanning that block linearly.
+///
+/// Takes O(sqrt(n)) comparisons. Returns the index of the first element equal to `item`.
 pub fn jump_search<T: Ord>(item: &T, arr: &[T]) -> Option<usize> {
     let len = arr.len();
     if len == 0 {
         return None;
     }
-    let mut step = (len as f64).sqrt() as usize;
+    let block = (len as f64).sqrt() as usize;
+    let mut step = block;
     let mut prev = 0;
 
     while &arr[min(len, step) - 1] < item {
         prev = step;
-        step += (len as f64).sqrt() as usize;
+        step += block;
         if prev >= len {
             return None;
         }
@@ -30,6 +35,8 @@ pub fn jump_search<T: Ord>(item: &T, arr: &[T]) -> Option<usize> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::math::PCG32;
+    use crate::searching::binary_search;
 
     #[test]
     fn empty() {
@@ -69,4 +76,33 @@ mod tests {
         let index = jump_search(&5, &vec![1, 2, 3, 4]);
         assert_eq!(index, None);
     }
+
+    #[test]
+    fn first_and_last() {
+        let arr: Vec<i32> = (0..50).map(|x| x * 3).collect();
+        assert_eq!(jump_search(&0, &arr), Some(0));
+        assert_eq!(jump_search(&147, &arr), Some(49));
+        assert_eq!(jump_search(&-1, &arr), None);
+        assert_eq!(jump_search(&148, &arr), None);
+        assert_eq!(jump_search(&76, &arr), None);
+    }
+
+    #[test]
+    fn first_of_duplicates() {
+        let arr = [1, 2, 2, 2, 2, 2, 2, 3];
+        assert_eq!(jump_search(&2, &arr), Some(1));
+    }
+
+    #[test]
+    fn matches_binary_search() {
+        let mut rng = PCG32::new_default(42);
+        for len in 0..60 {
+            let mut arr: Vec<u32> = (0..len).map(|_| rng.get_u32() % 1000).collect();
+            arr.sort_unstable();
+            arr.dedup();
+            for item in 0..1000 {
+                assert_eq!(jump_search(&item, &arr), binary_search(&item, &arr));
+            }
+        }
+    }
 }
@@ -8,6 +8,7 @@ mod kth_smallest;
 mod kth_smallest_heap;
 mod linear_search;
 mod quick_select;
+mod saddleback_search;
 mod ternary_search;
 mod ternary_search_min_max;
 mod ternary_search_min_max_recursive;
@@ -22,6 +23,7 @@ pub use self::kth_smallest::kth_smallest;
 pub use self::kth_smallest_heap::kth_smallest_heap;
 pub use self::linear_search::linear_search;
 pub use self::quick_select::quick_select;
+pub use self::saddleback_search::search_sorted_matrix;
 pub use self::ternary_search::ternary_search;
 pub use self::ternary_search_min_max::ternary_search_max;
 pub use self::ternary_search_min_max::ternary_search_min;
@@ -0,0 +1,79 @@
+use std::cmp::Ordering;
+
+/// Searches a matrix whose rows and columns are sorted in ascending order.
+///
+/// Starts in the top-right corner. If the current element is larger than `target`, so is every
+/// element below it, and the column is dropped; if it is smaller, so is every element to its left,
+/// and the row is dropped. Each step drops a row or a column, so this takes O(m + n) comparisons.
+///
+/// Rows may have different lengths as long as every column is sorted over the rows that reach
+/// it. Returns the `(row, column)` of an element equal to `target`.
+pub fn search_sorted_matrix<T: Ord>(matrix: &[Vec<T>], target: &T) -> Option<(usize, usize)> {
+    // Columns at or past `bound` are known to hold only elements larger than `target`
+    let mut bound = usize::MAX;
+    for (row, values) in matrix.iter().enumerate() {
+        loop {
+            let col = bound.min(values.len());
+            if col == 0 {
+                break;
+            }
+            match values[col - 1].cmp(target) {
+                Ordering::Equal => return Some((row, col - 1)),
+                Ordering::Greater => bound = col - 1,
+                Ordering::Less => break,
+            }
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> Vec<Vec<i32>> {
+        vec![
+            vec![1, 4, 7, 11, 15],
+            vec![2, 5, 8, 12, 19],
+            vec![3, 6, 9, 16, 22],
+            vec![10, 13, 14, 17, 24],
+            vec![18, 21, 23, 26, 30],
+        ]
+    }
+
+    #[test]
+    fn finds_every_element() {
+        let matrix = sample();
+        assert_eq!(search_sorted_matrix(&matrix, &9), Some((2, 2)));
+        for (r, row) in matrix.iter().enumerate() {
+            for (c, value) in row.iter().enumerate() {
+                assert_eq!(search_sorted_matrix(&matrix, value), Some((r, c)));
+            }
+        }
+    }
+
+    #[test]
+    fn missing_elements() {
+        let matrix = sample();
+        for &target in [0, 20, 25, 31, -5].iter() {
+            assert_eq!(search_sorted_matrix(&matrix, &target), None);
+        }
+    }
+
+    #[test]
+    fn ragged_and_empty() {
+        // A staircase shape with an empty row in the middle
+        let matrix = vec![vec![1, 3, 5, 7], vec![2, 4], vec![], vec![6]];
+        assert_eq!(search_sorted_matrix(&matrix, &7), Some((0, 3)));
+        assert_eq!(search_sorted_matrix(&matrix, &4), Some((1, 1)));
+        assert_eq!(search_sorted_matrix(&matrix, &6), Some((3, 0)));
+        assert_eq!(search_sorted_matrix(&matrix, &8), None);
+
+        let matrix = vec![vec![1], vec![2, 5], vec![3, 6, 8]];
+        assert_eq!(search_sorted_matrix(&matrix, &8), Some((2, 2)));
+        assert_eq!(search_sorted_matrix(&matrix, &5), Some((1, 1)));
+
+        assert_eq!(search_sorted_matrix::<i32>(&[], &1), None);
+        assert_eq!(search_sorted_matrix(&[Vec::new(), Vec::new()], &1), None);
+    }
+}
@@ -1,38 +1,40 @@
 use crate::sorting::traits::Sorter;
 
-fn cocktail_shaker_sort<T: Ord>(arr: &mut [T]) {
-    let len = arr.len();
-
-    if len == 0 {
+/// Sorts a slice in place with cocktail shaker sort, a bidirectional bubble sort.
+///
+/// Each round bubbles the largest remaining element to the back, then the smallest to the front,
+/// so both ends of the unsorted window shrink by one. Sorting stops as soon as a pass makes no
+/// swaps, which makes it O(n) on already sorted input and O(n^2) in general.
+pub fn cocktail_shaker_sort<T: Ord>(arr: &mut [T]) {
+    if arr.is_empty() {
         return;
     }
+    let (mut start, mut end) = (0, arr.len() - 1);
 
-    loop {
+    while start < end {
         let mut swapped = false;
-
-        for i in 0..(len - 1).clamp(0, len) {
+        for i in start..end {
             if arr[i] > arr[i + 1] {
                 arr.swap(i, i + 1);
                 swapped = true;
             }
         }
-
         if !swapped {
             break;
         }
+        end -= 1;
 
         swapped = false;
-
-        for i in (0..(len - 1).clamp(0, len)).rev() {
+        for i in (start..end).rev() {
             if arr[i] > arr[i + 1] {
                 arr.swap(i, i + 1);
                 swapped = true;
             }
         }
-
         if !swapped {
             break;
         }
+        start += 1;
     }
 }
 
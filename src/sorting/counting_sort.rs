@@ -23,6 +23,11 @@ fn counting_sort<T: Ord + Copy + Default + Into<usize>>(arr: &[T]) -> Vec<T> {
     output
 }
 
+/// Counting sort counts the occurrences of every key, then places each element directly at the
+/// position following all smaller keys.
+/// Time complexity is O(n + k) for keys up to k
+/// The sort is stable, since the elements are placed back to front, starting from the last
+/// position of their key.
 pub struct CountingSort;
 
 impl<T> Sorter<T> for CountingSort
@@ -41,9 +46,15 @@ where
 
 #[cfg(test)]
 mod tests {
+    use crate::sorting::stability::is_stable;
     use crate::sorting::traits::Sorter;
     use crate::sorting::CountingSort;
 
     sorting_tests!(CountingSort::sort, counting_sort);
     sorting_tests!(CountingSort::sort_inplace, counting_sort, inplace);
+
+    #[test]
+    fn stable() {
+        assert!(is_stable::<CountingSort>());
+    }
 }
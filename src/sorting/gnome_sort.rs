@@ -1,11 +1,16 @@
 use crate::sorting::traits::Sorter;
 
-fn gnome_sort<T: Ord>(arr: &mut [T]) {
+/// Sorts a slice in place with gnome sort.
+///
+/// The gnome walks forward while neighbours are in order and swaps an out-of-order element
+/// backwards until it fits, then jumps straight back to where it left off. Equal elements are
+/// never swapped, so the sort is stable. Runs in O(n^2), or O(n) on sorted input.
+pub fn gnome_sort<T: Ord>(arr: &mut [T]) {
     let mut i: usize = 1;
     let mut j: usize = 2;
 
     while i < arr.len() {
-        if arr[i - 1] < arr[i] {
+        if arr[i - 1] <= arr[i] {
             i = j;
             j = i + 1;
         } else {
@@ -32,9 +37,35 @@ where
 
 #[cfg(test)]
 mod tests {
+    use super::gnome_sort;
     use crate::sorting::traits::Sorter;
     use crate::sorting::GnomeSort;
 
     sorting_tests!(GnomeSort::sort, gnome_sort);
     sorting_tests!(GnomeSort::sort_inplace, gnome_sort, inplace);
+
+    #[test]
+    fn stable_on_equal_keys() {
+        // Compared by key only, so the index records the original order of equal keys
+        #[derive(Debug, PartialEq, Eq)]
+        struct Item(u32, usize);
+        impl PartialOrd for Item {
+            fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+                Some(self.cmp(other))
+            }
+        }
+        impl Ord for Item {
+            fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+                self.0.cmp(&other.0)
+            }
+        }
+        let mut items: Vec<Item> = [3, 1, 3, 2, 1]
+            .iter()
+            .enumerate()
+            .map(|(i, &k)| Item(k, i))
+            .collect();
+        gnome_sort(&mut items);
+        let order: Vec<usize> = items.iter().map(|item| item.1).collect();
+        assert_eq!(order, vec![1, 4, 3, 0, 2]);
+    }
 }
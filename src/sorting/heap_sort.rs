@@ -43,6 +43,10 @@ fn siftdown<T: Ord>(array: &mut [T], mut root: usize, end: usize) {
     }
 }
 
+/// Heap sort builds a max-heap in place, then repeatedly swaps its root to the end of the array.
+/// Time complexity is O(n log n)
+/// Auxiliary space is O(1)
+/// The sort is not stable: moving elements through the heap can reorder equal elements.
 pub struct HeapSort;
 
 impl<T> Sorter<T> for HeapSort
@@ -56,9 +60,16 @@ where
 
 #[cfg(test)]
 mod tests {
+    use crate::sorting::stability::is_stable;
     use crate::sorting::traits::Sorter;
     use crate::sorting::HeapSort;
 
     sorting_tests!(HeapSort::sort, heap_sort);
     sorting_tests!(HeapSort::sort_inplace, heap_sort_inplace, inplace);
+
+    #[test]
+    fn unstable() {
+        // Documented as unstable: equal keys may be reordered
+        assert!(!is_stable::<HeapSort>());
+    }
 }
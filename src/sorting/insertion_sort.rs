@@ -18,6 +18,7 @@ fn insertion_sort<T: Ord>(arr: &mut [T]) {
 /// Values from the unsorted parts are placed in the correct position in the sorted part.
 /// Time complexity is O(N^2)
 /// Auxiliary space is O(1)
+/// The sort is stable, since elements only move past strictly greater ones.
 pub struct InsertionSort;
 
 impl<T> Sorter<T> for InsertionSort
@@ -31,9 +32,15 @@ where
 
 #[cfg(test)]
 mod tests {
+    use crate::sorting::stability::is_stable;
     use crate::sorting::traits::Sorter;
     use crate::sorting::InsertionSort;
 
     sorting_tests!(InsertionSort::sort, insertion_sort);
     sorting_tests!(InsertionSort::sort_inplace, insertion_sort, inplace);
+
+    #[test]
+    fn stable() {
+        assert!(is_stable::<InsertionSort>());
+    }
 }
@@ -36,6 +36,7 @@ fn merge<T: Ord + Copy>(left: &mut Vec<T>, right: &mut Vec<T>) -> Vec<T> {
 // The Merge Sort algorithm is a sorting algorithm that is based on the Divide and Conquer paradigm.
 // The Time complexity is `O(nlog(n))` where n is the length of the array.
 // Auxillary Space required is `O(n)` Since all the elements are copied to the auxillary space.
+// The sort is stable: on equal elements the merge takes from the left half first.
 pub struct MergeSort;
 
 impl<T> Sorter<T> for MergeSort
@@ -54,9 +55,15 @@ where
 
 #[cfg(test)]
 mod tests {
+    use crate::sorting::stability::is_stable;
     use crate::sorting::traits::Sorter;
     use crate::sorting::MergeSort;
 
     sorting_tests!(MergeSort::sort, merge_sort);
     sorting_tests!(MergeSort::sort_inplace, merge_sort, inplace);
+
+    #[test]
+    fn stable() {
+        assert!(is_stable::<MergeSort>());
+    }
 }
@@ -44,6 +44,8 @@ mod radix_sort;
 mod selection_sort;
 mod shell_sort;
 mod sleep_sort;
+#[cfg(test)]
+mod stability;
 mod stooge_sort;
 mod strand_sort;
 mod tim_sort;
@@ -58,12 +60,12 @@ pub use self::bogo_bogo_sort::BogoBogoSort;
 pub use self::bogo_sort::BogoSort;
 pub use self::bubble_sort::BubbleSort;
 pub use self::bucket_sort::BucketSort;
-pub use self::cocktail_shaker_sort::CocktailShakerSort;
+pub use self::cocktail_shaker_sort::{cocktail_shaker_sort, CocktailShakerSort};
 pub use self::comb_sort::CombSort;
 pub use self::counting_sort::CountingSort;
 pub use self::cycle_sort::CycleSort;
 pub use self::exchange_sort::ExchangeSort;
-pub use self::gnome_sort::GnomeSort;
+pub use self::gnome_sort::{gnome_sort, GnomeSort};
 pub use self::heap_sort::HeapSort;
 pub use self::insertion_sort::InsertionSort;
 pub use self::merge_sort::MergeSort;
@@ -73,7 +75,7 @@ pub use self::pigeonhole_sort::pigeonhole_sort;
 pub use self::quick_sort::QuickSort;
 pub use self::radix_sort::RadixSort;
 pub use self::selection_sort::SelectionSort;
-pub use self::shell_sort::ShellSort;
+pub use self::shell_sort::{shell_sort, shell_sort_default, ShellSort};
 pub use self::sleep_sort::sleep_sort;
 pub use self::stooge_sort::StoogeSort;
 pub use self::strand_sort::strand_sort;
@@ -43,6 +43,8 @@ fn quick_sort<T: Ord>(array: &mut [T]) {
 /// put x at its correct position in a sorted array and put all smaller elements (smaller than x) before x,
 /// and put all greater elements (greater than x) after x. All this should be done in linear time.
 /// Quicksort's  time complexity is O(n*logn) .
+/// The sort is not stable: partitioning swaps elements across long distances, which can reorder
+/// equal elements.
 pub struct QuickSort;
 
 impl<T> Sorter<T> for QuickSort
@@ -56,9 +58,16 @@ where
 
 #[cfg(test)]
 mod tests {
+    use crate::sorting::stability::is_stable;
     use crate::sorting::traits::Sorter;
     use crate::sorting::QuickSort;
 
     sorting_tests!(QuickSort::sort, quick_sort);
     sorting_tests!(QuickSort::sort_inplace, quick_sort, inplace);
+
+    #[test]
+    fn unstable() {
+        // Documented as unstable: equal keys may be reordered
+        assert!(!is_stable::<QuickSort>());
+    }
 }
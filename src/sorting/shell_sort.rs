@@ -1,27 +1,43 @@
 use crate::sorting::traits::Sorter;
 
-pub fn shell_sort<T: Ord + Copy>(values: &mut [T]) {
+/// The best known gap sequence for Shell sort, found empirically by Marcin Ciura.
+const CIURA_GAPS: [usize; 8] = [701, 301, 132, 57, 23, 10, 4, 1];
+
+/// Sorts `values` with Shell sort using the given gap sequence.
+///
+/// The gaps are used in the order given and should be descending. A sequence that does not end
+/// with a gap of 1 is corrected by running a final pass with gap 1, which guarantees the result is
+/// sorted. Panics if a gap is 0.
+pub fn shell_sort<T: Ord>(values: &mut [T], gaps: &[usize]) {
+    assert!(!gaps.contains(&0), "gap sequence must not contain 0");
+
     // shell sort works by swiping the value at a given gap and decreasing the gap to 1
-    fn insertion<T: Ord + Copy>(values: &mut [T], start: usize, gap: usize) {
-        for i in ((start + gap)..values.len()).step_by(gap) {
-            let val_current = values[i];
+    fn gapped_insertion<T: Ord>(values: &mut [T], gap: usize) {
+        for i in gap..values.len() {
             let mut pos = i;
-            // make swaps
-            while pos >= gap && values[pos - gap] > val_current {
-                values[pos] = values[pos - gap];
+            while pos >= gap && values[pos - gap] > values[pos] {
+                values.swap(pos - gap, pos);
                 pos -= gap;
             }
-            values[pos] = val_current;
         }
     }
 
-    let mut count_sublist = values.len() / 2; // makes gap as long as half of the array
-    while count_sublist > 0 {
-        for pos_start in 0..count_sublist {
-            insertion(values, pos_start, count_sublist);
-        }
-        count_sublist /= 2; // makes gap as half of previous
+    for &gap in gaps {
+        gapped_insertion(values, gap);
+    }
+    if gaps.last() != Some(&1) {
+        gapped_insertion(values, 1);
+    }
+}
+
+/// Sorts `values` with Shell sort using the Ciura gap sequence, extended by a factor of 2.25 for
+/// slices longer than its largest gap.
+pub fn shell_sort_default<T: Ord>(values: &mut [T]) {
+    let mut gaps = CIURA_GAPS.to_vec();
+    while gaps[0] * 9 / 4 < values.len() {
+        gaps.insert(0, gaps[0] * 9 / 4);
     }
+    shell_sort(values, &gaps);
 }
 
 pub struct ShellSort;
@@ -31,15 +47,60 @@ where
     T: Ord + Copy,
 {
     fn sort_inplace(array: &mut [T]) {
-        shell_sort(array);
+        shell_sort_default(array);
     }
 }
 
 #[cfg(test)]
 mod test {
+    use super::{shell_sort, shell_sort_default};
+    use crate::math::PCG32;
     use crate::sorting::traits::Sorter;
     use crate::sorting::ShellSort;
 
     sorting_tests!(ShellSort::sort, shell_sort);
     sorting_tests!(ShellSort::sort_inplace, shell_sort, inplace);
+
+    fn check_against_std(mut values: Vec<u32>, gaps: &[usize]) {
+        let mut expected = values.clone();
+        expected.sort();
+        shell_sort(&mut values, gaps);
+        assert_eq!(values, expected);
+    }
+
+    #[test]
+    fn random_sorted_and_reverse() {
+        let mut rng = PCG32::new_default(2024);
+        let random: Vec<u32> = (0..2000).map(|_| rng.get_u32() % 500).collect();
+        let ascending: Vec<u32> = (0..2000).collect();
+        let descending: Vec<u32> = (0..2000).rev().collect();
+        for values in [random, ascending, descending].iter() {
+            check_against_std(values.clone(), &[701, 301, 132, 57, 23, 10, 4, 1]);
+            check_against_std(values.clone(), &[8, 4, 2, 1]);
+
+            let mut default = values.clone();
+            shell_sort_default(&mut default);
+            assert_sorted!(&default);
+        }
+    }
+
+    #[test]
+    fn missing_final_gap_is_corrected() {
+        let values: Vec<u32> = (0..100).rev().collect();
+        check_against_std(values.clone(), &[7, 3]);
+        check_against_std(values, &[]);
+    }
+
+    #[test]
+    #[should_panic(expected = "must not contain 0")]
+    fn zero_gap_is_rejected() {
+        shell_sort(&mut [3, 2, 1], &[2, 0, 1]);
+    }
+
+    #[test]
+    fn non_copy_values() {
+        let mut values = vec!["pear".to_string(), "apple".to_string(), "fig".to_string()];
+        shell_sort_default(&mut values);
+        assert_eq!(values, vec!["apple", "fig", "pear"]);
+    }
 }
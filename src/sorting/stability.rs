@@ -0,0 +1,79 @@
+//! Test helpers for checking whether a sort is stable, keeping equal keys in their input order.
+use crate::math::PCG32;
+use crate::sorting::traits::Sorter;
+use std::cmp::Ordering;
+
+/// An element that is compared by its key only and tagged with its original position.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Tagged {
+    pub key: usize,
+    pub index: usize,
+}
+
+impl PartialEq for Tagged {
+    fn eq(&self, other: &Self) -> bool {
+        self.key == other.key
+    }
+}
+
+impl Eq for Tagged {}
+
+impl PartialOrd for Tagged {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Tagged {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.key.cmp(&other.key)
+    }
+}
+
+/// Allows sorts over integer keys, such as counting sort, to sort tagged elements.
+impl From<Tagged> for usize {
+    fn from(tagged: Tagged) -> usize {
+        tagged.key
+    }
+}
+
+/// Tags every key with its position in `keys`.
+pub fn tag(keys: &[usize]) -> Vec<Tagged> {
+    keys.iter()
+        .enumerate()
+        .map(|(index, &key)| Tagged { key, index })
+        .collect()
+}
+
+/// Returns `true` if equal keys in `sorted` appear in the order of their original positions.
+pub fn preserves_order(sorted: &[Tagged]) -> bool {
+    sorted
+        .windows(2)
+        .all(|pair| pair[0].key != pair[1].key || pair[0].index < pair[1].index)
+}
+
+/// Sorts inputs full of equal keys with `S` and returns `true` if every result was stable.
+///
+/// Panics if some result is not sorted by key, so that an unstable result is never mistaken for
+/// a stable sort of the wrong order.
+pub fn is_stable<S: Sorter<Tagged>>() -> bool {
+    let mut rng = PCG32::new_default(17);
+    let random: Vec<usize> = (0..200).map(|_| rng.get_u32() as usize % 10).collect();
+    let inputs = vec![
+        vec![1, 1],
+        vec![3, 1, 3, 2, 1, 2, 3],
+        vec![5; 20],
+        (0..30).map(|i| 10 - i % 10).collect(),
+        random,
+    ];
+    let mut stable = true;
+    for keys in &inputs {
+        let sorted = S::sort(&tag(keys));
+        let mut expected = keys.clone();
+        expected.sort_unstable();
+        let actual: Vec<usize> = sorted.iter().map(|tagged| tagged.key).collect();
+        assert_eq!(actual, expected, "output is not sorted by key");
+        stable &= preserves_order(&sorted);
+    }
+    stable
+}
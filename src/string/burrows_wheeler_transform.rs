@@ -38,6 +38,92 @@ pub fn inv_burrows_wheeler_transform(input: (String, usize)) -> String {
     decoded
 }
 
+/// Sorts the cyclic rotations of `data`, returning their start positions in ascending order.
+///
+/// This is the suffix array of `data` with comparisons wrapping around at the end, built by
+/// prefix doubling: after round `k` the rotations are ranked by their first `2^k` bytes, and the
+/// ranks for twice the length follow from sorting the pairs of ranks of both halves. This takes
+/// O(n log^2 n), and stops early once all ranks are distinct.
+fn sorted_rotations(data: &[u8]) -> Vec<usize> {
+    let n = data.len();
+    let mut order: Vec<usize> = (0..n).collect();
+    let mut rank: Vec<usize> = data.iter().map(|&b| b as usize).collect();
+    let mut length = 1;
+    loop {
+        let key = |i: usize| (rank[i], rank[(i + length) % n]);
+        order.sort_by_key(|&i| key(i));
+        let mut next = vec![0; n];
+        for w in 1..n {
+            let (prev, cur) = (order[w - 1], order[w]);
+            next[cur] = next[prev] + usize::from(key(prev) != key(cur));
+        }
+        rank = next;
+        length *= 2;
+        if n == 0 || rank[order[n - 1]] == n - 1 || length >= n {
+            return order;
+        }
+    }
+}
+
+/// Computes the Burrows-Wheeler transform of `data`.
+///
+/// The transform is the last column of the table of all cyclic rotations of `data` in sorted
+/// order, which groups bytes that precede similar contexts into runs. The rotations are sorted
+/// with a suffix array over the cyclic string, in O(n log^2 n) instead of the O(n^2 log n) of
+/// sorting the rotations themselves.
+///
+/// Returns the transformed bytes and the row of `data` itself in the sorted table, which is
+/// needed by `bwt_inverse`.
+pub fn bwt_transform(data: &[u8]) -> (Vec<u8>, usize) {
+    let n = data.len();
+    let rotations = sorted_rotations(data);
+    let transformed = rotations.iter().map(|&i| data[(i + n - 1) % n]).collect();
+    let index = rotations.iter().position(|&i| i == 0).unwrap_or(0);
+    (transformed, index)
+}
+
+/// Inverts the Burrows-Wheeler transform, given the row `index` of the original data.
+///
+/// Sorting the last column yields the first column, and the k-th occurrence of a byte in the
+/// last column is the k-th occurrence of it in the first. This last-to-first mapping leads from
+/// each row to the row starting one position earlier, so following it from `index` reconstructs
+/// the data back to front in O(n).
+///
+/// # Panics
+///
+/// Panics if `transformed` is not empty and `index` is out of bounds.
+pub fn bwt_inverse(transformed: &[u8], index: usize) -> Vec<u8> {
+    let n = transformed.len();
+    if n == 0 {
+        return Vec::new();
+    }
+    assert!(index < n, "index {} out of bounds for length {}", index, n);
+    // The first row of the sorted table starting with each byte
+    let mut starts = [0; 256];
+    for &b in transformed {
+        starts[b as usize] += 1;
+    }
+    let mut total = 0;
+    for start in starts.iter_mut() {
+        let count = *start;
+        *start = total;
+        total += count;
+    }
+    let mut last_to_first = vec![0; n];
+    for (row, &b) in transformed.iter().enumerate() {
+        last_to_first[row] = starts[b as usize];
+        starts[b as usize] += 1;
+    }
+
+    let mut data = vec![0; n];
+    let mut row = index;
+    for position in (0..n).rev() {
+        data[position] = transformed[row];
+        row = last_to_first[row];
+    }
+    data
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -84,6 +170,41 @@ mod tests {
         );
     }
 
+    #[test]
+    fn bwt_round_trip() {
+        let inputs: [&[u8]; 8] = [
+            b"banana",
+            b"mississippi",
+            b"abracadabra abracadabra",
+            b"aaaaaaaa",
+            b"abababab",
+            b"x",
+            b"",
+            &[0, 255, 0, 255, 7, 0, 0, 3],
+        ];
+        for &input in inputs.iter() {
+            let (transformed, index) = bwt_transform(input);
+            assert_eq!(transformed.len(), input.len());
+            assert_eq!(bwt_inverse(&transformed, index), input);
+        }
+    }
+
+    #[test]
+    fn bwt_matches_rotation_table() {
+        assert_eq!(bwt_transform(b"banana"), (b"nnbaaa".to_vec(), 3));
+        for input in ["CARROT", "TOMATO", "THEALGORITHMS", "abcabcab"].iter() {
+            let bytes = input.as_bytes();
+            let mut table: Vec<Vec<u8>> = (0..bytes.len())
+                .map(|i| [&bytes[i..], &bytes[..i]].concat())
+                .collect();
+            table.sort();
+            let last: Vec<u8> = table.iter().map(|row| *row.last().unwrap()).collect();
+            let (transformed, index) = bwt_transform(bytes);
+            assert_eq!(transformed, last);
+            assert_eq!(table[index], bytes);
+        }
+    }
+
     #[test]
     fn empty() {
         assert_eq!(
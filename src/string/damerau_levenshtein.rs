@@ -0,0 +1,86 @@
+use std::cmp::min;
+
+/// Computes the Damerau-Levenshtein distance between two strings, in its optimal string
+/// alignment variant.
+///
+/// This is the minimum number of insertions, deletions, substitutions and transpositions of two
+/// adjacent characters needed to turn `a` into `b`, where no substring is edited more than once.
+/// Unlike plain Levenshtein distance, a swapped pair of characters such as `"ca"` and `"ac"` costs
+/// a single edit. The strings are compared by `char`.
+///
+/// # Complexity
+///
+/// - time complexity: O(nm),
+/// - space complexity: O(m),
+///
+/// where n and m are the number of chars in `a` and `b`.
+pub fn damerau_levenshtein(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    // Rows for prefixes of `a` of length i - 2, i - 1 and i
+    let mut before: Vec<usize> = Vec::new();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for i in 1..=a.len() {
+        let mut current = vec![i; b.len() + 1];
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            current[j] = min(
+                min(previous[j] + 1, current[j - 1] + 1),
+                previous[j - 1] + cost,
+            );
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                current[j] = min(current[j], before[j - 2] + 1);
+            }
+        }
+        before = previous;
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::damerau_levenshtein;
+    use crate::dynamic_programming::edit_distance;
+
+    #[test]
+    fn transposition_costs_one() {
+        assert_eq!(damerau_levenshtein("ca", "ac"), 1);
+        assert_eq!(edit_distance("ca", "ac"), 2);
+        assert_eq!(damerau_levenshtein("recieve", "receive"), 1);
+        assert_eq!(damerau_levenshtein("abcdef", "badcfe"), 3);
+    }
+
+    #[test]
+    fn no_substring_is_edited_twice() {
+        // The unrestricted distance is 2 (ca -> ac -> abc); optimal string alignment needs 3
+        assert_eq!(damerau_levenshtein("ca", "abc"), 3);
+    }
+
+    #[test]
+    fn empty_strings() {
+        assert_eq!(damerau_levenshtein("", ""), 0);
+        assert_eq!(damerau_levenshtein("abc", ""), 3);
+        assert_eq!(damerau_levenshtein("", "abcd"), 4);
+    }
+
+    #[test]
+    fn matches_levenshtein_without_transpositions() {
+        let pairs = [
+            ("kitten", "sitting"),
+            ("flaw", "lawn"),
+            ("intention", "execution"),
+            ("rust", "rust"),
+            ("abc", "xyz"),
+        ];
+        for &(a, b) in pairs.iter() {
+            assert_eq!(damerau_levenshtein(a, b), edit_distance(a, b) as usize);
+        }
+    }
+
+    #[test]
+    fn compares_chars() {
+        assert_eq!(damerau_levenshtein("héllo", "hlélo"), 1);
+        assert_eq!(damerau_levenshtein("日本語", "日語本"), 1);
+    }
+}
@@ -0,0 +1,147 @@
+use std::collections::BTreeMap;
+
+#[derive(Default)]
+struct TrieNode {
+    children: BTreeMap<char, TrieNode>,
+    is_word: bool,
+}
+
+/// Finds all dictionary words within Levenshtein distance `max_distance` of `query`.
+///
+/// The dictionary is stored in a trie which is walked depth-first while computing one row of the
+/// edit distance table per trie node, against the characters of `query`. Words sharing a prefix
+/// share the rows for that prefix, and a whole subtree is skipped as soon as every entry of a row
+/// exceeds `max_distance`, since the distance can only grow from there.
+///
+/// Returns each matching word once with its distance, ordered by distance and then alphabetically.
+pub fn fuzzy_match(
+    dictionary: &[String],
+    query: &str,
+    max_distance: usize,
+) -> Vec<(String, usize)> {
+    let mut root = TrieNode::default();
+    for word in dictionary {
+        let mut node = &mut root;
+        for c in word.chars() {
+            node = node.children.entry(c).or_default();
+        }
+        node.is_word = true;
+    }
+
+    let query: Vec<char> = query.chars().collect();
+    let first_row: Vec<usize> = (0..=query.len()).collect();
+    let mut matches = Vec::new();
+    if root.is_word && first_row[query.len()] <= max_distance {
+        matches.push((String::new(), first_row[query.len()]));
+    }
+    let mut prefix = String::new();
+    for (&c, child) in &root.children {
+        walk(
+            child,
+            c,
+            &query,
+            &first_row,
+            max_distance,
+            &mut prefix,
+            &mut matches,
+        );
+    }
+
+    matches.sort_by(|(a, da), (b, db)| da.cmp(db).then_with(|| a.cmp(b)));
+    matches
+}
+
+/// Computes the edit distance row for the trie node reached by appending `c` to `prefix`, records
+/// a match if the node ends a word, and recurses into the children unless the row is hopeless.
+fn walk(
+    node: &TrieNode,
+    c: char,
+    query: &[char],
+    previous_row: &[usize],
+    max_distance: usize,
+    prefix: &mut String,
+    matches: &mut Vec<(String, usize)>,
+) {
+    prefix.push(c);
+    let mut row = Vec::with_capacity(previous_row.len());
+    row.push(previous_row[0] + 1);
+    for j in 1..previous_row.len() {
+        let substitution = previous_row[j - 1] + usize::from(query[j - 1] != c);
+        let deletion = previous_row[j] + 1;
+        let insertion = row[j - 1] + 1;
+        row.push(substitution.min(deletion).min(insertion));
+    }
+
+    let distance = row[query.len()];
+    if node.is_word && distance <= max_distance {
+        matches.push((prefix.clone(), distance));
+    }
+    if row.iter().min().is_some_and(|&best| best <= max_distance) {
+        for (&next, child) in &node.children {
+            walk(child, next, query, &row, max_distance, prefix, matches);
+        }
+    }
+    prefix.pop();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dynamic_programming::edit_distance;
+
+    fn words(list: &[&str]) -> Vec<String> {
+        list.iter().map(|w| w.to_string()).collect()
+    }
+
+    #[test]
+    fn within_one_edit() {
+        let dictionary = words(&["cat", "car", "bat", "cart", "dog", "cast", "at", "act"]);
+        let matches = fuzzy_match(&dictionary, "cat", 1);
+        assert_eq!(
+            matches,
+            vec![
+                ("cat".to_string(), 0),
+                ("at".to_string(), 1),
+                ("bat".to_string(), 1),
+                ("car".to_string(), 1),
+                ("cart".to_string(), 1),
+                ("cast".to_string(), 1),
+            ]
+        );
+    }
+
+    #[test]
+    fn excludes_distant_words() {
+        let dictionary = words(&["cat", "car", "bat", "dog", "horse"]);
+        let matches = fuzzy_match(&dictionary, "cat", 1);
+        let found: Vec<&str> = matches.iter().map(|(w, _)| w.as_str()).collect();
+        assert_eq!(found, vec!["cat", "bat", "car"]);
+        assert!(fuzzy_match(&dictionary, "zebra", 2).is_empty());
+    }
+
+    #[test]
+    fn agrees_with_edit_distance() {
+        let dictionary = words(&[
+            "kitten", "sitting", "mitten", "kit", "knitting", "smitten", "", "bitten", "sit",
+        ]);
+        for max_distance in 0..5 {
+            let matches = fuzzy_match(&dictionary, "kitten", max_distance);
+            let mut expected: Vec<(String, usize)> = dictionary
+                .iter()
+                .map(|w| (w.clone(), edit_distance(w, "kitten") as usize))
+                .filter(|&(_, d)| d <= max_distance)
+                .collect();
+            expected.sort_by(|(a, da), (b, db)| da.cmp(db).then_with(|| a.cmp(b)));
+            assert_eq!(matches, expected);
+        }
+    }
+
+    #[test]
+    fn duplicates_are_reported_once() {
+        let dictionary = words(&["cat", "cat", "cot"]);
+        assert_eq!(
+            fuzzy_match(&dictionary, "cat", 0),
+            vec![("cat".to_string(), 0)]
+        );
+    }
+}
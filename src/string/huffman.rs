@@ -0,0 +1,250 @@
+use crate::general::HuffmanDictionary;
+use std::convert::TryFrom;
+
+/// A Huffman code tree over bytes, built from the byte frequencies of some data.
+///
+/// The codes come from a `HuffmanDictionary`, which merges the subtrees in the same order for the
+/// same frequency table, so building from that table always yields the same codes. This allows
+/// the tree to be serialized compactly as that table.
+pub struct HuffmanTree {
+    /// The number of occurrences of every byte that occurs at all, sorted by byte.
+    frequencies: Vec<(u8, u64)>,
+    /// The code, or `None` if no bytes occur.
+    dictionary: Option<HuffmanDictionary<u8>>,
+}
+
+impl HuffmanTree {
+    /// Builds the tree, or returns `None` if the frequencies add up to more than `u64::MAX`, the
+    /// code would need more than 64 bits for a byte, or the encoded length overflows `usize`.
+    fn from_frequencies(frequencies: Vec<(u8, u64)>) -> Option<Self> {
+        let dictionary = if frequencies.is_empty() {
+            None
+        } else {
+            Some(HuffmanDictionary::try_new(&frequencies)?)
+        };
+        let tree = HuffmanTree {
+            frequencies,
+            dictionary,
+        };
+        tree.checked_bit_len()?;
+        Some(tree)
+    }
+
+    /// Returns the code of every byte as a sequence of bits, or an empty code for absent bytes.
+    ///
+    /// If only one distinct byte occurs, it is encoded as a single `0` bit.
+    fn codes(&self) -> Vec<Vec<bool>> {
+        let mut codes = vec![Vec::new(); 256];
+        if let Some(dictionary) = &self.dictionary {
+            for (&symbol, code) in &dictionary.alphabet {
+                codes[symbol as usize] = (0..code.bits.max(1))
+                    .map(|i| (code.value >> i) & 1 == 1)
+                    .collect();
+            }
+        }
+        codes
+    }
+
+    /// Sums the code lengths of all bytes of the data, or returns `None` on overflow.
+    fn checked_bit_len(&self) -> Option<usize> {
+        let codes = self.codes();
+        self.frequencies
+            .iter()
+            .try_fold(0usize, |bits, &(symbol, frequency)| {
+                let frequency = usize::try_from(frequency).ok()?;
+                bits.checked_add(codes[symbol as usize].len().checked_mul(frequency)?)
+            })
+    }
+
+    /// Returns the number of bits needed to encode the data the tree was built from.
+    pub fn bit_len(&self) -> usize {
+        self.checked_bit_len()
+            .expect("the length is checked when the tree is built")
+    }
+
+    /// Serializes the tree as its frequency table.
+    ///
+    /// The output is a sequence of 9-byte records: a byte followed by its frequency as a
+    /// little-endian `u64`.
+    pub fn serialize(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(9 * self.frequencies.len());
+        for &(symbol, frequency) in &self.frequencies {
+            bytes.push(symbol);
+            bytes.extend_from_slice(&frequency.to_le_bytes());
+        }
+        bytes
+    }
+
+    /// Rebuilds a tree from the output of `serialize`.
+    ///
+    /// Returns `None` if the input is truncated, lists the same byte twice or out of order,
+    /// contains a zero frequency, or has frequencies too large to build a code from: a total above
+    /// `u64::MAX`, a code longer than 64 bits, or an encoded length above `usize::MAX`.
+    pub fn deserialize(bytes: &[u8]) -> Option<Self> {
+        if !bytes.len().is_multiple_of(9) {
+            return None;
+        }
+        let mut frequencies: Vec<(u8, u64)> = Vec::with_capacity(bytes.len() / 9);
+        for record in bytes.chunks(9) {
+            let mut frequency = [0; 8];
+            frequency.copy_from_slice(&record[1..]);
+            let frequency = u64::from_le_bytes(frequency);
+            let ordered = frequencies.last().is_none_or(|&(last, _)| last < record[0]);
+            if frequency == 0 || !ordered {
+                return None;
+            }
+            frequencies.push((record[0], frequency));
+        }
+        HuffmanTree::from_frequencies(frequencies)
+    }
+}
+
+/// Compresses `data` with a Huffman code built from its byte frequencies.
+///
+/// Frequent bytes receive shorter codes, and no code is a prefix of another, so the concatenated
+/// codes can be decoded unambiguously. The codes are packed into bytes starting from the most
+/// significant bit; the final byte is padded with zeros and `HuffmanTree::bit_len` gives the exact
+/// number of bits. Building the tree takes O(k log k) for k distinct bytes, and encoding O(n).
+///
+/// Returns the encoded bytes and the tree needed to decode them.
+pub fn huffman_encode(data: &[u8]) -> (Vec<u8>, HuffmanTree) {
+    let mut counts = [0_u64; 256];
+    for &byte in data {
+        counts[byte as usize] += 1;
+    }
+    let frequencies = (0..=255_u8)
+        .filter(|&b| counts[b as usize] > 0)
+        .map(|b| (b, counts[b as usize]))
+        .collect();
+    // A code longer than 64 bits needs terabytes of data with Fibonacci-like byte frequencies
+    let tree = HuffmanTree::from_frequencies(frequencies).expect("the data is too large to encode");
+
+    let codes = tree.codes();
+    let mut encoded = Vec::with_capacity(tree.bit_len().div_ceil(8));
+    let mut bits = 0_usize;
+    for &byte in data {
+        for &bit in &codes[byte as usize] {
+            if bits.is_multiple_of(8) {
+                encoded.push(0);
+            }
+            if bit {
+                *encoded.last_mut().unwrap() |= 0x80 >> (bits % 8);
+            }
+            bits += 1;
+        }
+    }
+    (encoded, tree)
+}
+
+/// Decodes the first `bit_len` bits of `encoded` with the tree returned by `huffman_encode`.
+///
+/// Decoding walks the tree from the root, taking the left child on a `0` bit and the right child
+/// on a `1` bit, and emits a byte whenever it reaches a leaf. Trailing bits that do not complete a
+/// code are ignored. Runs in O(bit_len).
+pub fn huffman_decode(encoded: &[u8], tree: &HuffmanTree, bit_len: usize) -> Vec<u8> {
+    let bit_len = bit_len.min(8 * encoded.len());
+    let bit = |i: usize| encoded[i / 8] & (0x80 >> (i % 8)) != 0;
+    let root = match &tree.dictionary {
+        Some(dictionary) => &dictionary.root,
+        None => return Vec::new(),
+    };
+    if let Some(symbol) = root.symbol {
+        return vec![symbol; bit_len];
+    }
+    let mut decoded = Vec::new();
+    let mut node = root;
+    for i in 0..bit_len {
+        let child = if bit(i) { &node.right } else { &node.left };
+        node = child.as_ref().unwrap();
+        if let Some(symbol) = node.symbol {
+            decoded.push(symbol);
+            node = root;
+        }
+    }
+    decoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::general::HuffmanDictionary;
+
+    const TEXT: &str = "Lorem ipsum dolor sit amet, consectetur adipiscing elit, sed do eiusmod \
+                        tempor incididunt ut labore et dolore magna aliqua.";
+
+    fn round_trip(data: &[u8]) -> usize {
+        let (encoded, tree) = huffman_encode(data);
+        assert_eq!(encoded.len(), tree.bit_len().div_ceil(8));
+        assert_eq!(huffman_decode(&encoded, &tree, tree.bit_len()), data);
+        tree.bit_len()
+    }
+
+    #[test]
+    fn round_trip_text() {
+        let bits = round_trip(TEXT.as_bytes());
+        assert!(bits < 8 * TEXT.len());
+    }
+
+    #[test]
+    fn skewed_distribution_compresses_well() {
+        let mut data = vec![b'a'; 900];
+        data.extend(std::iter::repeat_n(b'b', 90));
+        data.extend(b"cdefghijkl");
+        let bits = round_trip(&data);
+        // 'a' gets a one-bit code, so the output is well below two bits per byte
+        assert!(bits < 2 * data.len());
+    }
+
+    #[test]
+    fn packs_the_dictionary_codes() {
+        let bytes = TEXT.as_bytes();
+        let (_, tree) = huffman_encode(bytes);
+        let dictionary = HuffmanDictionary::new(&tree.frequencies);
+        assert_eq!(tree.bit_len() as u64, dictionary.encode(bytes).num_bits);
+    }
+
+    #[test]
+    fn single_symbol_and_empty() {
+        assert_eq!(round_trip(b"zzzzz"), 5);
+        assert_eq!(round_trip(b"z"), 1);
+        assert_eq!(round_trip(b""), 0);
+    }
+
+    #[test]
+    fn serialized_tree() {
+        let (encoded, tree) = huffman_encode(TEXT.as_bytes());
+        let restored = HuffmanTree::deserialize(&tree.serialize()).unwrap();
+        assert_eq!(
+            huffman_decode(&encoded, &restored, restored.bit_len()),
+            TEXT.as_bytes()
+        );
+
+        assert!(HuffmanTree::deserialize(&[]).is_some());
+        assert!(HuffmanTree::deserialize(&[b'a', 1, 0]).is_none());
+        let zero = [b'a', 0, 0, 0, 0, 0, 0, 0, 0];
+        assert!(HuffmanTree::deserialize(&zero).is_none());
+    }
+
+    #[test]
+    fn overflowing_frequencies() {
+        let record = |symbol: u8, frequency: u64| {
+            let mut bytes = vec![symbol];
+            bytes.extend_from_slice(&frequency.to_le_bytes());
+            bytes
+        };
+        let mut bytes = record(b'a', u64::MAX);
+        bytes.extend(record(b'b', u64::MAX));
+        assert!(HuffmanTree::deserialize(&bytes).is_none());
+        // The total fits, but not the number of bits
+        let mut bytes = record(b'a', u64::MAX / 2);
+        bytes.extend(record(b'b', u64::MAX / 4));
+        bytes.extend(record(b'c', u64::MAX / 4));
+        assert!(HuffmanTree::deserialize(&bytes).is_none());
+        let mut bytes = record(b'a', u64::MAX / 4);
+        bytes.extend(record(b'b', u64::MAX / 4));
+        assert_eq!(
+            HuffmanTree::deserialize(&bytes).unwrap().bit_len() as u64,
+            u64::MAX / 4 * 2
+        );
+    }
+}
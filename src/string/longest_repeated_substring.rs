@@ -0,0 +1,153 @@
+/// Finds the longest substring that occurs at least twice in `s`, where the occurrences may
+/// overlap.
+///
+/// Every repeated substring is a common prefix of two suffixes, and the longest common prefixes
+/// are found between neighbours in sorted order. So the suffixes are sorted into a suffix array by
+/// prefix doubling, and Kasai's algorithm computes the longest common prefix of each suffix with
+/// the one before it in O(n), reusing all but one character of the previous prefix. The suffix
+/// array is built over bytes, and a common prefix that ends inside a multi-byte character is cut
+/// back to the last character boundary.
+///
+/// Takes O(n log^2 n) for the suffix array and O(n) for the rest.
+///
+/// Returns the lexicographically smallest of the longest repeated substrings, or `""` if no
+/// character repeats.
+pub fn longest_repeated_substring(s: &str) -> &str {
+    let bytes = s.as_bytes();
+    let suffixes = suffix_array(bytes);
+    let lcp = lcp_array(bytes, &suffixes);
+    let (mut start, mut len) = (0, 0);
+    for (i, &common) in lcp.iter().enumerate().skip(1) {
+        let suffix = suffixes[i];
+        // Neighbours that share a byte either both start on a character boundary or both not
+        if common <= len || !s.is_char_boundary(suffix) {
+            continue;
+        }
+        let mut common = common;
+        while !s.is_char_boundary(suffix + common) {
+            common -= 1;
+        }
+        if common > len {
+            start = suffix;
+            len = common;
+        }
+    }
+    &s[start..start + len]
+}
+
+/// Returns the starting positions of the suffixes of `s` in lexicographic order.
+///
+/// Sorts by the first `2k` bytes using the ranks of the first `k` bytes of each suffix and of the
+/// suffix `k` further on, doubling `k` until all ranks are distinct.
+fn suffix_array(s: &[u8]) -> Vec<usize> {
+    let n = s.len();
+    let mut suffixes: Vec<usize> = (0..n).collect();
+    let mut rank: Vec<usize> = s.iter().map(|&b| b as usize).collect();
+    let mut next = vec![0; n];
+    let mut k = 1;
+    while k < n {
+        // Suffixes shorter than k sort before all longer ones sharing their prefix
+        let key = |i: usize| (rank[i], rank.get(i + k).map_or(0, |&r| r + 1));
+        suffixes.sort_unstable_by_key(|&i| key(i));
+        next[suffixes[0]] = 0;
+        for w in 1..n {
+            let (a, b) = (suffixes[w - 1], suffixes[w]);
+            next[b] = next[a] + (key(a) != key(b)) as usize;
+        }
+        std::mem::swap(&mut rank, &mut next);
+        if rank[suffixes[n - 1]] == n - 1 {
+            break;
+        }
+        k *= 2;
+    }
+    suffixes
+}
+
+/// Computes with Kasai's algorithm the length of the longest common prefix of every suffix in
+/// `suffixes` with the one before it, or 0 for the first.
+fn lcp_array(s: &[u8], suffixes: &[usize]) -> Vec<usize> {
+    let n = s.len();
+    let mut rank = vec![0; n];
+    for (r, &i) in suffixes.iter().enumerate() {
+        rank[i] = r;
+    }
+    let mut lcp = vec![0; n];
+    let mut common = 0;
+    // Going from a suffix to the next shorter one loses at most its first byte of common prefix
+    for i in 0..n {
+        if rank[i] == 0 {
+            common = 0;
+            continue;
+        }
+        let j = suffixes[rank[i] - 1];
+        while i + common < n && j + common < n && s[i + common] == s[j + common] {
+            common += 1;
+        }
+        lcp[rank[i]] = common;
+        common = common.saturating_sub(1);
+    }
+    lcp
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{lcp_array, longest_repeated_substring, suffix_array};
+    use crate::math::PCG32;
+
+    #[test]
+    fn examples() {
+        assert_eq!(longest_repeated_substring("banana"), "ana");
+        assert_eq!(longest_repeated_substring("abcdefg"), "");
+        assert_eq!(longest_repeated_substring(""), "");
+        assert_eq!(longest_repeated_substring("a"), "");
+        assert_eq!(longest_repeated_substring("aaaa"), "aaa");
+        assert_eq!(longest_repeated_substring("abcXabcYabc"), "abc");
+        // Of the longest ones, the smallest comes first in the suffix array
+        assert_eq!(longest_repeated_substring("xyxyabab"), "ab");
+    }
+
+    #[test]
+    fn multi_byte_characters() {
+        assert_eq!(longest_repeated_substring("héllo, héllo"), "héllo");
+        // é and ê share their first byte, which is not a character on its own
+        assert_eq!(longest_repeated_substring("éê"), "");
+        assert_eq!(longest_repeated_substring("éxêx"), "x");
+        assert_eq!(longest_repeated_substring("日本日本"), "日本");
+    }
+
+    #[test]
+    fn suffix_and_lcp_arrays() {
+        let s = b"banana";
+        let suffixes = suffix_array(s);
+        assert_eq!(suffixes, vec![5, 3, 1, 0, 4, 2]);
+        assert_eq!(lcp_array(s, &suffixes), vec![0, 1, 3, 0, 0, 2]);
+        assert!(suffix_array(b"").is_empty());
+    }
+
+    #[test]
+    fn matches_brute_force() {
+        let mut rng = PCG32::new_default(271);
+        for len in 0..40 {
+            let s: String = (0..len)
+                .map(|_| (b'a' + (rng.get_u32() % 3) as u8) as char)
+                .collect();
+            let mut suffixes: Vec<usize> = (0..len).collect();
+            suffixes.sort_by_key(|&i| &s[i..]);
+            assert_eq!(suffix_array(s.as_bytes()), suffixes);
+
+            let longest = (0..len)
+                .flat_map(|i| (i + 1..len).map(move |j| (i, j)))
+                .map(|(i, j)| {
+                    let (a, b) = (&s.as_bytes()[i..], &s.as_bytes()[j..]);
+                    a.iter().zip(b).take_while(|(x, y)| x == y).count()
+                })
+                .max()
+                .unwrap_or(0);
+            let found = longest_repeated_substring(&s);
+            assert_eq!(found.len(), longest);
+            if longest > 0 {
+                assert_ne!(s.find(found), s.rfind(found));
+            }
+        }
+    }
+}
@@ -0,0 +1,87 @@
+use std::collections::HashMap;
+
+/// Finds the shortest substring of `s` that contains every character of `t`, counting repeated
+/// characters of `t` with their multiplicity.
+///
+/// Uses a sliding window over the characters of `s` that grows until it covers `t` and then
+/// shrinks from the left as far as possible, which takes O(n + m) time. When several windows have
+/// the minimum length, the leftmost one is returned. An empty `t` is covered by the empty string.
+pub fn min_window<'a>(s: &'a str, t: &str) -> Option<&'a str> {
+    if t.is_empty() {
+        return Some("");
+    }
+
+    // How many more of each character of `t` the window needs; negative values are a surplus
+    let mut needed: HashMap<char, i64> = HashMap::new();
+    for c in t.chars() {
+        *needed.entry(c).or_default() += 1;
+    }
+    // Number of characters of `t` (with multiplicity) not yet covered by the window
+    let mut missing = t.chars().count();
+    let mut best: Option<(usize, usize)> = None;
+
+    let chars: Vec<(usize, char)> = s.char_indices().collect();
+    let mut left = 0;
+    for (right, &(_, c)) in chars.iter().enumerate() {
+        if let Some(count) = needed.get_mut(&c) {
+            if *count > 0 {
+                missing -= 1;
+            }
+            *count -= 1;
+        }
+        if missing > 0 {
+            continue;
+        }
+        // Shrink from the left while the window still covers `t`
+        while left <= right {
+            let c = chars[left].1;
+            match needed.get_mut(&c) {
+                Some(count) if *count == 0 => break,
+                Some(count) => *count += 1,
+                None => {}
+            }
+            left += 1;
+        }
+        let start = chars[left].0;
+        let end = chars[right].0 + chars[right].1.len_utf8();
+        if best.is_none_or(|(best_start, best_end)| end - start < best_end - best_start) {
+            best = Some((start, end));
+        }
+    }
+
+    best.map(|(start, end)| &s[start..end])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn classic() {
+        assert_eq!(min_window("ADOBECODEBANC", "ABC"), Some("BANC"));
+    }
+
+    #[test]
+    fn multiplicity() {
+        assert_eq!(min_window("aa", "aa"), Some("aa"));
+        assert_eq!(min_window("abcaab", "aab"), Some("aab"));
+        assert_eq!(min_window("a", "aa"), None);
+    }
+
+    #[test]
+    fn no_window() {
+        assert_eq!(min_window("ADOBECODEBANC", "XYZ"), None);
+        assert_eq!(min_window("", "a"), None);
+    }
+
+    #[test]
+    fn empty_pattern() {
+        assert_eq!(min_window("ADOBECODEBANC", ""), Some(""));
+        assert_eq!(min_window("", ""), Some(""));
+    }
+
+    #[test]
+    fn unicode() {
+        assert_eq!(min_window("äxxöyä", "öä"), Some("öyä"));
+    }
+}
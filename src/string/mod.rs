@@ -1,21 +1,40 @@
 //! This module provides string manipulation algorithms.
 mod aho_corasick;
 mod burrows_wheeler_transform;
+mod damerau_levenshtein;
+mod fuzzy_match;
 mod hamming_distance;
+mod huffman;
 mod knuth_morris_pratt;
+mod longest_repeated_substring;
 mod manacher;
+mod minimum_window_substring;
 mod naive;
+mod parse_number;
 mod rabin_karp;
+mod regex_lite;
 mod reverse;
+mod suffix_tree;
+mod tokenize;
 mod z_algorithm;
 
 pub use self::aho_corasick::AhoCorasick;
-pub use self::burrows_wheeler_transform::burrows_wheeler_transform;
-pub use self::burrows_wheeler_transform::inv_burrows_wheeler_transform;
+pub use self::burrows_wheeler_transform::{
+    burrows_wheeler_transform, bwt_inverse, bwt_transform, inv_burrows_wheeler_transform,
+};
+pub use self::damerau_levenshtein::damerau_levenshtein;
+pub use self::fuzzy_match::fuzzy_match;
 pub use self::hamming_distance::hamming_distance;
+pub use self::huffman::{huffman_decode, huffman_encode, HuffmanTree};
 pub use self::knuth_morris_pratt::knuth_morris_pratt;
+pub use self::longest_repeated_substring::longest_repeated_substring;
 pub use self::manacher::manacher;
+pub use self::minimum_window_substring::min_window;
 pub use self::naive::naive;
+pub use self::parse_number::parse_number;
 pub use self::rabin_karp::rabin_karp;
+pub use self::regex_lite::regex_match;
 pub use self::reverse::reverse;
+pub use self::suffix_tree::SuffixTree;
+pub use self::tokenize::tokenize;
 pub use self::z_algorithm::{match_pattern, z_array};
@@ -0,0 +1,98 @@
+/// Parses a decimal number with an optional sign, fraction and exponent, such as `-3.2e-4`.
+///
+/// Either the integer or the fractional part may be left out, as in `.5` or `5.`, but not both.
+/// Like in Rust literals, the digits of each part may be separated by underscores, as in
+/// `1_000.5`, as long as every part starts with a digit. Anything else, including surrounding
+/// whitespace, `inf` and `NaN`, is rejected.
+///
+/// The input is validated in a single pass while copying it without the underscores, and the
+/// copy is then converted with the standard library, which rounds correctly.
+///
+/// Returns the parsed number, or `None` if `s` is not a well-formed number.
+pub fn parse_number(s: &str) -> Option<f64> {
+    let bytes = s.as_bytes();
+    let mut digits = String::with_capacity(s.len());
+    let mut i = 0;
+
+    if let Some(&sign @ (b'+' | b'-')) = bytes.first() {
+        digits.push(sign as char);
+        i += 1;
+    }
+    let integer = digit_group(bytes, &mut i, &mut digits);
+    let mut fraction = 0;
+    if bytes.get(i) == Some(&b'.') {
+        digits.push('.');
+        i += 1;
+        fraction = digit_group(bytes, &mut i, &mut digits);
+    }
+    if integer == 0 && fraction == 0 {
+        return None;
+    }
+    if let Some(b'e' | b'E') = bytes.get(i) {
+        digits.push('e');
+        i += 1;
+        if let Some(&sign @ (b'+' | b'-')) = bytes.get(i) {
+            digits.push(sign as char);
+            i += 1;
+        }
+        if digit_group(bytes, &mut i, &mut digits) == 0 {
+            return None;
+        }
+    }
+    if i != bytes.len() {
+        return None;
+    }
+    digits.parse().ok()
+}
+
+/// Copies the digits starting at `bytes[*i]` into `digits`, skipping the underscores between and
+/// after them, and advances `i` past them.
+///
+/// Returns the number of digits, which is 0 if the group does not start with a digit.
+fn digit_group(bytes: &[u8], i: &mut usize, digits: &mut String) -> usize {
+    let mut count = 0;
+    while let Some(&b) = bytes.get(*i) {
+        match b {
+            b'0'..=b'9' => {
+                digits.push(b as char);
+                count += 1;
+            }
+            b'_' if count > 0 => {}
+            _ => break,
+        }
+        *i += 1;
+    }
+    count
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_number;
+
+    #[test]
+    fn accepted() {
+        assert_eq!(parse_number("1_000.5"), Some(1000.5));
+        assert_eq!(parse_number("-3.2e-4"), Some(-3.2e-4));
+        assert_eq!(parse_number("+.5"), Some(0.5));
+        assert_eq!(parse_number("42"), Some(42.0));
+        assert_eq!(parse_number("5."), Some(5.0));
+        assert_eq!(parse_number("-0"), Some(-0.0));
+        assert_eq!(parse_number("1E3"), Some(1000.0));
+        assert_eq!(parse_number("2.5e+1_0"), Some(2.5e10));
+        assert_eq!(parse_number("1__0_"), Some(10.0));
+        assert_eq!(parse_number("0.1"), Some(0.1));
+        assert_eq!(parse_number("1e400"), Some(f64::INFINITY));
+    }
+
+    #[test]
+    fn rejected() {
+        for s in [
+            "", "1.2.3", "+", "-", ".", "+.", "e5", ".e5", "1e", "1e+", "1.5e-", "_1", "1._5",
+            "1e_5", "1_000,5", " 1", "1 ", "--1", "+-1", "0x10", "inf", "NaN", "1e5.0", "1f",
+        ]
+        .iter()
+        {
+            assert_eq!(parse_number(s), None, "{:?} should be rejected", s);
+        }
+    }
+}
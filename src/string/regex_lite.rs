@@ -0,0 +1,75 @@
+/// Matches `text` against a pattern of literal characters, `.` and `*`.
+///
+/// `.` matches any single character and `*` matches zero or more repetitions of the character or
+/// `.` right before it. The pattern has to match the entire text rather than a substring of it,
+/// as if it were anchored with `^` and `$`.
+///
+/// `matches[i][j]` records whether the first `i` characters of the text match the first `j`
+/// characters of the pattern, which is filled in row by row in O(n * m) time.
+///
+/// # Panics
+///
+/// Panics if a `*` does not follow a character or `.`, such as at the start of the pattern or
+/// right after another `*`.
+pub fn regex_match(text: &str, pattern: &str) -> bool {
+    let text: Vec<char> = text.chars().collect();
+    let pattern: Vec<char> = pattern.chars().collect();
+    for (j, &p) in pattern.iter().enumerate() {
+        assert!(
+            p != '*' || (j > 0 && pattern[j - 1] != '*'),
+            "`*` must follow a character or `.`"
+        );
+    }
+
+    let single = |c: char, p: char| p == '.' || p == c;
+    let mut matches = vec![vec![false; pattern.len() + 1]; text.len() + 1];
+    matches[0][0] = true;
+    for i in 0..=text.len() {
+        for j in 1..=pattern.len() {
+            matches[i][j] = if pattern[j - 1] == '*' {
+                // Either skip the starred element, or let it consume one more character
+                matches[i][j - 2]
+                    || (i > 0 && single(text[i - 1], pattern[j - 2]) && matches[i - 1][j])
+            } else {
+                i > 0 && single(text[i - 1], pattern[j - 1]) && matches[i - 1][j - 1]
+            };
+        }
+    }
+    matches[text.len()][pattern.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::regex_match;
+
+    #[test]
+    fn stars_and_dots() {
+        assert!(regex_match("aab", "c*a*b"));
+        assert!(!regex_match("mississippi", "mis*is*p*."));
+        assert!(regex_match("mississippi", "mis*is*ip*."));
+        assert!(regex_match("ab", ".*"));
+        assert!(regex_match("abc", "a.c"));
+        assert!(!regex_match("ac", "a.c"));
+        assert!(regex_match("aaa", "a*a"));
+        assert!(regex_match("", "x*.*y*"));
+    }
+
+    #[test]
+    fn matches_entire_text() {
+        // A match of a prefix, suffix or substring is not enough
+        assert!(!regex_match("aa", "a"));
+        assert!(!regex_match("abc", "bc"));
+        assert!(!regex_match("abc", "b"));
+        assert!(!regex_match("a", "ab*c"));
+        assert!(regex_match("", ""));
+        assert!(!regex_match("a", ""));
+        assert!(!regex_match("", "."));
+        assert!(regex_match("héllo", "h.l*o"));
+    }
+
+    #[test]
+    #[should_panic(expected = "`*` must follow a character or `.`")]
+    fn leading_star() {
+        regex_match("a", "*a");
+    }
+}
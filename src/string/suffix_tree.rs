@@ -0,0 +1,266 @@
+use std::collections::BTreeMap;
+
+/// The symbol appended to the text to terminate it, which differs from every `char`.
+///
+/// `char` values are at most `0x10FFFF`, so `u32::MAX` never occurs in the text itself.
+const TERMINAL: u32 = u32::MAX;
+
+/// A node of a `SuffixTree`, labelled by the edge `text[start..end]` leading into it.
+struct Node {
+    start: usize,
+    end: usize,
+    /// The suffix link, pointing to the node for this node's label without its first symbol.
+    link: usize,
+    children: BTreeMap<u32, usize>,
+    /// The number of leaves in this node's subtree, which is the number of suffixes below it.
+    leaves: usize,
+}
+
+impl Node {
+    fn new(start: usize, end: usize) -> Self {
+        Node {
+            start,
+            end,
+            link: 0,
+            children: BTreeMap::new(),
+            leaves: 0,
+        }
+    }
+}
+
+/// A suffix tree of a text, answering substring queries in time proportional to the query.
+///
+/// The tree is a compressed trie of all suffixes of the text, built with Ukkonen's online
+/// algorithm in O(n log σ) for an alphabet of σ distinct characters, which is O(n) for a fixed
+/// alphabet. The text is read as `char`s, followed by a terminal symbol that is distinct from
+/// every `char`. Because of it no suffix is a prefix of another, so every suffix ends in its own
+/// leaf, and any `&str` can be indexed without reserving a sentinel character such as `$`.
+///
+/// # Examples
+///
+/// ```rust
+/// use rust_algorithms::string::SuffixTree;
+///
+/// let tree = SuffixTree::new("banana");
+///
+/// assert!(tree.contains("nan"));
+/// assert!(!tree.contains("nab"));
+/// assert_eq!(tree.count_occurrences("ana"), 2);
+/// ```
+pub struct SuffixTree {
+    text: Vec<u32>,
+    nodes: Vec<Node>,
+}
+
+impl SuffixTree {
+    /// Builds the suffix tree of `text`.
+    pub fn new(text: &str) -> Self {
+        let mut symbols: Vec<u32> = text.chars().map(|c| c as u32).collect();
+        symbols.push(TERMINAL);
+        let mut tree = SuffixTree {
+            text: symbols,
+            nodes: vec![Node::new(0, 0)],
+        };
+        tree.build();
+        tree.count_leaves();
+        tree
+    }
+
+    /// Runs Ukkonen's algorithm, extending the tree of the prefix `text[..i]` by `text[i]` for
+    /// every `i`.
+    ///
+    /// Leaves are created with the end of the whole text, so they grow implicitly with each step.
+    /// The active point tracks where the longest suffix still present implicitly ends, and
+    /// `remainder` counts the suffixes that yet have to be inserted explicitly.
+    fn build(&mut self) {
+        let n = self.text.len();
+        let (mut active_node, mut active_edge, mut active_length) = (0, 0, 0);
+        let mut remainder = 0;
+        for i in 0..n {
+            let symbol = self.text[i];
+            remainder += 1;
+            let mut last_internal: Option<usize> = None;
+            while remainder > 0 {
+                if active_length == 0 {
+                    active_edge = i;
+                }
+                let edge = self.text[active_edge];
+                match self.nodes[active_node].children.get(&edge) {
+                    None => {
+                        let leaf = self.add_node(i, n);
+                        self.nodes[active_node].children.insert(edge, leaf);
+                        if let Some(node) = last_internal.take() {
+                            self.nodes[node].link = active_node;
+                        }
+                    }
+                    Some(&next) => {
+                        let length = self.nodes[next].end.min(i + 1) - self.nodes[next].start;
+                        if active_length >= length {
+                            // Walk down to the child and continue from there
+                            active_edge += length;
+                            active_length -= length;
+                            active_node = next;
+                            continue;
+                        }
+                        if self.text[self.nodes[next].start + active_length] == symbol {
+                            // The suffix is already present implicitly, as are all shorter ones
+                            if let Some(node) = last_internal.take() {
+                                self.nodes[node].link = active_node;
+                            }
+                            active_length += 1;
+                            break;
+                        }
+                        // Split the edge and hang a new leaf off the middle
+                        let start = self.nodes[next].start;
+                        let split = self.add_node(start, start + active_length);
+                        self.nodes[active_node].children.insert(edge, split);
+                        let leaf = self.add_node(i, n);
+                        self.nodes[split].children.insert(symbol, leaf);
+                        self.nodes[next].start += active_length;
+                        let rest = self.text[self.nodes[next].start];
+                        self.nodes[split].children.insert(rest, next);
+                        if let Some(node) = last_internal.replace(split) {
+                            self.nodes[node].link = split;
+                        }
+                    }
+                }
+                remainder -= 1;
+                if active_node == 0 && active_length > 0 {
+                    active_length -= 1;
+                    active_edge = i + 1 - remainder;
+                } else if active_node != 0 {
+                    active_node = self.nodes[active_node].link;
+                }
+            }
+        }
+    }
+
+    fn add_node(&mut self, start: usize, end: usize) -> usize {
+        self.nodes.push(Node::new(start, end));
+        self.nodes.len() - 1
+    }
+
+    /// Fills in the number of leaves below every node.
+    fn count_leaves(&mut self) {
+        // Nodes in depth-first preorder, so that children come after their parents
+        let mut order = Vec::with_capacity(self.nodes.len());
+        let mut stack = vec![0];
+        while let Some(node) = stack.pop() {
+            order.push(node);
+            stack.extend(self.nodes[node].children.values());
+        }
+        for &node in order.iter().rev() {
+            let children = &self.nodes[node].children;
+            let leaves = if children.is_empty() {
+                1
+            } else {
+                children.values().map(|&c| self.nodes[c].leaves).sum()
+            };
+            self.nodes[node].leaves = leaves;
+        }
+    }
+
+    /// Returns the topmost node whose label starts with `pattern`, if `pattern` occurs at all.
+    fn locate(&self, pattern: &str) -> Option<usize> {
+        let mut node = 0;
+        let mut offset = 0;
+        let mut end = 0;
+        for c in pattern.chars() {
+            if offset == end {
+                node = *self.nodes[node].children.get(&(c as u32))?;
+                offset = self.nodes[node].start;
+                end = self.nodes[node].end;
+            }
+            if self.text[offset] != c as u32 {
+                return None;
+            }
+            offset += 1;
+        }
+        Some(node)
+    }
+
+    /// Returns `true` if `pattern` is a substring of the text.
+    ///
+    /// Takes O(m log σ) for a pattern of m characters.
+    pub fn contains(&self, pattern: &str) -> bool {
+        self.locate(pattern).is_some()
+    }
+
+    /// Returns the number of possibly overlapping occurrences of `pattern` in the text.
+    ///
+    /// Every occurrence starts one suffix, so this is the number of leaves below the point where
+    /// `pattern` ends. That count is precomputed for every node, which makes this take
+    /// O(m log σ) like `contains`. The empty pattern occurs once at every position of the text,
+    /// including its end.
+    pub fn count_occurrences(&self, pattern: &str) -> usize {
+        self.locate(pattern)
+            .map_or(0, |node| self.nodes[node].leaves)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SuffixTree;
+
+    fn brute_force_count(text: &str, pattern: &str) -> usize {
+        let text: Vec<char> = text.chars().collect();
+        let pattern: Vec<char> = pattern.chars().collect();
+        (0..=text.len())
+            .filter(|&i| text[i..].starts_with(&pattern))
+            .count()
+    }
+
+    #[test]
+    fn banana() {
+        let tree = SuffixTree::new("banana");
+        assert!(tree.contains("ana"));
+        assert!(tree.contains("banana"));
+        assert_eq!(tree.count_occurrences("a"), 3);
+        assert_eq!(tree.count_occurrences("ana"), 2);
+        assert_eq!(tree.count_occurrences("nana"), 1);
+        assert!(!tree.contains("bananas"));
+        assert!(!tree.contains("nab"));
+        assert_eq!(tree.count_occurrences("x"), 0);
+    }
+
+    #[test]
+    fn empty_text_and_pattern() {
+        let tree = SuffixTree::new("");
+        assert!(tree.contains(""));
+        assert!(!tree.contains("a"));
+        assert_eq!(tree.count_occurrences(""), 1);
+        assert_eq!(SuffixTree::new("abc").count_occurrences(""), 4);
+    }
+
+    #[test]
+    fn matches_brute_force() {
+        let texts = ["mississippi", "abababbababaabbab", "aaaaaaaa", "abcabxabcd"];
+        let patterns = [
+            "a", "ab", "aba", "abab", "ss", "issi", "aaa", "abc", "bx", "dd",
+        ];
+        for text in texts.iter() {
+            let tree = SuffixTree::new(text);
+            for pattern in patterns.iter() {
+                assert_eq!(
+                    tree.count_occurrences(pattern),
+                    brute_force_count(text, pattern),
+                    "{:?} in {:?}",
+                    pattern,
+                    text
+                );
+                assert_eq!(tree.contains(pattern), text.contains(pattern));
+            }
+            for start in 0..text.len() {
+                assert!(tree.contains(&text[start..]));
+            }
+        }
+    }
+
+    #[test]
+    fn unicode() {
+        let tree = SuffixTree::new("čaj, čaj a káva");
+        assert_eq!(tree.count_occurrences("čaj"), 2);
+        assert!(tree.contains("á"));
+        assert!(!tree.contains("čaja"));
+    }
+}
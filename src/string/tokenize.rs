@@ -0,0 +1,75 @@
+/// Splits `input` on any of the `delimiters`, treating text between a pair of `quote` characters
+/// as a single span in which delimiters are kept literally.
+///
+/// Inside a quoted span, a doubled quote (e.g. `""`) produces one literal quote character. The
+/// surrounding quotes are not part of the resulting token. Adjacent delimiters yield empty tokens,
+/// like in CSV, while an empty input yields no tokens at all.
+pub fn tokenize(input: &str, delimiters: &[char], quote: char) -> Vec<String> {
+    let mut tokens = Vec::new();
+    if input.is_empty() {
+        return tokens;
+    }
+
+    let mut current = String::new();
+    let mut in_quotes = false;
+    let mut chars = input.chars().peekable();
+    while let Some(c) = chars.next() {
+        if in_quotes {
+            if c == quote {
+                if chars.peek() == Some(&quote) {
+                    // escaped quote
+                    current.push(quote);
+                    chars.next();
+                } else {
+                    in_quotes = false;
+                }
+            } else {
+                current.push(c);
+            }
+        } else if c == quote {
+            in_quotes = true;
+        } else if delimiters.contains(&c) {
+            tokens.push(std::mem::take(&mut current));
+        } else {
+            current.push(c);
+        }
+    }
+    tokens.push(current);
+    tokens
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quoted_delimiter() {
+        assert_eq!(
+            tokenize("a,b,\"c,d\",e", &[','], '"'),
+            vec!["a", "b", "c,d", "e"]
+        );
+    }
+
+    #[test]
+    fn escaped_quote() {
+        assert_eq!(
+            tokenize("say,\"he said \"\"hi\"\"\",done", &[','], '"'),
+            vec!["say", "he said \"hi\"", "done"]
+        );
+    }
+
+    #[test]
+    fn multiple_delimiters() {
+        assert_eq!(
+            tokenize("echo 'hello world'\tnow", &[' ', '\t'], '\''),
+            vec!["echo", "hello world", "now"]
+        );
+    }
+
+    #[test]
+    fn empty_tokens() {
+        assert_eq!(tokenize("a,,b,", &[','], '"'), vec!["a", "", "b", ""]);
+        assert_eq!(tokenize("\"\"", &[','], '"'), vec![""]);
+        assert!(tokenize("", &[','], '"').is_empty());
+    }
+}